@@ -1,4 +1,5 @@
 import { useWindowFocus, useIdle } from '@vueuse/core'
+import { setPollActive } from '~/utils/bd-api'
 
 /**
  * Adaptive polling composable that adjusts poll interval based on window state.
@@ -133,9 +134,11 @@ export function useAdaptivePolling(pollFn: () => Promise<void>, options?: Adapti
       wasHidden = true
       if (timer) { clearTimeout(timer); timer = null }
       clearCheckTimer()
+      void setPollActive(false)
     } else if (wasHidden) {
       // Returning from hidden — immediate poll + resume both loops
       wasHidden = false
+      void setPollActive(true)
       runPoll().finally(() => {
         scheduleNext()
         scheduleCheck()
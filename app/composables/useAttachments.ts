@@ -2,7 +2,7 @@ import { invoke } from '@tauri-apps/api/core'
 
 export interface AttachmentFile {
   filename: string
-  fileType: string   // "image" or "markdown"
+  fileType: string   // "image", "markdown" or "other"
   path: string       // absolute path
   modified: number   // mtime epoch seconds
 }
@@ -13,7 +13,7 @@ const cache = new Map<string, AttachmentFile[]>()
 export function useAttachments() {
   const { beadsPath } = useBeadsPath()
 
-  async function listAttachments(issueId: string): Promise<{ images: AttachmentFile[], markdown: AttachmentFile[] }> {
+  async function listAttachments(issueId: string): Promise<{ images: AttachmentFile[], markdown: AttachmentFile[], other: AttachmentFile[] }> {
     const cacheKey = `${beadsPath.value}:${issueId}`
     let files = cache.get(cacheKey)
 
@@ -22,6 +22,7 @@ export function useAttachments() {
         files = await invoke<AttachmentFile[]>('list_attachments', {
           projectPath: beadsPath.value || '.',
           issueId,
+          includeAllTypes: true,
         })
         cache.set(cacheKey, files)
       } catch {
@@ -32,6 +33,7 @@ export function useAttachments() {
     return {
       images: files.filter(f => f.fileType === 'image'),
       markdown: files.filter(f => f.fileType === 'markdown'),
+      other: files.filter(f => f.fileType === 'other'),
     }
   }
 
@@ -1,4 +1,4 @@
-import { fsExists } from '~/utils/bd-api'
+import { fsExists, setFavoriteProjects } from '~/utils/bd-api'
 import { getFolderName } from '~/utils/path'
 import { useNotification } from '~/composables/useNotification'
 import type { Project, ProjectSortMode } from '~/utils/favorites-helpers'
@@ -108,6 +108,14 @@ export function useProjects() {
       },
       { deep: true }
     )
+    // Let the backend auto-sync favorites that haven't been opened yet
+    watch(
+      () => projects.value.map(p => p.path),
+      (paths) => {
+        setFavoriteProjects(paths).catch(() => {})
+      },
+      { immediate: true }
+    )
   }
 
   const sortedProjects = computed<Project[]>(() => {
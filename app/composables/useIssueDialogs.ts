@@ -1,5 +1,5 @@
 import type { Issue, ChildIssue } from '~/types/issue'
-import { bdAvailableRelationTypes, checkBdCompatibility } from '~/utils/bd-api'
+import { bdAvailableRelationTypes, checkBdCompatibility, parseOpenChildrenError } from '~/utils/bd-api'
 
 // Singleton state — shared across all callers
 
@@ -186,7 +186,7 @@ export function useIssueDialogs() {
 
     for (const sourcePath of filePaths) {
       try {
-        await invoke<string>('copy_file_to_attachments', {
+        await invoke<{ filename: string, deduplicated: boolean }>('copy_file_to_attachments', {
           projectPath: beadsPath.value,
           sourcePath,
           issueId: selectedIssue.value.id,
@@ -281,14 +281,32 @@ export function useIssueDialogs() {
     }
   }
 
+  // The backend refused a delete because of open children the list didn't show:
+  // ask whether to delete them too or detach them, then carry on with `remaining`
+  const promptForOpenChildren = (id: string, remaining: string[]): boolean => {
+    const childIds = parseOpenChildrenError(issueError.value)
+    const issue = issues.value.find(i => i.id === id) ?? (selectedIssue.value?.id === id ? selectedIssue.value : null)
+    if (!childIds || !issue) return false
+    epicToDelete.value = issue
+    epicChildren.value = childIds.map((childId) => {
+      const child = issues.value.find(i => i.id === childId)
+      return { id: childId, title: child?.title ?? childId, status: child?.status ?? 'open', priority: child?.priority ?? 'p2' }
+    })
+    remainingDeleteIds.value = remaining
+    isEpicDeleteDialogOpen.value = true
+    return true
+  }
+
   const confirmDelete = async () => {
     isDeleting.value = true
     try {
       if (multiSelectMode.value && selectedIds.value.length > 0) {
         const successfullyDeleted: string[] = []
-        for (const id of selectedIds.value) {
+        const ids = [...selectedIds.value]
+        for (const [index, id] of ids.entries()) {
           const success = await deleteIssue(id)
           if (!success) {
+            if (promptForOpenChildren(id, ids.slice(index + 1))) break
             notifyError('Failed to delete issue', issueError.value || `Could not delete ${id}`)
           } else {
             successfullyDeleted.push(id)
@@ -303,7 +321,9 @@ export function useIssueDialogs() {
         const issueTitle = selectedIssue.value.title
         const success = await deleteIssue(issueId)
         if (!success) {
-          notifyError('Failed to delete issue', issueError.value || 'Could not delete the issue')
+          if (!promptForOpenChildren(issueId, [])) {
+            notifyError('Failed to delete issue', issueError.value || 'Could not delete the issue')
+          }
         } else {
           isEditMode.value = false
           isCreatingNew.value = false
@@ -327,17 +347,12 @@ export function useIssueDialogs() {
         for (const child of epicChildren.value) {
           await updateIssue(child.id, { parent: '' })
         }
-      } else {
-        for (const child of epicChildren.value) {
-          const success = await deleteIssue(child.id)
-          if (!success) {
-            notifyError('Failed to delete child issue', issueError.value || `Could not delete ${child.id}`)
-          }
-        }
       }
       const epicId = epicToDelete.value.id
       const epicTitle = epicToDelete.value.title
-      const epicSuccess = await deleteIssue(epicId)
+      // The user chose what happens to the children: cascade deletes the whole subtree,
+      // force lets the delete through once they're detached
+      const epicSuccess = await deleteIssue(epicId, mode === 'delete-all' ? { cascade: true } : { force: true })
       if (!epicSuccess) {
         notifyError('Failed to delete issue', issueError.value || `Could not delete ${epicId}`)
       } else {
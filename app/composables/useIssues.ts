@@ -1,5 +1,5 @@
 import type { Issue, CreateIssuePayload, UpdateIssuePayload } from '~/types/issue'
-import { bdList, bdCount, bdShow, bdCreate, bdUpdate, bdClose, bdDelete, bdAddComment, bdAddDependency, bdRemoveDependency, bdAddRelation, bdRemoveRelation, bdPurgeOrphanAttachments, bdPollData, bdSearch, bdLabelAdd, bdLabelRemove, type BdListOptions, type PollData } from '~/utils/bd-api'
+import { bdListWithWarnings, bdCount, bdShow, bdCreate, bdUpdate, bdClose, bdDelete, bdAddComment, bdAddDependency, bdRemoveDependency, bdAddRelation, bdRemoveRelation, bdPurgeOrphanAttachments, bdPollData, mergePollDelta, parseMergeConflictError, parseCreatePartialError, isNotFoundError, bdSearch, bdLabelAdd, bdLabelRemove, type BdListOptions, type DeleteChildrenOptions, type PollData, type SkippedIssue } from '~/utils/bd-api'
 import { useProjectStorage } from '~/composables/useProjectStorage'
 import {
   deduplicateIssues,
@@ -23,6 +23,9 @@ export interface IssueGroup {
   inProgressChild?: { id: string; title: string; priority: string }
 }
 
+// Ask the backend to gzip poll payloads once a project gets this big
+const COMPRESSED_POLL_MIN_ISSUES = 2000
+
 // Shared state across all components (singleton pattern)
 const issues = ref<Issue[]>([])
 const selectedIssue = ref<Issue | null>(null)
@@ -32,6 +35,24 @@ const error = ref<string | null>(null)
 
 // Polling state for change detection
 const lastKnownCount = ref<number>(0)
+// Last complete poll (pristine copy) that delta polls are merged into
+let lastPoll: { path: string | undefined, data: PollData } | null = null
+
+// Issue ids inside git conflict hunks of issues.jsonl, as of the last poll (null: no conflict)
+const mergeConflictIds = ref<string[] | null>(null)
+
+// Issues the CLI returned that could not be parsed, as of the last load
+const parseWarnings = ref<SkippedIssue[]>([])
+
+// Drop locally deleted issues from the delta base, or the next merge brings them back
+function forgetPolledIssues(ids: string[]) {
+  if (!lastPoll) return
+  const gone = new Set(ids)
+  const keep = (list: Issue[]) => list.filter(i => !gone.has(i.id))
+  lastPoll.data.openIssues = keep(lastPoll.data.openIssues)
+  lastPoll.data.closedIssues = keep(lastPoll.data.closedIssues)
+  lastPoll.data.readyIssues = keep(lastPoll.data.readyIssues)
+}
 const lastKnownUpdated = ref<string | null>(null)
 
 // Track newly added issue IDs for flash animation
@@ -78,6 +99,40 @@ export function useEpicExpand() {
   return { isEpicExpanded, toggleEpicExpand, expandEpic }
 }
 
+/**
+ * Track the poll's merge conflict state; notify once each time a (different) conflict appears.
+ */
+function reportMergeConflict(conflict: string | undefined) {
+  const ids = conflict ? parseMergeConflictError(conflict) : null
+  const previous = mergeConflictIds.value
+  mergeConflictIds.value = ids
+  if (!ids || (previous && previous.join(',') === ids.join(','))) return
+  const { error: notifyError } = useNotification()
+  notifyError(
+    'issues.jsonl has unresolved merge conflicts',
+    ids.length ? `Conflicting issues: ${ids.join(', ')}. Resolve them, then sync again.` : 'Resolve them, then sync again.',
+    15000,
+  )
+}
+
+/**
+ * Track unparseable issues; warn once each time the set of skipped issues changes.
+ */
+function reportParseWarnings(warnings: SkippedIssue[] | undefined) {
+  const next = warnings ?? []
+  const key = (list: SkippedIssue[]) => list.map(w => w.id ?? `#${w.index}`).join(',')
+  const changed = key(next) !== key(parseWarnings.value)
+  parseWarnings.value = next
+  if (!next.length || !changed) return
+  const { warning: notifyWarning } = useNotification()
+  const ids = next.map(w => w.id).filter(Boolean)
+  notifyWarning(
+    next.length === 1 ? '1 issue could not be displayed' : `${next.length} issues could not be displayed`,
+    ids.length ? `Unreadable CLI output for: ${ids.join(', ')}` : 'The CLI returned entries that could not be read.',
+    10000,
+  )
+}
+
 /**
  * Detect and notify status transitions (close, reopen, delete) between two issue snapshots.
  */
@@ -128,7 +183,8 @@ export function useIssues() {
       // Single call with --all to get all issues (bd >= 0.55 fixed the --all flag)
       const path = getPath()
 
-      const allIssues = await bdList({ path, includeAll: true })
+      const { issues: allIssues, parseWarnings: skipped } = await bdListWithWarnings({ path, includeAll: true })
+      reportParseWarnings(skipped)
       const newIssues = deduplicateIssues(allIssues || [])
 
       // Build parent-child relationships from the data we already have (no bdShow needed)
@@ -254,7 +310,13 @@ export function useIssues() {
     error.value = null
     try {
       const path = getPath()
-      const data = await bdPollData(path)
+      const previous = lastPoll?.path === path && lastPoll.data.highWatermark ? lastPoll.data : null
+      const polled = await bdPollData(path, true, issues.value.length >= COMPRESSED_POLL_MIN_ISSUES, previous?.highWatermark)
+      const data = previous ? mergePollDelta(previous, polled) : polled
+      // A failed bucket leaves holes in the lists: start over with a full poll next time
+      lastPoll = data.errors?.length ? null : { path, data: structuredClone(data) }
+      reportMergeConflict(data.mergeConflict)
+      reportParseWarnings(data.parseWarnings)
 
       const mergedIssues = [...(data.openIssues || []), ...(data.closedIssues || [])]
       const newIssues = deduplicateIssues(mergedIssues)
@@ -373,6 +435,7 @@ export function useIssues() {
 
       return data.readyIssues || []
     } catch (e) {
+      lastPoll = null
       if (checkMigrateError(e, beadsPath.value)) {
         error.value = 'Database needs migration to Dolt backend.'
       } else if (checkRepairError(e)) {
@@ -457,12 +520,6 @@ export function useIssues() {
     try {
       const data = await bdShow(id, getPath())
 
-      if (data === null) {
-        selectedIssue.value = null
-        error.value = `Issue ${id} not found`
-        return null
-      }
-
       // Enrich parent/children from loaded issues list (bd >= 0.50 doesn't return these)
       if (!data.parent?.id) {
         const lastDot = data.id.lastIndexOf('.')
@@ -493,6 +550,11 @@ export function useIssues() {
 
       return data
     } catch (e) {
+      if (isNotFoundError(e)) {
+        selectedIssue.value = null
+        error.value = `Issue ${id} not found`
+        return null
+      }
       error.value = e instanceof Error ? e.message : 'Failed to fetch issue'
       return null
     }
@@ -508,6 +570,17 @@ export function useIssues() {
       await fetchIssues()
       return data
     } catch (e) {
+      // The issue exists but its create output was unreadable: open it rather than
+      // letting the user submit again and create a duplicate
+      const createdId = parseCreatePartialError(e)
+      if (createdId) {
+        markAsNewlyAdded(createdId)
+        await fetchIssues()
+        const created = await fetchIssue(createdId) ?? issues.value.find(i => i.id === createdId)
+        if (created) return created
+        error.value = `Issue ${createdId} was created but could not be loaded`
+        return null
+      }
       error.value = e instanceof Error ? e.message : 'Failed to create issue'
       return null
     } finally {
@@ -564,7 +637,10 @@ export function useIssues() {
       // Use bdShow directly to avoid overwriting selectedIssue
       const path = getPath()
       for (const depId of dependentIds) {
-        const fresh = await bdShow(depId, path)
+        const fresh = await bdShow(depId, path).catch((e) => {
+          if (isNotFoundError(e)) return null
+          throw e
+        })
         if (fresh) {
           const idx = issues.value.findIndex(i => i.id === depId)
           const existing = idx !== -1 ? issues.value[idx] : undefined
@@ -589,22 +665,23 @@ export function useIssues() {
     }
   }
 
-  const deleteIssue = async (id: string) => {
+  // Fails with a HAS_OPEN_CHILDREN:<ids>: error (see parseOpenChildrenError) unless
+  // children.force or children.cascade is set
+  const deleteIssue = async (id: string, children: DeleteChildrenOptions = {}) => {
     isUpdating.value = true
     error.value = null
 
     try {
-      await bdDelete(id, getPath())
+      const result = await bdDelete(id, getPath(), children)
+      const deleted = result.deleted ?? [id]
+      forgetPolledIssues(deleted)
 
-      // Remove from local list
-      const index = issues.value.findIndex(i => i.id === id)
-      if (index !== -1) {
-        issues.value.splice(index, 1)
-        lastKnownCount.value = issues.value.length
-      }
+      // Remove from local list (a cascade also removes the descendants)
+      issues.value = issues.value.filter(i => !deleted.includes(i.id))
+      lastKnownCount.value = issues.value.length
 
       // Clear selection if deleted issue was selected
-      if (selectedIssue.value?.id === id) {
+      if (selectedIssue.value && deleted.includes(selectedIssue.value.id)) {
         selectedIssue.value = null
       }
 
@@ -753,6 +830,7 @@ export function useIssues() {
     selectedIssue.value = null
     lastKnownCount.value = 0
     lastKnownUpdated.value = null
+    lastPoll = null
     error.value = null
     newlyAddedIds.value = new Set()
   }
@@ -812,6 +890,8 @@ export function useIssues() {
     isLoading,
     isUpdating,
     error,
+    mergeConflictIds: readonly(mergeConflictIds),
+    parseWarnings: readonly(parseWarnings),
     // Pagination
     currentPage,
     pageSize,
@@ -31,13 +31,14 @@ export function useMigrateRefs() {
   }
 
   // Perform the migration
-  const migrate = async (projectPath?: string): Promise<boolean> => {
+  // confirm: the user explicitly asked for it (required while safe mode is on)
+  const migrate = async (projectPath?: string, confirm = false): Promise<boolean> => {
     const path = projectPath || beadsPath.value
     isMigrating.value = true
     migrateError.value = null
 
     try {
-      const result = await bdMigrateRefs(path)
+      const result = await bdMigrateRefs(path, confirm)
 
       if (result.success) {
         needsMigration.value = false
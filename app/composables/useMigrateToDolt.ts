@@ -39,13 +39,14 @@ export function useMigrateToDolt() {
   }
 
   // Perform the migration
-  const migrate = async (projectPath?: string): Promise<boolean> => {
+  // confirm: the user explicitly asked for it (required while safe mode is on)
+  const migrate = async (projectPath?: string, confirm = false): Promise<boolean> => {
     const path = projectPath || beadsPath.value
     isMigrating.value = true
     migrateError.value = null
 
     try {
-      const result = await bdMigrateToDolt(path)
+      const result = await bdMigrateToDolt(path, confirm)
 
       if (result.success) {
         needsMigration.value = false
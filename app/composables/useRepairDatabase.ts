@@ -22,14 +22,15 @@ export function useRepairDatabase() {
   }
 
   // Perform the repair on current project
-  const repair = async (projectPath?: string): Promise<boolean> => {
+  // confirm: the user explicitly asked for it (required while safe mode is on)
+  const repair = async (projectPath?: string, confirm = false): Promise<boolean> => {
     const path = projectPath || beadsPath.value
     isRepairing.value = true
     repairError.value = null
     lastRepairResult.value = null
 
     try {
-      const result = await bdRepairDatabase(path)
+      const result = await bdRepairDatabase(path, confirm)
       lastRepairResult.value = result
 
       if (result.success) {
@@ -49,7 +50,7 @@ export function useRepairDatabase() {
   }
 
   // Repair all projects
-  const repairAll = async (projectPaths: string[]): Promise<{ success: number; failed: number; errors: string[] }> => {
+  const repairAll = async (projectPaths: string[], confirm = false): Promise<{ success: number; failed: number; errors: string[] }> => {
     isRepairing.value = true
     repairError.value = null
     const results = { success: 0, failed: 0, errors: [] as string[] }
@@ -61,7 +62,7 @@ export function useRepairDatabase() {
       repairProgress.value = { current: i + 1, total: projectPaths.length, currentPath: path }
 
       try {
-        const result = await bdRepairDatabase(path)
+        const result = await bdRepairDatabase(path, confirm)
         if (result.success) {
           results.success++
         } else {
@@ -1,4 +1,4 @@
-import { bdSync } from '~/utils/bd-api'
+import { bdSync, type SyncSummary } from '~/utils/bd-api'
 
 // Shared state across all components
 const lastSyncError = ref<string | null>(null)
@@ -10,8 +10,17 @@ const showErrorDialog = ref(false)
 // Auto-clear success message after delay
 let messageTimeout: ReturnType<typeof setTimeout> | null = null
 
-function showSuccessMessage() {
-  syncMessage.value = 'Sync OK'
+// "Sync OK · 2 created, 1 updated" — only the non-empty counts
+function formatSyncSummary(summary?: SyncSummary): string {
+  if (!summary) return 'Sync OK'
+  const parts = (['created', 'updated', 'closed', 'deleted'] as const)
+    .filter(key => summary[key].length > 0)
+    .map(key => `${summary[key].length} ${key}`)
+  return parts.length > 0 ? `Sync OK · ${parts.join(', ')}` : 'Sync OK · no changes'
+}
+
+function showSuccessMessage(summary?: SyncSummary) {
+  syncMessage.value = formatSyncSummary(summary)
   if (messageTimeout) clearTimeout(messageTimeout)
   messageTimeout = setTimeout(() => {
     syncMessage.value = null
@@ -30,9 +39,9 @@ export function useSyncStatus() {
     syncMessage.value = null
 
     try {
-      await bdSync(beadsPath.value || undefined)
+      const summary = await bdSync(beadsPath.value || undefined)
       lastSyncSuccess.value = true
-      showSuccessMessage()
+      showSuccessMessage(summary)
     } catch (e) {
       const message = e instanceof Error ? e.message : String(e)
       lastSyncError.value = message
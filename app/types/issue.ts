@@ -9,11 +9,19 @@ export interface Comment {
   createdAt: string
 }
 
+export interface LatestComment {
+  author: string
+  excerpt: string
+  createdAt: string
+}
+
 export interface ChildIssue {
   id: string
   title: string
   status: IssueStatus
   priority: IssuePriority
+  /** Nested children; only from bdShow with includeSubtree */
+  children?: ChildIssue[]
 }
 
 export interface ParentIssue {
@@ -60,6 +68,28 @@ export interface Issue {
   commentCount?: number
   dependencyCount?: number
   dependentCount?: number
+  dueAt?: string
+  danglingRefs?: string[]
+  descriptionIsMarkdown?: boolean
+  designNotesIsMarkdown?: boolean
+  acceptanceCriteriaIsMarkdown?: boolean
+  workingNotesIsMarkdown?: boolean
+  /** A text field still contains git conflict markers (<<<<<<< … >>>>>>>) */
+  hasConflictMarkers?: boolean
+  /** Set only in tree-ordered lists; roots are 0 */
+  depth?: number
+  /** Open issues blocked directly or transitively; only when requested from bdShow */
+  transitiveBlockingCount?: number
+  /** Direct children; from bdShow, or within the list when tree-ordered */
+  childCount?: number
+  hasChildren?: boolean
+  /** Newest comment; only when listed with includeLatestComment */
+  latestComment?: LatestComment
+  /** Ready issues only, when listed with includeReadySince: last blocker close (or creation) */
+  readySince?: string
+  daysReady?: number
+  /** Set only on bd_update results: editable fields that actually changed */
+  changedFields?: string[]
 }
 
 export interface FilterState {
@@ -93,6 +123,7 @@ export interface CreateIssuePayload {
   title: string
   description?: string
   type?: IssueType
+  status?: IssueStatus // e.g. 'in_progress' to create and start in one step
   priority?: IssuePriority
   assignee?: string
   labels?: string[]
@@ -103,6 +134,8 @@ export interface CreateIssuePayload {
   workingNotes?: string
   parent?: string // Parent epic ID for hierarchical child
   specId?: string
+  due?: string // ISO date or relative: '+3d', '+2w', 'tomorrow', 'next friday'
+  discoveredFrom?: string // Source issue ID; linked with a discovered-from relation
 }
 
 export interface UpdateIssuePayload {
@@ -121,6 +154,7 @@ export interface UpdateIssuePayload {
   parent?: string | null // null or empty string to detach, string ID to attach
   metadata?: string
   specId?: string
+  due?: string // Same formats as CreateIssuePayload.due; empty string clears
 }
 
 export interface CollapsibleState {
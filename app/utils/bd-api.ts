@@ -1,4 +1,4 @@
-import type { Issue, CreateIssuePayload, UpdateIssuePayload } from '~/types/issue'
+import type { Comment, Issue, IssueStatus, IssueType, CreateIssuePayload, UpdateIssuePayload } from '~/types/issue'
 import { invoke } from '@tauri-apps/api/core'
 import { matchProbeProject } from '~/utils/probe-adapter'
 import type { ProbeMetricsResponse, ProbeProject } from '~/utils/probe-adapter'
@@ -247,6 +247,30 @@ async function fetchProbeMetrics(): Promise<ProbeMetricsResponse> {
   return metrics
 }
 
+// Typed errors from commands that return { kind, message } (bd_show, bd_update, bd_delete, bd_dep_subgraph)
+export type BackendErrorKind = 'notFound' | 'schemaMigration' | 'invalidInput' | 'io' | 'bd'
+
+export class BackendError extends Error {
+  constructor(public kind: BackendErrorKind, message: string) {
+    super(message)
+    this.name = 'BackendError'
+  }
+}
+
+// Rethrow an invoke rejection as a BackendError when it carries a kind, so callers
+// keep getting an Error with the usual message (prefix parsers still work)
+function rethrowBackendError(error: unknown): never {
+  if (error && typeof error === 'object' && 'kind' in error && 'message' in error) {
+    const { kind, message } = error as { kind: BackendErrorKind, message: string }
+    throw new BackendError(kind, message)
+  }
+  throw error
+}
+
+export function isNotFoundError(error: unknown): boolean {
+  return error instanceof BackendError && error.kind === 'notFound'
+}
+
 // Check if error is a schema migration error (bd 0.49.4 bug)
 export function isSchemaMigrationError(error: unknown): boolean {
   if (error instanceof Error) {
@@ -258,6 +282,29 @@ export function isSchemaMigrationError(error: unknown): boolean {
   return false
 }
 
+// Conflicting issue ids if the error is a JSONL merge conflict (MERGE_CONFLICT:<ids>: ...), else null
+export function parseMergeConflictError(error: unknown): string[] | null {
+  const message = error instanceof Error ? error.message : typeof error === 'string' ? error : ''
+  const match = message.match(/MERGE_CONFLICT:([^:]*):/)
+  if (!match) return null
+  return match[1] ? match[1].split(',') : []
+}
+
+// Id of the issue bdCreate created but could not load back (CREATE_PARTIAL:<id>: ...), else null
+export function parseCreatePartialError(error: unknown): string | null {
+  const message = error instanceof Error ? error.message : typeof error === 'string' ? error : ''
+  const match = message.match(/CREATE_PARTIAL:([^:]+):/)
+  return match?.[1] ?? null
+}
+
+// Open child ids if bdDelete refused because of them (HAS_OPEN_CHILDREN:<ids>: ...), else null
+export function parseOpenChildrenError(error: unknown): string[] | null {
+  const message = error instanceof Error ? error.message : typeof error === 'string' ? error : ''
+  const match = message.match(/HAS_OPEN_CHILDREN:([^:]*):/)
+  if (!match) return null
+  return match[1] ? match[1].split(',') : []
+}
+
 // Repair database result
 export interface RepairResult {
   success: boolean
@@ -266,17 +313,37 @@ export interface RepairResult {
 }
 
 // Repair database (for schema migration issues)
-export async function bdRepairDatabase(path?: string): Promise<RepairResult> {
+// confirm=true is required while safe mode is on
+export async function bdRepairDatabase(path?: string, confirm = false): Promise<RepairResult> {
   if (!isTauri()) {
     throw new Error('Database repair is only available in the desktop app')
   }
-  return invoke<RepairResult>('bd_repair_database', { cwd: path })
+  return invoke<RepairResult>('bd_repair_database', { cwd: path, confirm })
 }
 
 // Dolt migration result
 export interface MigrateResult {
   success: boolean
   message: string
+  // Present when exportJsonl was requested: regenerated export vs. imported count.
+  // issues.jsonl is only replaced when the counts match (expected null = no file before)
+  jsonlExport?: {
+    exported: number
+    expected: number | null
+    matches: boolean
+    replaced: boolean
+  }
+  // Present on dry runs: what the init+import fallback would do
+  preview?: {
+    prefix: string | null
+    issueCount: number
+    tombstones: number
+    malformed: number
+    reprefixed: number
+    refsToTruncate: number
+    filesToBackup: string[]
+    filesToRemove: string[]
+  }
 }
 
 // Migration status check result
@@ -301,11 +368,13 @@ export async function bdCheckNeedsMigration(path?: string): Promise<MigrationSta
 }
 
 // Migrate a project from SQLite to Dolt backend
-export async function bdMigrateToDolt(path?: string): Promise<MigrateResult> {
+// confirm=true is required while safe mode is on
+// dryRun=true only reports the plan in `preview` (no confirmation needed, nothing changes)
+export async function bdMigrateToDolt(path?: string, confirm = false, exportJsonl = false, dryRun = false): Promise<MigrateResult> {
   if (!isTauri()) {
     throw new Error('Database migration is only available in the desktop app')
   }
-  return invoke<MigrateResult>('bd_migrate_to_dolt', { cwd: path })
+  return invoke<MigrateResult>('bd_migrate_to_dolt', { cwd: path, confirm, exportJsonl, dryRun })
 }
 
 // Attachment refs migration v3 (filesystem-only)
@@ -327,11 +396,25 @@ export async function bdCheckRefsMigration(path?: string): Promise<RefsMigration
   return invoke<RefsMigrationStatus>('check_refs_migration', { cwd: path })
 }
 
-export async function bdMigrateRefs(path?: string): Promise<MigrateRefsResult> {
+export async function bdMigrateRefs(path?: string, confirm = false): Promise<MigrateRefsResult> {
   if (!isTauri()) {
     throw new Error('Attachment migration is only available in the desktop app')
   }
-  return invoke<MigrateRefsResult>('migrate_attachment_refs', { cwd: path })
+  return invoke<MigrateRefsResult>('migrate_attachment_refs', { cwd: path, confirm })
+}
+
+// Safe mode: no auto-sync or migration prompts; migrations need confirm=true
+export async function setSafeMode(enabled: boolean): Promise<void> {
+  if (isTauri()) {
+    return invoke<void>('set_safe_mode', { enabled })
+  }
+}
+
+export async function getSafeMode(): Promise<boolean> {
+  if (isTauri()) {
+    return invoke<boolean>('get_safe_mode')
+  }
+  return false
 }
 
 // Remove stale Dolt lock files that block database access (left by crashed processes)
@@ -359,6 +442,22 @@ export async function bdCheckChanged(path?: string): Promise<boolean> {
   return true
 }
 
+// Pause/resume backend change detection while the window is hidden
+export async function setPollActive(active: boolean): Promise<void> {
+  if (isTauri()) {
+    return invoke<void>('set_poll_active', { active })
+  }
+}
+
+export type ResolvedBackend = 'bd' | 'br' | 'dolt' | 'unknown'
+
+export async function bdResolvedBackend(path?: string): Promise<ResolvedBackend> {
+  if (isTauri()) {
+    return invoke<ResolvedBackend>('bd_resolved_backend', { cwd: path })
+  }
+  return 'unknown'
+}
+
 /**
  * Reset the cached mtime for a specific project (or all projects).
  * Called when switching projects to force a fresh poll on next cycle.
@@ -378,11 +477,48 @@ export interface PollData {
   openIssues: Issue[]
   closedIssues: Issue[]
   readyIssues: Issue[]
+  /** Buckets that failed to load, e.g. "ready: <error>" */
+  errors?: string[]
+  /** Latest updatedAt seen — pass as sinceTimestamp for the next delta poll */
+  highWatermark?: string
+  /** Delta polls only: ids deleted since the timestamp */
+  deletedIds?: string[]
+  /** Delta polls only: every id that still exists; anything else was hard-deleted */
+  liveIds?: string[]
+  /** Issues the CLI returned that could not be parsed (missing from the lists) */
+  parseWarnings?: SkippedIssue[]
+  /** MERGE_CONFLICT:<ids>: ... while issues.jsonl has git conflict markers (see parseMergeConflictError) */
+  mergeConflict?: string
+}
+
+export interface SkippedIssue {
+  index: number
+  id: string | null
+  reason: string
 }
 
-export async function bdPollData(path?: string): Promise<PollData> {
+type PollPayload = PollData | { compressed: true, data: string }
+
+// Decode a gzip+base64 JSON payload from the backend
+async function gunzipJson<T>(base64: string): Promise<T> {
+  const bytes = Uint8Array.from(atob(base64), c => c.charCodeAt(0))
+  const stream = new Blob([bytes]).stream().pipeThrough(new DecompressionStream('gzip'))
+  return JSON.parse(await new Response(stream).text()) as T
+}
+
+// includeClosed=false is the first-paint path: closed comes back empty, fetch it with bdClosed
+// compress=true lets the backend gzip large payloads (worth it for thousands of issues)
+// With sinceTimestamp (requires includeClosed), open/closed hold only issues changed since then
+// (ready is always full); merge the result into the previous poll with mergePollDelta
+export async function bdPollData(
+  path?: string,
+  includeClosed = true,
+  compress = false,
+  sinceTimestamp?: string,
+): Promise<PollData> {
   if (isTauri()) {
-    return invoke<PollData>('bd_poll_data', { cwd: path })
+    const payload = await invoke<PollPayload>('bd_poll_data', { cwd: path, includeClosed, compress, sinceTimestamp })
+    return 'compressed' in payload ? gunzipJson<PollData>(payload.data) : payload
   }
 
   // Web fallback: make separate calls
@@ -394,6 +530,33 @@ export async function bdPollData(path?: string): Promise<PollData> {
   return { openIssues, closedIssues, readyIssues }
 }
 
+// Apply a delta poll to the previous full/merged poll result
+export function mergePollDelta(previous: PollData, delta: PollData): PollData {
+  const replaced = new Set([
+    ...delta.openIssues.map(i => i.id),
+    ...delta.closedIssues.map(i => i.id),
+    ...(delta.deletedIds ?? []),
+  ])
+  const live = delta.liveIds ? new Set(delta.liveIds) : null
+  const keep = (issues: Issue[]) => issues.filter(i => !replaced.has(i.id) && (!live || live.has(i.id)))
+  return {
+    openIssues: [...keep(previous.openIssues), ...delta.openIssues],
+    closedIssues: [...keep(previous.closedIssues), ...delta.closedIssues],
+    readyIssues: delta.readyIssues,
+    errors: delta.errors,
+    parseWarnings: delta.parseWarnings,
+    mergeConflict: delta.mergeConflict,
+    highWatermark: delta.highWatermark ?? previous.highWatermark,
+  }
+}
+
+export async function bdClosed(path?: string, limit?: number): Promise<Issue[]> {
+  if (isTauri()) {
+    return invoke<Issue[]>('bd_closed', { cwd: path, limit })
+  }
+  return bdList({ path, status: ['closed'] })
+}
+
 // ============================================================================
 // BD API Functions - Use Tauri invoke in app, fetch in web
 // ============================================================================
@@ -402,23 +565,121 @@ export interface BdListOptions {
   status?: string[]
   type?: string[]
   priority?: string[]
-  assignee?: string
+  /** One assignee or several (matches any) */
+  assignee?: string | string[]
+  /** Only issues with these labels; labelsMatch 'any' (default) or 'all' */
+  labels?: string[]
+  labelsMatch?: 'any' | 'all'
   includeAll?: boolean
+  /** Only issues assigned to the current actor (resolved by the backend) */
+  mineOnly?: boolean
+  /** Inclusive created_at bounds (ISO timestamp or YYYY-MM-DD; a date-only createdBefore covers the whole day) */
+  createdAfter?: string
+  createdBefore?: string
+  /** Id prefix (`frontend-`) or glob with * / ? (`frontend-*`) */
+  idPattern?: string
+  /** Parent-before-children order with `depth` set on each issue */
+  tree?: boolean
+  /** Only issues without a parent epic */
+  topLevelOnly?: boolean
+  /** Last modified by this actor — not tracked by bd/br, so always empty there */
+  lastActor?: string
+  /** Truncate descriptions to ~N chars (word boundary + …) for card previews */
+  descriptionExcerpt?: number
+  /** Attach each issue's newest comment (author + excerpt) as latestComment; one backend show per issue with comments (capped at 50), so pair with limit */
+  includeLatestComment?: boolean
+  /** Set readySince/daysReady on ready issues (open, all blockers closed) */
+  includeReadySince?: boolean
+  /** Offset paging, newest first (created_at DESC) */
+  limit?: number
+  offset?: number
   path?: string
 }
 
+// Map BdListOptions to the Rust ListOptions payload
+function toListOptionsPayload(options: BdListOptions) {
+  return {
+    status: options.status,
+    type: options.type,
+    priority: options.priority,
+    assignee: options.assignee,
+    labels: options.labels,
+    labelsMatch: options.labelsMatch,
+    includeAll: options.includeAll,
+    mineOnly: options.mineOnly,
+    createdAfter: options.createdAfter,
+    createdBefore: options.createdBefore,
+    idPattern: options.idPattern,
+    tree: options.tree,
+    topLevelOnly: options.topLevelOnly,
+    lastActor: options.lastActor,
+    descriptionExcerpt: options.descriptionExcerpt,
+    includeLatestComment: options.includeLatestComment,
+    includeReadySince: options.includeReadySince,
+    limit: options.limit,
+    offset: options.offset,
+    cwd: options.path,
+  }
+}
+
+export interface IssueSummary {
+  id: string
+  title: string
+  status: IssueStatus
+  type: IssueType
+}
+
+// Lightweight list for pickers/autocomplete: only id, title, status and type
+export async function bdListMinimal(options: BdListOptions = {}): Promise<IssueSummary[]> {
+  if (isTauri()) {
+    return invoke<IssueSummary[]>('bd_list_minimal', { options: toListOptionsPayload(options) })
+  }
+  const issues = await bdList(options)
+  return issues.map(({ id, title, status, type }) => ({ id, title, status, type }))
+}
+
+// Filtered issues as CSV text (header row, CRLF line endings) for the clipboard
+export async function bdListCsv(options: BdListOptions = {}): Promise<string> {
+  if (!isTauri()) {
+    throw new Error('CSV export is only available in the desktop app')
+  }
+  return invoke<string>('bd_list_csv', { options: toListOptionsPayload(options) })
+}
+
+export type GroupByField = 'assignee' | 'priority' | 'type' | 'status' | 'label'
+
+export interface IssueGroup {
+  group: string
+  issues: Issue[]
+}
+
+// Server-side grouping for board views; with 'label' an issue appears under each of its labels
+export async function bdListGrouped(groupBy: GroupByField, options: BdListOptions = {}): Promise<IssueGroup[]> {
+  if (isTauri()) {
+    return invoke<IssueGroup[]>('bd_list_grouped', { options: toListOptionsPayload(options), groupBy })
+  }
+  throw new Error('Grouped lists are only available in the desktop app')
+}
+
+export interface AttentionThresholds {
+  unassignedDays?: number
+  staleDays?: number
+  blockingCount?: number
+}
+
+export type AttentionIssue = Issue & { reasons: string[] }
+
+// Open issues that need a look (urgent, idle, stalled, or blocking others), with reasons
+export async function bdNeedsAttention(path?: string, thresholds?: AttentionThresholds): Promise<AttentionIssue[]> {
+  if (isTauri()) {
+    return invoke<AttentionIssue[]>('bd_needs_attention', { cwd: path, thresholds })
+  }
+  throw new Error('Needs-attention view is only available in the desktop app')
+}
+
 export async function bdList(options: BdListOptions = {}): Promise<Issue[]> {
   if (isTauri()) {
-    return invoke<Issue[]>('bd_list', {
-      options: {
-        status: options.status,
-        type: options.type,
-        priority: options.priority,
-        assignee: options.assignee,
-        includeAll: options.includeAll,
-        cwd: options.path,
-      },
-    })
+    return invoke<Issue[]>('bd_list', { options: toListOptionsPayload(options) })
   }
 
   // Web: use fetch
@@ -428,18 +689,73 @@ export async function bdList(options: BdListOptions = {}): Promise<Issue[]> {
   if (options.status?.length === 1 && options.status[0]) params.set('status', options.status[0])
   if (options.type?.length) params.set('type', options.type.join(','))
   if (options.priority?.length) params.set('priority', options.priority.join(','))
-  if (options.assignee) params.set('assignee', options.assignee)
+  const assignees = ([] as string[]).concat(options.assignee ?? []).filter(Boolean)
+  if (assignees.length === 1) params.set('assignee', assignees[0]!)
 
   const queryString = params.toString()
   const url = queryString ? `/api/bd/list?${queryString}` : '/api/bd/list'
-  return $fetch<Issue[]>(url)
+  const issues = await $fetch<Issue[]>(url)
+  // The web route takes a single assignee; several are matched here instead
+  return assignees.length > 1
+    ? issues.filter(issue => !!issue.assignee && assignees.includes(issue.assignee))
+    : issues
+}
+
+export interface IssueListReport {
+  issues: Issue[]
+  /** Issues the CLI returned that could not be parsed (missing from issues) */
+  parseWarnings: SkippedIssue[]
+}
+
+// bdList plus the entries that were dropped as unparseable (the web API doesn't report them)
+export async function bdListWithWarnings(options: BdListOptions = {}): Promise<IssueListReport> {
+  if (isTauri()) {
+    return invoke<IssueListReport>('bd_list', {
+      options: { ...toListOptionsPayload(options), includeParseWarnings: true },
+    })
+  }
+  return { issues: await bdList(options), parseWarnings: [] }
+}
+
+export interface ListCursor {
+  afterCreatedAt: string
+  afterId: string
+}
+
+export interface IssuePage {
+  issues: Issue[]
+  nextCursor: ListCursor | null
+}
+
+// Cursor-based paging (created_at DESC, id DESC) — stable while issues are being created
+// limit=null returns everything after the cursor
+export async function bdListCursor(options: BdListOptions = {}, cursor: ListCursor | null = null, limit: number | null = 50): Promise<IssuePage> {
+  if (!isTauri()) {
+    throw new Error('Cursor paging is only available in the desktop app')
+  }
+  return invoke<IssuePage>('bd_list_cursor', {
+    options: toListOptionsPayload(options),
+    cursor,
+    limit,
+  })
+}
+
+export async function bdCurrentActor(path?: string): Promise<string | null> {
+  if (isTauri()) {
+    return invoke<string | null>('bd_current_actor', { cwd: path })
+  }
+  return null
 }
 
 export interface BdCountResult {
   count: number
   byType: Record<string, number>
   byPriority: Record<string, number>
+  /** Issues per assignee; unassigned under '' */
+  byAssignee?: Record<string, number>
   lastUpdated: string | null
+  /** Deleted issues, not included in count or breakdowns */
+  tombstoneCount?: number
 }
 
 export async function bdCount(path?: string): Promise<BdCountResult> {
@@ -451,6 +767,33 @@ export async function bdCount(path?: string): Promise<BdCountResult> {
   return $fetch<BdCountResult>(url)
 }
 
+export interface BdDashboard {
+  counts: BdCountResult
+  ready: Issue[]
+}
+
+// Counts + ready queue only: much cheaper than bdPollData for summary widgets
+export async function bdDashboard(path?: string): Promise<BdDashboard> {
+  if (isTauri()) {
+    return invoke<BdDashboard>('bd_dashboard', { cwd: path })
+  }
+
+  throw new Error('The dashboard summary is only available in the desktop app')
+}
+
+export interface TypeCount {
+  type: string
+  count: number
+}
+
+// Issue types actually present in the project (custom ones included), most common first
+export async function bdTypes(path?: string): Promise<TypeCount[]> {
+  if (isTauri()) {
+    return invoke<TypeCount[]>('bd_types', { cwd: path })
+  }
+  return []
+}
+
 export async function bdReady(path?: string): Promise<Issue[]> {
   if (isTauri()) {
     return invoke<Issue[]>('bd_ready', { options: { cwd: path } })
@@ -469,13 +812,23 @@ export async function bdStatus(path?: string): Promise<unknown> {
   return $fetch(url)
 }
 
-export async function bdShow(id: string, path?: string): Promise<Issue | null> {
+// includeBlockingCount adds transitiveBlockingCount (costs a full list on the backend)
+// includeSubtree: children carry their own children, down the whole hierarchy
+// includeDanglingRefs adds danglingRefs for deps on deleted issues (also costs a full list)
+// A missing issue rejects with BackendError kind 'notFound' (see isNotFoundError)
+export async function bdShow(id: string, path?: string, includeBlockingCount = false, includeSubtree = false, includeDanglingRefs = false): Promise<Issue> {
   if (isTauri()) {
-    return invoke<Issue | null>('bd_show', { id, options: { cwd: path } })
+    return invoke<Issue>('bd_show', { id, options: { cwd: path }, includeBlockingCount, includeSubtree, includeDanglingRefs }).catch(rethrowBackendError)
   }
 
   const url = path && path !== '.' ? `/api/bd/show/${id}?path=${encodeURIComponent(path)}` : `/api/bd/show/${id}`
-  return $fetch<Issue>(url)
+  // The web route answers a missing issue with a 404 or a null body
+  const issue = await $fetch<Issue | null>(url).catch((error: unknown) => {
+    if ((error as { statusCode?: number } | null)?.statusCode === 404) throw new BackendError('notFound', `Issue not found: ${id}`)
+    throw error
+  })
+  if (!issue) throw new BackendError('notFound', `Issue not found: ${id}`)
+  return issue
 }
 
 export async function bdCreate(payload: CreateIssuePayload, path?: string): Promise<Issue | null> {
@@ -497,7 +850,7 @@ export async function bdUpdate(id: string, payload: UpdateIssuePayload, path?: s
     return invoke<Issue | null>('bd_update', {
       id,
       updates: { ...payload, cwd: path },
-    })
+    }).catch(rethrowBackendError)
   }
 
   const url = path && path !== '.' ? `/api/bd/update/${id}?path=${encodeURIComponent(path)}` : `/api/bd/update/${id}`
@@ -515,6 +868,20 @@ export async function bdSearch(query: string, path?: string): Promise<Issue[]> {
   return []
 }
 
+// Last queries passed to bdSearch for a project, newest first
+export async function getRecentSearches(path?: string): Promise<string[]> {
+  if (isTauri()) {
+    return invoke<string[]>('get_recent_searches', { cwd: path })
+  }
+  return []
+}
+
+export async function clearRecentSearches(path?: string): Promise<void> {
+  if (isTauri()) {
+    return invoke<void>('clear_recent_searches', { cwd: path })
+  }
+}
+
 export async function bdLabelAdd(id: string, label: string, path?: string): Promise<void> {
   if (isTauri()) {
     return invoke<void>('bd_label_add', { id, label, options: { cwd: path } })
@@ -529,6 +896,14 @@ export async function bdLabelRemove(id: string, label: string, path?: string): P
   throw new Error('Label operations are only available in the desktop app')
 }
 
+/** Replace all labels of an issue; resolves to the labels it ends up with */
+export async function bdSetLabels(id: string, labels: string[], path?: string): Promise<string[]> {
+  if (isTauri()) {
+    return invoke<string[]>('bd_set_labels', { id, labels, options: { cwd: path } })
+  }
+  throw new Error('Label operations are only available in the desktop app')
+}
+
 export async function bdClose(id: string, path?: string): Promise<unknown> {
   if (isTauri()) {
     return invoke('bd_close', { id, options: { cwd: path } })
@@ -538,15 +913,70 @@ export async function bdClose(id: string, path?: string): Promise<unknown> {
   return $fetch(url, { method: 'POST' })
 }
 
-export async function bdDelete(id: string, path?: string): Promise<{ success: boolean; id: string }> {
+export async function bdReopen(id: string, path?: string): Promise<Issue | null> {
   if (isTauri()) {
-    return invoke<{ success: boolean; id: string }>('bd_delete', { id, options: { cwd: path } })
+    return invoke<Issue | null>('bd_reopen', { id, options: { cwd: path } })
+  }
+  return bdUpdate(id, { status: 'open' }, path)
+}
+
+export interface DeleteChildrenOptions {
+  /** Delete even with open children (they're left without a parent) */
+  force?: boolean
+  /** Delete the children (and their descendants) too */
+  cascade?: boolean
+}
+
+// Rejects with HAS_OPEN_CHILDREN:<ids>: when the issue has open children and neither flag is set
+export async function bdDelete(
+  id: string,
+  path?: string,
+  children: DeleteChildrenOptions = {},
+): Promise<{ success: boolean; id: string; deleted?: string[] }> {
+  if (isTauri()) {
+    return invoke<{ success: boolean; id: string; deleted?: string[] }>('bd_delete', {
+      id,
+      options: { cwd: path },
+      force: children.force,
+      cascade: children.cascade,
+    }).catch(rethrowBackendError)
   }
 
   const url = path && path !== '.' ? `/api/bd/delete/${id}?path=${encodeURIComponent(path)}` : `/api/bd/delete/${id}`
   return $fetch<{ success: boolean; id: string }>(url, { method: 'DELETE' })
 }
 
+export interface BulkItemResult {
+  id: string
+  success: boolean
+  error?: string
+}
+
+// Same update applied to each issue; by default keeps going past failures
+export async function bdBulkUpdate(
+  ids: string[],
+  updates: UpdateIssuePayload,
+  path?: string,
+  continueOnError = true,
+): Promise<BulkItemResult[]> {
+  if (!isTauri()) {
+    throw new Error('Bulk update is only available in the desktop app')
+  }
+  return invoke<BulkItemResult[]>('bd_bulk_update', {
+    ids,
+    updates: { ...updates, cwd: path },
+    continueOnError,
+  })
+}
+
+// Delete several issues with a single sync; soft delete (hard=false) keeps attachments
+export async function bdBulkDelete(ids: string[], hard = true, path?: string): Promise<BulkItemResult[]> {
+  if (!isTauri()) {
+    throw new Error('Bulk delete is only available in the desktop app')
+  }
+  return invoke<BulkItemResult[]>('bd_bulk_delete', { ids, hard, options: { cwd: path } })
+}
+
 export async function bdAddComment(id: string, content: string, path?: string): Promise<{ success: boolean }> {
   if (isTauri()) {
     return invoke<{ success: boolean }>('bd_comments_add', { id, content, options: { cwd: path } })
@@ -559,9 +989,102 @@ export async function bdAddComment(id: string, content: string, path?: string):
   })
 }
 
-export async function bdAddDependency(issueId: string, blockerId: string, path?: string): Promise<{ success: boolean }> {
+// Requires bd 0.56+; older CLIs reject it with an error
+export async function bdDeleteComment(issueId: string, commentId: string, path?: string): Promise<{ success: boolean }> {
+  if (isTauri()) {
+    return invoke<{ success: boolean }>('bd_comment_delete', { issueId, commentId, cwd: path })
+  }
+  throw new Error('Deleting comments is only available in the desktop app')
+}
+
+export interface CommentWithAttachment {
+  comment: Comment | null
+  filename: string
+}
+
+// Copies an image into the issue's attachments and comments with a markdown reference to it
+export async function bdCommentWithAttachment(
+  id: string,
+  content: string,
+  sourcePath: string,
+  path?: string,
+): Promise<CommentWithAttachment> {
+  if (!isTauri()) {
+    throw new Error('Comment attachments are only available in the desktop app')
+  }
+  return invoke<CommentWithAttachment>('bd_comment_with_attachment', {
+    issueId: id,
+    content,
+    sourcePath,
+    options: { cwd: path },
+  })
+}
+
+export async function bdAddDependency(issueId: string, blockerId: string, path?: string): Promise<{ success: boolean; added?: boolean; warning?: string }> {
+  if (isTauri()) {
+    return invoke<{ success: boolean; added?: boolean; warning?: string }>('bd_dep_add', { issueId, blockerId, options: { cwd: path } })
+  }
+
+  throw new Error('Dependency management is only available in the desktop app')
+}
+
+export interface BlockerResult {
+  blockerId: string
+  // false without error: skipped duplicate
+  added: boolean
+  error?: string
+}
+
+// Add several blockers at once; missing ids, self-edges and cycles are rejected per blocker
+export async function bdAddDependencies(issueId: string, blockerIds: string[], path?: string): Promise<BlockerResult[]> {
   if (isTauri()) {
-    return invoke<{ success: boolean }>('bd_dep_add', { issueId, blockerId, options: { cwd: path } })
+    return invoke<BlockerResult[]>('bd_dep_add_many', { issueId, blockerIds, options: { cwd: path } })
+  }
+
+  throw new Error('Dependency management is only available in the desktop app')
+}
+
+export interface CreateBatchResult {
+  created: Issue[]
+  // Also created, but could not be loaded back: refresh instead of retrying these titles
+  unloaded: { index: number; id: string; title: string }[]
+  // index = position of the failed title in the request
+  errors: { index: number; title: string; error: string }[]
+}
+
+// One issue per non-blank title, sharing defaults (type, priority, labels, parent...).
+// Stops at the first failure unless continueOnError is set.
+export async function bdCreateBatch(
+  titles: string[],
+  defaults: Omit<CreateIssuePayload, 'title'>,
+  path?: string,
+  continueOnError?: boolean,
+): Promise<CreateBatchResult> {
+  if (isTauri()) {
+    return invoke<CreateBatchResult>('bd_create_batch', {
+      titles,
+      defaults: { ...defaults, title: '' },
+      cwd: path,
+      continueOnError,
+    })
+  }
+
+  throw new Error('Batch create is only available in the desktop app')
+}
+
+// blockerId blocks issueId (same edge as bdAddDependency)
+export async function bdAddBlocker(issueId: string, blockerId: string, path?: string): Promise<{ success: boolean; added?: boolean; warning?: string }> {
+  if (isTauri()) {
+    return invoke<{ success: boolean; added?: boolean; warning?: string }>('bd_add_blocker', { issueId, blockerId, options: { cwd: path } })
+  }
+
+  throw new Error('Dependency management is only available in the desktop app')
+}
+
+// issueId blocks blockedId
+export async function bdAddBlocks(issueId: string, blockedId: string, path?: string): Promise<{ success: boolean; added?: boolean; warning?: string }> {
+  if (isTauri()) {
+    return invoke<{ success: boolean; added?: boolean; warning?: string }>('bd_add_blocks', { issueId, blockedId, options: { cwd: path } })
   }
 
   throw new Error('Dependency management is only available in the desktop app')
@@ -575,6 +1098,72 @@ export async function bdRemoveDependency(issueId: string, blockerId: string, pat
   throw new Error('Dependency management is only available in the desktop app')
 }
 
+export async function bdPruneDependencies(id: string, path?: string): Promise<{ success: boolean; removed: string[] }> {
+  if (isTauri()) {
+    return invoke<{ success: boolean; removed: string[] }>('bd_dep_prune', { id, options: { cwd: path } })
+  }
+
+  throw new Error('Dependency management is only available in the desktop app')
+}
+
+export interface DepEdge {
+  from: string
+  to: string
+  type: string
+}
+
+export interface DepSubgraph {
+  nodes: Issue[]
+  edges: DepEdge[]
+  truncated: boolean
+}
+
+export async function bdDepSubgraph(id: string, depth = 2, path?: string): Promise<DepSubgraph> {
+  if (isTauri()) {
+    return invoke<DepSubgraph>('bd_dep_subgraph', { id, depth, options: { cwd: path } }).catch(rethrowBackendError)
+  }
+
+  throw new Error('Dependency management is only available in the desktop app')
+}
+
+export interface RelationEnd {
+  id: string
+  title: string
+  status: string
+}
+
+export interface RelationPair {
+  from: RelationEnd
+  to: RelationEnd
+}
+
+// Every from → to pair of one relation type across the project
+export async function bdRelationsOfType(depType: string, path?: string): Promise<RelationPair[]> {
+  if (isTauri()) {
+    return invoke<RelationPair[]>('bd_relations_of_type', { depType, cwd: path })
+  }
+  throw new Error('Relation management is only available in the desktop app')
+}
+
+export interface DepValidationReport {
+  checkedIssues: number
+  dangling: DepEdge[]
+  selfDeps: DepEdge[]
+  duplicates: DepEdge[]
+  affectedIds: string[]
+  fixed: number
+  /** fix=true only: duplicated edges the CLI removed entirely and that could not be re-added */
+  lost?: DepEdge[]
+}
+
+// Find dangling/self/duplicate dependencies; fix=true removes them (surplus copies of duplicates only)
+export async function bdValidateDependencies(path?: string, fix = false): Promise<DepValidationReport> {
+  if (isTauri()) {
+    return invoke<DepValidationReport>('bd_validate_dependencies', { cwd: path, fix })
+  }
+  throw new Error('Dependency validation is only available in the desktop app')
+}
+
 export async function bdAvailableRelationTypes(): Promise<Array<{ value: string; label: string }>> {
   if (isTauri()) {
     return invoke<Array<{ value: string; label: string }>>('bd_available_relation_types')
@@ -596,14 +1185,58 @@ export async function bdRemoveRelation(id1: string, id2: string, path?: string):
   throw new Error('Relation management is only available in the desktop app')
 }
 
-export async function bdSync(path?: string): Promise<void> {
+// Issue ids changed locally by a sync (always empty for Dolt projects)
+export interface SyncSummary {
+  created: string[]
+  updated: string[]
+  closed: string[]
+  deleted: string[]
+}
+
+export async function bdSync(path?: string): Promise<SyncSummary | undefined> {
   if (isTauri()) {
-    return invoke<void>('bd_sync', { cwd: path })
+    return invoke<SyncSummary>('bd_sync', { cwd: path })
   }
 
   // Web fallback - no-op (sync not available in web mode)
 }
 
+// Background auto-sync cadence in seconds (0 = sync on reads with the short cooldown)
+export async function setAutoSyncInterval(secs: number): Promise<void> {
+  if (isTauri()) {
+    return invoke<void>('set_auto_sync_interval', { secs })
+  }
+}
+
+export async function getAutoSyncInterval(): Promise<number> {
+  if (isTauri()) {
+    return invoke<number>('get_auto_sync_interval')
+  }
+  return 0
+}
+
+// Favorite project paths; auto-sync covers them as well as the projects opened this session
+export async function setFavoriteProjects(paths: string[]): Promise<void> {
+  if (isTauri()) {
+    return invoke<void>('set_favorite_projects', { paths })
+  }
+}
+
+export interface ExportResult {
+  path: string
+  lineCount: number
+  // True when the CLI lacked `export` and the file was rebuilt from `list --all`
+  usedListFallback: boolean
+}
+
+// Native CLI export (canonical JSONL) written to outPath
+export async function bdExport(outPath: string, path?: string): Promise<ExportResult> {
+  if (!isTauri()) {
+    throw new Error('Export is only available in the desktop app')
+  }
+  return invoke<ExportResult>('bd_export', { cwd: path, outPath })
+}
+
 export interface PurgeResult {
   deletedCount: number
   deletedFolders: string[]
@@ -650,6 +1283,12 @@ export async function setVerboseLogging(enabled: boolean): Promise<void> {
   }
 }
 
+export async function setVerboseLoggingTimed(minutes?: number): Promise<void> {
+  if (isTauri()) {
+    return invoke<void>('set_verbose_logging_timed', { minutes })
+  }
+}
+
 export async function clearLogs(): Promise<void> {
   if (isTauri()) {
     return invoke<void>('clear_logs')
@@ -670,6 +1309,21 @@ export async function readLogs(tailLines?: number): Promise<string> {
   return ''
 }
 
+export interface LogEntry {
+  timestamp: string | null
+  level: string | null
+  category: string | null
+  message: string
+}
+
+// Log lines parsed into entries; unparseable lines come back with only `message`
+export async function readLogsStructured(tailLines?: number): Promise<LogEntry[]> {
+  if (isTauri()) {
+    return invoke<LogEntry[]>('read_logs_structured', { tailLines })
+  }
+  return []
+}
+
 export async function getLogPath(): Promise<string> {
   if (isTauri()) {
     return invoke<string>('get_log_path_string')
@@ -718,9 +1372,25 @@ export interface BdCompatibilityInfo {
   usesDoltBackend: boolean
   /** bd >= 0.55: --all flag works correctly for bd list */
   supportsListAllFlag: boolean
+  /** Embedded Dolt may crash if another process uses the project concurrently */
+  hasDoltConcurrencyBug: boolean
   warnings: string[]
 }
 
+export interface DiagnosticCheck {
+  name: string
+  status: 'pass' | 'warn' | 'fail'
+  message: string
+}
+
+// Environment self-test (CLI, .beads, backend, Dolt, config dir, sqlite3) for bug reports
+export async function runDiagnostics(path?: string): Promise<DiagnosticCheck[]> {
+  if (!isTauri()) {
+    throw new Error('Diagnostics are only available in the desktop app')
+  }
+  return invoke<DiagnosticCheck[]>('run_diagnostics', { cwd: path })
+}
+
 export async function checkBdCompatibility(): Promise<BdCompatibilityInfo> {
   if (isTauri()) {
     return invoke<BdCompatibilityInfo>('check_bd_compatibility')
@@ -733,6 +1403,7 @@ export async function checkBdCompatibility(): Promise<BdCompatibilityInfo> {
     usesJsonlFiles: false,
     usesDoltBackend: false,
     supportsListAllFlag: false,
+    hasDoltConcurrencyBug: false,
     warnings: [],
   }
 }
@@ -748,6 +1419,29 @@ export async function getCliBinaryPath(): Promise<string> {
   return 'bd'
 }
 
+// Absolute path of the configured CLI as found on PATH (rejects when not found)
+export async function resolveCliBinaryPath(): Promise<string> {
+  if (!isTauri()) {
+    throw new Error('CLI resolution is only available in the desktop app')
+  }
+  return invoke<string>('resolve_cli_binary_path')
+}
+
+export interface AvailableCli {
+  name: 'bd' | 'br'
+  path: string
+  version: string
+  clientType: 'bd' | 'br' | 'unknown'
+}
+
+// Working bd/br binaries on PATH and common install locations, for the settings picker
+export async function detectAvailableClis(): Promise<AvailableCli[]> {
+  if (!isTauri()) {
+    throw new Error('CLI detection is only available in the desktop app')
+  }
+  return invoke<AvailableCli[]>('detect_available_clis')
+}
+
 export async function setCliBinaryPath(path: string): Promise<string> {
   if (!isTauri()) {
     throw new Error('CLI binary configuration is only available in the desktop app')
@@ -755,6 +1449,85 @@ export async function setCliBinaryPath(path: string): Promise<string> {
   return invoke<string>('set_cli_binary_path', { path })
 }
 
+// All persisted app settings (settings.json keys are snake_case)
+export interface AppConfig {
+  cli_binary: string
+  projects: Record<string, ProjectConfig>
+  env_passthrough: string[] | null
+  auto_sync_interval_secs: number
+  auto_backup: { dir: string, interval_secs: number, keep: number } | null
+  command_timeout_secs: number
+  recent_searches?: Record<string, string[]>
+  watch_debounce_ms: number
+  logging_enabled: boolean
+  verbose_logging: boolean
+}
+
+// Periodic JSONL backups of every project opened this session; dir=null or intervalSecs=0 turns them off
+export async function setAutoBackup(dir: string | null, intervalSecs: number, keep: number): Promise<void> {
+  if (isTauri()) {
+    return invoke<void>('set_auto_backup', { dir, intervalSecs, keep })
+  }
+}
+
+export async function runBackupNow(path?: string): Promise<ExportResult> {
+  if (!isTauri()) {
+    throw new Error('Backups are only available in the desktop app')
+  }
+  return invoke<ExportResult>('run_backup_now', { cwd: path })
+}
+
+export async function getConfig(): Promise<AppConfig> {
+  if (!isTauri()) {
+    throw new Error('Settings are only available in the desktop app')
+  }
+  return invoke<AppConfig>('get_config')
+}
+
+export async function setConfig(config: AppConfig): Promise<void> {
+  if (!isTauri()) {
+    throw new Error('Settings are only available in the desktop app')
+  }
+  return invoke<void>('set_config', { config })
+}
+
+// Opt-in allow-list (exact names or PREFIX*) for BEADS_/BD_/DOLT_/GIT_ vars reaching the CLI;
+// null = the CLI inherits the full environment
+export async function getEnvPassthrough(): Promise<string[] | null> {
+  if (isTauri()) {
+    return invoke<string[] | null>('get_env_passthrough')
+  }
+  return null
+}
+
+export async function setEnvPassthrough(patterns: string[] | null): Promise<void> {
+  if (!isTauri()) {
+    throw new Error('CLI configuration is only available in the desktop app')
+  }
+  return invoke<void>('set_env_passthrough', { patterns })
+}
+
+export interface ProjectConfig {
+  defaultIssueType?: string
+  defaultPriority?: string
+  /** Lowercase labels on create/update/add */
+  lowercaseLabels?: boolean
+}
+
+export async function getProjectConfig(path?: string): Promise<ProjectConfig> {
+  if (isTauri()) {
+    return invoke<ProjectConfig>('get_project_config', { cwd: path })
+  }
+  return {}
+}
+
+export async function setProjectConfig(config: ProjectConfig, path?: string): Promise<void> {
+  if (!isTauri()) {
+    throw new Error('Project configuration is only available in the desktop app')
+  }
+  return invoke<void>('set_project_config', { cwd: path, config })
+}
+
 
 // ============================================================================
 // File System API - For folder picker
@@ -815,6 +1588,14 @@ export async function stopWatching(): Promise<void> {
   }
 }
 
+// Clamped to 200–5000ms by the backend; resolves to the value applied. Restarts an active watcher.
+export async function setWatchDebounce(ms: number): Promise<number> {
+  if (isTauri()) {
+    return invoke<number>('set_watch_debounce', { ms })
+  }
+  return ms
+}
+
 export async function getWatcherStatus(): Promise<WatcherStatus> {
   if (isTauri()) {
     return invoke<WatcherStatus>('get_watcher_status')
@@ -401,3 +401,33 @@ export function computeReadyIssues(issues: Issue[]): Issue[] {
     i.status === 'open' && (!i.blockedBy || i.blockedBy.length === 0),
   )
 }
+
+const RELATIVE_UNITS: [Intl.RelativeTimeFormatUnit, number][] = [
+  ['year', 365 * 86400],
+  ['month', 30 * 86400],
+  ['day', 86400],
+  ['hour', 3600],
+  ['minute', 60],
+]
+
+/**
+ * Render a backend timestamp as "2 days ago" / "in 3 hours" in the user's locale.
+ * Computed at render time so cached or merged issues never show a stale distance.
+ * Timestamps without a zone (SQLite `YYYY-MM-DD HH:MM:SS`) are read as UTC.
+ */
+export function formatRelativeTime(timestamp: string | undefined | null, now: number = Date.now(), locale?: string): string | null {
+  if (!timestamp) return null
+  let iso = timestamp.trim().replace(' ', 'T')
+  if (/T\d{2}:\d{2}(:\d{2}(\.\d+)?)?$/.test(iso)) iso += 'Z'
+  const time = new Date(iso).getTime()
+  if (Number.isNaN(time)) return null
+
+  const deltaSeconds = Math.round((time - now) / 1000)
+  const format = new Intl.RelativeTimeFormat(locale, { numeric: 'auto' })
+  for (const [unit, seconds] of RELATIVE_UNITS) {
+    if (Math.abs(deltaSeconds) >= seconds) {
+      return format.format(Math.trunc(deltaSeconds / seconds), unit)
+    }
+  }
+  return format.format(0, 'second')
+}
@@ -131,6 +131,16 @@ export interface ImageData {
   mimeType: string
 }
 
+// Save a pasted image (base64 or data: URL) as an attachment; returns the stored filename
+export async function writeImageFile(projectPath: string, issueId: string, filename: string, base64: string): Promise<string> {
+  if (!isTauri()) {
+    throw new Error('Saving images is only available in the desktop app')
+  }
+
+  const { invoke } = await import('@tauri-apps/api/core')
+  return invoke<string>('write_image_file', { projectPath, issueId, filename, base64 })
+}
+
 export async function readImageFile(filePath: string): Promise<ImageData | null> {
   if (!isTauri()) {
     console.warn('readImageFile is only available in Tauri mode')
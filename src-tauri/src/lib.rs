@@ -6,22 +6,65 @@ use std::env;
 use std::fs;
 use std::path::PathBuf;
 use std::process::Command;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{LazyLock, Mutex};
 use std::time::{Duration, Instant};
 
 // Global flags for logging
 static LOGGING_ENABLED: AtomicBool = AtomicBool::new(false);
 static VERBOSE_LOGGING: AtomicBool = AtomicBool::new(false);
+// Bumped on every verbose toggle so a pending auto-disable timer knows it's stale
+static VERBOSE_LOGGING_GENERATION: AtomicU64 = AtomicU64::new(0);
+const VERBOSE_LOGGING_DEFAULT_MINUTES: u64 = 30;
+
+// Safe mode (BEADS_SAFE_MODE or set_safe_mode): no background sync, no automatic
+// migrations, and migration commands need an explicit `confirm`
+static SAFE_MODE: AtomicBool = AtomicBool::new(false);
 
 // Sync cooldown: skip redundant syncs within 10 seconds
-static LAST_SYNC_TIME: Mutex<Option<Instant>> = Mutex::new(None);
+// Last successful sync per project (working dir), for the cooldown
+static LAST_SYNC_TIME: Mutex<std::collections::BTreeMap<String, Instant>> = Mutex::new(std::collections::BTreeMap::new());
 const SYNC_COOLDOWN_SECS: u64 = 10;
 
+// Auto-sync: when > 0, a background task syncs every project in SESSION_PROJECTS and
+// FAVORITE_PROJECTS every N seconds, and reads only sync when that project's last sync
+// is older than N. 0 = sync on reads (cooldown).
+static AUTO_SYNC_INTERVAL_SECS: AtomicU64 = AtomicU64::new(0);
+// Bumped when the interval changes so the previous background task exits
+static AUTO_SYNC_GENERATION: AtomicU64 = AtomicU64::new(0);
+// Every project read since startup, in first-read order
+static SESSION_PROJECTS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+// The frontend's favorite projects, kept in sync by `set_favorite_projects`
+static FAVORITE_PROJECTS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+// Auto-backup: active settings, and a generation bumped on change so the old task exits.
+// Backs up every project in SESSION_PROJECTS.
+static AUTO_BACKUP: Mutex<Option<AutoBackupConfig>> = Mutex::new(None);
+static AUTO_BACKUP_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+// False while the window is in the background: change checks return "unchanged"
+// without touching the filesystem until the frontend reactivates polling
+static POLL_ACTIVE: AtomicBool = AtomicBool::new(true);
+
 // Filesystem mtime tracking for change detection (per-project)
 static LAST_KNOWN_MTIME: LazyLock<Mutex<HashMap<String, std::time::SystemTime>>> =
     LazyLock::new(|| Mutex::new(HashMap::new()));
 
+// bd_show cache: project working dir → issue id → (data mtime when cached, issue).
+// Entries are only served while the project's mtime is unchanged and are dropped
+// whenever a mutating CLI command runs for that project.
+type ShowCache = HashMap<String, HashMap<String, (std::time::SystemTime, Issue)>>;
+static SHOW_CACHE: Mutex<Option<ShowCache>> = Mutex::new(None);
+
+// Opt-in env allow-list for the CLI subprocess (None = plain inheritance, loaded at startup)
+static ENV_PASSTHROUGH: Mutex<Option<Vec<String>>> = Mutex::new(None);
+
+// Seconds a CLI call may run before it is killed (0 = no limit); see command_timeout
+static COMMAND_TIMEOUT_SECS: AtomicU64 = AtomicU64::new(DEFAULT_COMMAND_TIMEOUT_SECS);
+
+// Debounce interval of the .beads/ file watcher, applied when a watcher starts
+static WATCH_DEBOUNCE_MS: AtomicU64 = AtomicU64::new(DEFAULT_WATCH_DEBOUNCE_MS);
+
 // Configurable CLI binary name (default: "bd")
 static CLI_BINARY: LazyLock<Mutex<String>> = LazyLock::new(|| Mutex::new("bd".to_string()));
 
@@ -35,6 +78,12 @@ static PROBE_CHILD: LazyLock<Mutex<Option<std::process::Child>>> =
 static BD_PROJECT_LOCKS: LazyLock<Mutex<HashMap<String, std::sync::Arc<Mutex<()>>>>> =
     LazyLock::new(|| Mutex::new(HashMap::new()));
 
+thread_local! {
+    // Project whose lock this thread already holds (see with_project_lock), so nested
+    // CLI calls for it don't try to take the non-reentrant lock again
+    static HELD_PROJECT_LOCK: std::cell::RefCell<Option<String>> = const { std::cell::RefCell::new(None) };
+}
+
 // Cached CLI client info — detected once on first use
 // Stores: (client_type, major, minor, patch)
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -44,8 +93,13 @@ enum CliClient {
     Unknown,
 }
 
-static CLI_CLIENT_INFO: LazyLock<Mutex<Option<(CliClient, u32, u32, u32)>>> =
-    LazyLock::new(|| Mutex::new(None));
+// Detected client/version per CLI binary (as configured), so capability checks
+// never apply one binary's flags to another after a switch.
+type CliInfoCache = HashMap<String, (CliClient, u32, u32, u32)>;
+static CLI_CLIENT_INFO: LazyLock<Mutex<CliInfoCache>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+// `cli_cache_key` of the configured binary, resolved on first use and again whenever
+// the binary is changed, so capability checks don't scan PATH each time
+static CLI_KEY: Mutex<Option<String>> = Mutex::new(None);
 
 // Conditional logging macros
 macro_rules! log_info {
@@ -213,6 +267,7 @@ pub struct BdRawIssue {
     pub metadata: Option<String>,
     pub spec_id: Option<String>,
     pub comment_count: Option<i32>,
+    pub due_at: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -225,7 +280,7 @@ pub struct BdRawComment {
     pub created_at: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Issue {
     pub id: String,
     pub title: String,
@@ -268,9 +323,52 @@ pub struct Issue {
     pub dependency_count: Option<i32>,
     #[serde(rename = "dependentCount")]
     pub dependent_count: Option<i32>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
+    #[serde(rename = "dueAt", skip_serializing_if = "Option::is_none", default)]
+    pub due_at: Option<String>,
+    /// Dependency ids (blockedBy/blocks) that no longer resolve to an existing issue.
+    /// Only populated by `bd_show` with `include_dangling_refs`.
+    #[serde(rename = "danglingRefs", skip_serializing_if = "Option::is_none", default)]
+    pub dangling_refs: Option<Vec<String>>,
+    /// Markdown hints so the UI can pick a renderer per long-text field
+    #[serde(rename = "descriptionIsMarkdown", default)]
+    pub description_is_markdown: bool,
+    #[serde(rename = "designNotesIsMarkdown", default)]
+    pub design_notes_is_markdown: bool,
+    #[serde(rename = "acceptanceCriteriaIsMarkdown", default)]
+    pub acceptance_criteria_is_markdown: bool,
+    #[serde(rename = "workingNotesIsMarkdown", default)]
+    pub working_notes_is_markdown: bool,
+    /// A text field still holds git conflict markers from a bad merge of the JSONL
+    #[serde(rename = "hasConflictMarkers", default)]
+    pub has_conflict_markers: bool,
+    /// Nesting level in tree-ordered lists (`tree: true`), roots are 0
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub depth: Option<usize>,
+    /// Open issues this one blocks directly or transitively. Only set by `bd_show`
+    /// with `include_blocking_count`.
+    #[serde(rename = "transitiveBlockingCount", skip_serializing_if = "Option::is_none", default)]
+    pub transitive_blocking_count: Option<usize>,
+    /// Editable fields (camelCase names) that differ from before the edit. Only set by `bd_update`.
+    #[serde(rename = "changedFields", skip_serializing_if = "Option::is_none", default)]
+    pub changed_fields: Option<Vec<String>>,
+    /// Direct children, so tree views can show an expand control. Set by `bd_show`
+    /// (from the CLI's parent-child dependents) and by tree-ordered lists (children in the list).
+    #[serde(rename = "childCount", skip_serializing_if = "Option::is_none", default)]
+    pub child_count: Option<usize>,
+    #[serde(rename = "hasChildren", skip_serializing_if = "Option::is_none", default)]
+    pub has_children: Option<bool>,
+    /// Most recent comment, only set by lists with `include_latest_comment`
+    #[serde(rename = "latestComment", skip_serializing_if = "Option::is_none", default)]
+    pub latest_comment: Option<LatestComment>,
+    /// When an open issue became ready: its last blocker's `closed_at`, or `created_at`
+    /// if it never had blockers. Only set by lists with `include_ready_since`.
+    #[serde(rename = "readySince", skip_serializing_if = "Option::is_none", default)]
+    pub ready_since: Option<String>,
+    #[serde(rename = "daysReady", skip_serializing_if = "Option::is_none", default)]
+    pub days_ready: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Comment {
     pub id: String,
     pub author: String,
@@ -279,15 +377,27 @@ pub struct Comment {
     pub created_at: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Author and a short excerpt of an issue's newest comment, for triage lists
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LatestComment {
+    pub author: String,
+    pub excerpt: String,
+    #[serde(rename = "createdAt")]
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChildIssue {
     pub id: String,
     pub title: String,
     pub status: String,
     pub priority: String,
+    /// Grandchildren, only filled by `bd_show` with `include_subtree`
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub children: Option<Vec<ChildIssue>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParentIssue {
     pub id: String,
     pub title: String,
@@ -295,7 +405,7 @@ pub struct ParentIssue {
     pub priority: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Relation {
     pub id: String,
     pub title: String,
@@ -313,8 +423,54 @@ pub struct CountResult {
     pub by_type: HashMap<String, usize>,
     #[serde(rename = "byPriority")]
     pub by_priority: HashMap<String, usize>,
+    /// Issues per assignee; unassigned issues are counted under ""
+    #[serde(rename = "byAssignee", default)]
+    pub by_assignee: HashMap<String, usize>,
     #[serde(rename = "lastUpdated")]
     pub last_updated: Option<String>,
+    /// Tombstoned (deleted) issues, excluded from every other figure
+    #[serde(rename = "tombstoneCount", default)]
+    pub tombstone_count: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IssuePage {
+    pub issues: Vec<Issue>,
+    #[serde(rename = "nextCursor")]
+    pub next_cursor: Option<ListCursor>,
+}
+
+/// Edge of a dependency subgraph: `from` blocks/relates to `to`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct DepEdge {
+    pub from: String,
+    pub to: String,
+    #[serde(rename = "type")]
+    pub edge_type: String,
+}
+
+/// Connected component around one issue, bounded by depth and node count
+#[derive(Debug, Serialize)]
+pub struct DepSubgraph {
+    pub nodes: Vec<Issue>,
+    pub edges: Vec<DepEdge>,
+    /// True when the node cap stopped the traversal early
+    pub truncated: bool,
+}
+
+/// Endpoint of a relation pair with basic issue info
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct RelationEnd {
+    pub id: String,
+    pub title: String,
+    pub status: String,
+}
+
+/// One `from → to` link of a given relation type
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct RelationPair {
+    pub from: RelationEnd,
+    pub to: RelationEnd,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -352,29 +508,104 @@ pub struct FsListResult {
 // Options structs for commands
 // ============================================================================
 
+/// Accept `"a"` or `["a", "b"]` for a list field (older callers send a single string).
+fn string_or_vec<'de, D>(deserializer: D) -> Result<Option<Vec<String>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+    Ok(Option::<OneOrMany>::deserialize(deserializer)?.map(|v| match v {
+        OneOrMany::One(s) => vec![s],
+        OneOrMany::Many(v) => v,
+    }))
+}
+
 #[derive(Debug, Deserialize, Default)]
 pub struct ListOptions {
     pub status: Option<Vec<String>>,
     #[serde(rename = "type")]
     pub issue_type: Option<Vec<String>>,
     pub priority: Option<Vec<String>>,
-    pub assignee: Option<String>,
+    /// Any of these assignees; a bare string is accepted too. Empty = no filter.
+    #[serde(default, deserialize_with = "string_or_vec")]
+    pub assignee: Option<Vec<String>>,
+    /// Only issues carrying these labels; unlabeled issues never match
+    pub labels: Option<Vec<String>>,
+    /// "any" (default) or "all" of `labels`
+    #[serde(rename = "labelsMatch")]
+    pub labels_match: Option<String>,
     #[serde(rename = "includeAll")]
     pub include_all: Option<bool>,
+    /// Only issues assigned to the current actor (resolved server-side, see `resolve_actor`)
+    #[serde(rename = "mineOnly")]
+    pub mine_only: Option<bool>,
+    /// Inclusive lower bound on created_at (RFC 3339 timestamp or YYYY-MM-DD)
+    #[serde(rename = "createdAfter")]
+    pub created_after: Option<String>,
+    /// Inclusive upper bound on created_at (RFC 3339 timestamp, or YYYY-MM-DD for the whole day)
+    #[serde(rename = "createdBefore")]
+    pub created_before: Option<String>,
+    /// Issue id filter: a prefix (`frontend-`) or a glob with `*`/`?` (`frontend-*`)
+    #[serde(rename = "idPattern")]
+    pub id_pattern: Option<String>,
+    /// Return issues parent-before-children with a `depth` on each (roots = 0)
+    pub tree: Option<bool>,
+    /// Only issues without a parent (not the child of any epic)
+    #[serde(rename = "topLevelOnly")]
+    pub top_level_only: Option<bool>,
+    /// Issues last modified by this actor. bd/br don't record who last touched
+    /// an issue, so on CLI backends this filter matches nothing.
+    #[serde(rename = "lastActor")]
+    pub last_actor: Option<String>,
+    /// Cut descriptions to about this many characters for card previews
+    #[serde(rename = "descriptionExcerpt")]
+    pub description_excerpt: Option<usize>,
+    /// Attach each issue's newest comment (author + excerpt) as `latestComment`.
+    /// Costs one `bd show` per returned issue with comments (at most
+    /// LATEST_COMMENT_MAX_SHOWS), so pair it with `limit`.
+    #[serde(rename = "includeLatestComment")]
+    pub include_latest_comment: Option<bool>,
+    /// Set `readySince`/`daysReady` on ready issues (open, every blocker closed)
+    #[serde(rename = "includeReadySince")]
+    pub include_ready_since: Option<bool>,
+    /// `bd_list` returns `{issues, parseWarnings}` instead of a bare array, listing
+    /// the entries the CLI returned that couldn't be parsed
+    #[serde(rename = "includeParseWarnings")]
+    pub include_parse_warnings: Option<bool>,
+    /// Page size; when `limit` or `offset` is set, issues come newest first
+    /// (`created_at DESC, id DESC`) and only that slice is returned
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
     pub cwd: Option<String>,
 }
 
+/// Keyset cursor for stable paging in `created_at DESC, id DESC` order.
+/// Points at the last issue of the previous page.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ListCursor {
+    #[serde(rename = "afterCreatedAt")]
+    pub after_created_at: String,
+    #[serde(rename = "afterId")]
+    pub after_id: String,
+}
+
 #[derive(Debug, Deserialize, Default)]
 pub struct CwdOptions {
     pub cwd: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct CreatePayload {
     pub title: String,
     pub description: Option<String>,
     #[serde(rename = "type")]
     pub issue_type: Option<String>,
+    pub status: Option<String>, // Initial status, e.g. "in_progress" to start right away
     pub priority: Option<String>,
     pub assignee: Option<String>,
     pub labels: Option<Vec<String>>,
@@ -391,10 +622,14 @@ pub struct CreatePayload {
     pub parent: Option<String>, // Parent epic ID for hierarchical child
     #[serde(rename = "specId")]
     pub spec_id: Option<String>,
+    pub due: Option<String>, // ISO date or relative ("+3d", "tomorrow", "next friday")
+    /// Issue this one was found while working on; linked with a `discovered-from` relation
+    #[serde(rename = "discoveredFrom")]
+    pub discovered_from: Option<String>,
     pub cwd: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Default)]
 pub struct UpdatePayload {
     pub title: Option<String>,
     pub description: Option<String>,
@@ -418,6 +653,7 @@ pub struct UpdatePayload {
     pub metadata: Option<String>,
     #[serde(rename = "specId")]
     pub spec_id: Option<String>,
+    pub due: Option<String>, // Same formats as CreatePayload::due
     pub cwd: Option<String>,
 }
 
@@ -457,6 +693,33 @@ fn normalize_issue_status(status: &str) -> String {
     }
 }
 
+/// Statuses an issue can be created in (closed/tombstone make no sense for a new issue).
+const CREATE_STATUSES: [&str; 6] = ["open", "in_progress", "blocked", "deferred", "pinned", "hooked"];
+
+/// Status to set right after `create` (which always starts issues open, as not every
+/// bd/br version accepts `create --status`). None when open is already right;
+/// rejects statuses a new issue can't start in.
+fn initial_status(status: Option<&str>) -> Result<Option<&str>, String> {
+    match status {
+        None | Some("") | Some("open") => Ok(None),
+        Some(s) if CREATE_STATUSES.contains(&s) => Ok(Some(s)),
+        Some(s) => Err(format!("Invalid status for a new issue: {}", s)),
+    }
+}
+
+fn transform_comment(c: BdRawComment) -> Comment {
+    Comment {
+        id: match c.id {
+            serde_json::Value::Number(n) => n.to_string(),
+            serde_json::Value::String(s) => s,
+            _ => "0".to_string(),
+        },
+        author: c.author,
+        content: c.text.or(c.content).unwrap_or_default(),
+        created_at: c.created_at,
+    }
+}
+
 fn transform_issue(raw: BdRawIssue) -> Issue {
     // Parent info - dependencies array now contains relationship info, not full issue details
     // For now, we just use the parent ID if available
@@ -478,6 +741,7 @@ fn transform_issue(raw: BdRawIssue) -> Issue {
                 title: c.title.clone().unwrap_or_default(),
                 status: normalize_issue_status(&c.status.clone().unwrap_or_else(|| "open".to_string())),
                 priority: priority_to_string(c.priority.unwrap_or(3)),
+                children: None,
             })
             .collect()
     }).filter(|v: &Vec<ChildIssue>| !v.is_empty());
@@ -556,30 +820,32 @@ fn transform_issue(raw: BdRawIssue) -> Issue {
         raw.comments.as_ref().map(|c| c.len() as i32)
     });
 
+    let description_is_markdown = raw.description.as_deref().is_some_and(looks_like_markdown);
+    let design_notes_is_markdown = raw.design.as_deref().is_some_and(looks_like_markdown);
+    let acceptance_criteria_is_markdown = raw.acceptance_criteria.as_deref().is_some_and(looks_like_markdown);
+    let working_notes_is_markdown = raw.notes.as_deref().is_some_and(looks_like_markdown);
+    let has_conflict_markers = [&raw.description, &raw.design, &raw.acceptance_criteria, &raw.notes]
+        .iter()
+        .any(|field| field.as_deref().is_some_and(text_has_conflict_markers));
+
+    let status = normalize_issue_status(&raw.status);
+    // Older CLIs keep `closed_at` when an issue goes back to open; only a closed
+    // (or deleted) issue has a close date.
+    let closed_at = raw.closed_at.filter(|_| matches!(status.as_str(), "closed" | "tombstone"));
+
     Issue {
         id: raw.id,
         title: raw.title,
         description: raw.description.unwrap_or_default(),
         issue_type: normalize_issue_type(&raw.issue_type),
-        status: normalize_issue_status(&raw.status),
+        status,
         priority: priority_to_string(raw.priority),
         assignee: raw.assignee,
         labels: raw.labels.unwrap_or_default(),
         created_at: raw.created_at,
         updated_at: raw.updated_at,
-        closed_at: raw.closed_at,
-        comments: raw.comments.unwrap_or_default().into_iter().map(|c| {
-            Comment {
-                id: match c.id {
-                    serde_json::Value::Number(n) => n.to_string(),
-                    serde_json::Value::String(s) => s,
-                    _ => "0".to_string(),
-                },
-                author: c.author,
-                content: c.text.or(c.content).unwrap_or_default(),
-                created_at: c.created_at,
-            }
-        }).collect(),
+        closed_at,
+        comments: raw.comments.unwrap_or_default().into_iter().map(transform_comment).collect(),
         blocked_by: {
             // Try raw.blocked_by first (if bd ever populates it directly)
             let mut bb = raw.blocked_by.unwrap_or_default();
@@ -634,15 +900,71 @@ fn transform_issue(raw: BdRawIssue) -> Issue {
         dependent_count: raw.dependent_count.or_else(|| {
             raw.dependents.as_ref().map(|d| d.len() as i32)
         }),
-    }
+        due_at: raw.due_at,
+        dangling_refs: None,
+        description_is_markdown,
+        design_notes_is_markdown,
+        acceptance_criteria_is_markdown,
+        working_notes_is_markdown,
+        has_conflict_markers,
+        depth: None,
+        transitive_blocking_count: None,
+        changed_fields: None,
+        child_count: None,
+        has_children: None,
+        latest_comment: None,
+        ready_since: None,
+        days_ready: None,
+    }
+}
+
+/// Git conflict hunk in free text: a `<<<<<<<` line followed later by a `>>>>>>>` line.
+/// A lone `=======` is a markdown heading underline, so it doesn't count on its own.
+fn text_has_conflict_markers(text: &str) -> bool {
+    let mut lines = text.lines();
+    lines.any(|l| l.starts_with("<<<<<<<")) && lines.any(|l| l.starts_with(">>>>>>>"))
+}
+
+/// Lightweight markdown sniffing: headings, list items, code fences, block quotes or links.
+fn looks_like_markdown(text: &str) -> bool {
+    if text.contains("```") || text.contains("](") {
+        return true;
+    }
+    text.lines().map(str::trim_start).any(|line| {
+        let heading = line.starts_with('#') && line.trim_start_matches('#').starts_with(' ');
+        // Also covers task-list items (`- [ ]`, `- [x]`)
+        let bullet = line.starts_with("- ") || line.starts_with("* ") || line.starts_with("+ ");
+        let numbered = line.split_once(". ").is_some_and(|(n, _)| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+        heading || bullet || numbered || line.starts_with("> ")
+    })
 }
 
 /// Parse issues with tolerance for malformed entries
 /// Returns all successfully parsed issues and logs failures
 fn parse_issues_tolerant(output: &str, context: &str) -> Result<Vec<BdRawIssue>, String> {
+    parse_issues_report(output, context).map(|parsed| parsed.issues)
+}
+
+/// An array entry `parse_issues_report` had to drop.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SkippedIssue {
+    /// Position in the CLI's output array
+    pub index: usize,
+    pub id: Option<String>,
+    pub reason: String,
+}
+
+#[derive(Debug)]
+struct ParsedIssues {
+    issues: Vec<BdRawIssue>,
+    skipped: Vec<SkippedIssue>,
+}
+
+/// `parse_issues_tolerant`, also reporting which entries were skipped and why.
+fn parse_issues_report(output: &str, context: &str) -> Result<ParsedIssues, String> {
     // First try strict parsing
     if let Ok(issues) = serde_json::from_str::<Vec<BdRawIssue>>(output) {
-        return Ok(issues);
+        return Ok(ParsedIssues { issues, skipped: Vec::new() });
     }
 
     // If strict parsing fails, try tolerant parsing
@@ -675,16 +997,17 @@ fn parse_issues_tolerant(output: &str, context: &str) -> Result<Vec<BdRawIssue>,
     };
 
     let mut issues = Vec::new();
-    let mut failed_count = 0;
+    let mut skipped = Vec::new();
 
     for (i, obj) in arr.iter().enumerate() {
         let obj_str = serde_json::to_string(obj).unwrap_or_default();
         match serde_json::from_str::<BdRawIssue>(&obj_str) {
             Ok(issue) => issues.push(issue),
             Err(e) => {
-                failed_count += 1;
-                let id = obj.get("id").and_then(|v| v.as_str()).unwrap_or("unknown");
+                let raw_id = obj.get("id").and_then(|v| v.as_str());
+                let id = raw_id.unwrap_or("unknown");
                 log_error!("[{}] Skipping issue {} (id={}): {}", context, i, id, e);
+                skipped.push(SkippedIssue { index: i, id: raw_id.map(String::from), reason: e.to_string() });
 
                 // Log which fields are present/missing
                 if let Some(obj_map) = obj.as_object() {
@@ -702,11 +1025,11 @@ fn parse_issues_tolerant(output: &str, context: &str) -> Result<Vec<BdRawIssue>,
         }
     }
 
-    if failed_count > 0 {
-        log_warn!("[{}] Parsed {} issues, skipped {} malformed entries", context, issues.len(), failed_count);
+    if !skipped.is_empty() {
+        log_warn!("[{}] Parsed {} issues, skipped {} malformed entries", context, issues.len(), skipped.len());
     }
 
-    Ok(issues)
+    Ok(ParsedIssues { issues, skipped })
 }
 
 fn get_extended_path() -> String {
@@ -753,14 +1076,270 @@ fn new_command(program: &str) -> Command {
     cmd
 }
 
+/// Parse a bd/SQLite timestamp into UTC `(unix_seconds, nanoseconds)`.
+/// Accepts RFC 3339 (`2025-06-15T09:30:00.123Z`, `2025-06-15T11:30:00+02:00`) and
+/// SQLite's `datetime('now')` format (`2025-06-15 09:30:00`), which is UTC without a suffix.
+fn parse_timestamp_utc(value: &str) -> Option<(i64, u32)> {
+    let s = value.trim();
+    let b = s.as_bytes();
+    if b.len() < 19
+        || b[4] != b'-'
+        || b[7] != b'-'
+        || (b[10] != b'T' && b[10] != b' ')
+        || b[13] != b':'
+        || b[16] != b':'
+    {
+        return None;
+    }
+    let num = |range: std::ops::Range<usize>| -> Option<i64> { s.get(range)?.parse().ok() };
+    let (year, month, day) = (num(0..4)?, num(5..7)?, num(8..10)?);
+    let (hour, minute, second) = (num(11..13)?, num(14..16)?, num(17..19)?);
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    let mut rest = &s[19..];
+    let mut nanos: u32 = 0;
+    if let Some(frac) = rest.strip_prefix('.') {
+        let digits = frac.bytes().take_while(|c| c.is_ascii_digit()).count();
+        if digits == 0 {
+            return None;
+        }
+        let mut padded = frac[..digits.min(9)].to_string();
+        while padded.len() < 9 {
+            padded.push('0');
+        }
+        nanos = padded.parse().ok()?;
+        rest = &frac[digits..];
+    }
+
+    let offset_secs: i64 = match rest {
+        "" | "Z" | "z" => 0,
+        _ => {
+            let sign = match rest.as_bytes()[0] {
+                b'+' => 1,
+                b'-' => -1,
+                _ => return None,
+            };
+            let tz = rest[1..].replace(':', "");
+            if tz.len() != 4 {
+                return None;
+            }
+            let (h, m): (i64, i64) = (tz.get(..2)?.parse().ok()?, tz.get(2..)?.parse().ok()?);
+            sign * (h * 3600 + m * 60)
+        }
+    };
+
+    let days = days_from_civil(year, month, day);
+    Some((days * 86400 + hour * 3600 + minute * 60 + second - offset_secs, nanos))
+}
+
+/// Format unix seconds as a normalized UTC ISO-8601 string (`2025-06-15T09:30:00Z`).
+fn format_timestamp_utc(secs: i64) -> String {
+    let (year, month, day) = civil_from_days(secs.div_euclid(86400));
+    let tod = secs.rem_euclid(86400);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year, month, day, tod / 3600, (tod % 3600) / 60, tod % 60
+    )
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date (Howard Hinnant's algorithm).
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// Inverse of `days_from_civil`: (year, month, day) for days since 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Resolve a due-date input to `YYYY-MM-DD`, relative to `today` (days since epoch).
+/// Accepts ISO dates, `today`/`tomorrow`, `+Nd`/`+Nw`, `in N days|weeks`, and
+/// weekday names (`friday`, `next friday` — both the next one after today).
+fn parse_relative_date(input: &str, today: i64) -> Result<String, String> {
+    let text = input.trim().to_lowercase();
+    let invalid = || format!("Unrecognized date: {} (try 2025-06-30, +3d, +2w, tomorrow, next friday)", input.trim());
+    let format_day = |days: i64| {
+        let (y, m, d) = civil_from_days(days);
+        format!("{:04}-{:02}-{:02}", y, m, d)
+    };
+
+    if let Some((secs, _)) = parse_timestamp_utc(&format!("{}T00:00:00Z", text)) {
+        let days = secs.div_euclid(86400);
+        // Reject dates that only parsed by rolling over (2025-02-30)
+        return if format_day(days) == text { Ok(text) } else { Err(invalid()) };
+    }
+
+    let offset = |count: &str, unit: &str| -> Option<i64> {
+        let n: i64 = count.parse().ok()?;
+        match unit {
+            "d" | "day" | "days" => Some(n),
+            "w" | "week" | "weeks" => n.checked_mul(7),
+            _ => None,
+        }
+    };
+    let days = match text.as_str() {
+        "today" => Some(0),
+        "tomorrow" => Some(1),
+        _ => {
+            if let Some(rest) = text.strip_prefix('+') {
+                let split = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+                offset(&rest[..split], &rest[split..])
+            } else if let Some(rest) = text.strip_prefix("in ") {
+                let mut parts = rest.split_whitespace();
+                match (parts.next(), parts.next(), parts.next()) {
+                    (Some(count), Some(unit), None) => offset(count, unit),
+                    _ => None,
+                }
+            } else {
+                const WEEKDAYS: [&str; 7] = ["monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday"];
+                let name = text.strip_prefix("next ").unwrap_or(&text);
+                WEEKDAYS.iter().position(|d| *d == name).map(|target| {
+                    // 1970-01-01 was a Thursday (index 3 with Monday = 0)
+                    let current = (today + 3).rem_euclid(7);
+                    let ahead = (target as i64 - current).rem_euclid(7);
+                    if ahead == 0 { 7 } else { ahead }
+                })
+            }
+        }
+    };
+    match days {
+        Some(n) if n >= 0 => today.checked_add(n)
+            .filter(|day| *day <= days_from_civil(9999, 12, 31))
+            .map(format_day)
+            .ok_or_else(|| format!("Date is too far in the future: {}", input.trim())),
+        _ => Err(invalid()),
+    }
+}
+
+/// `parse_relative_date` against today's UTC date.
+fn resolve_due_date(input: &str) -> Result<String, String> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64;
+    parse_relative_date(input, now.div_euclid(86400))
+}
+
+/// Most recent of the given timestamps, normalized to UTC.
+/// Unparseable values are ignored; if none parse, falls back to the lexicographic max.
+fn latest_timestamp<'a>(values: impl Iterator<Item = &'a str>) -> Option<String> {
+    let mut best: Option<(i64, u32)> = None;
+    let mut fallback: Option<&str> = None;
+    for value in values {
+        match parse_timestamp_utc(value) {
+            Some(ts) => {
+                if best.map_or(true, |b| ts > b) {
+                    best = Some(ts);
+                }
+            }
+            None => {
+                if fallback.map_or(true, |f| value > f) {
+                    fallback = Some(value);
+                }
+            }
+        }
+    }
+    best.map(|(secs, _)| format_timestamp_utc(secs))
+        .or_else(|| fallback.map(String::from))
+}
+
 // ============================================================================
 // CLI Binary Configuration
 // ============================================================================
 
-#[derive(Debug, Serialize, Deserialize)]
+/// All persisted app settings (settings.json).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 struct AppConfig {
     #[serde(default = "default_cli_binary")]
     cli_binary: String,
+    /// Per-project settings, keyed by project path
+    #[serde(default)]
+    projects: HashMap<String, ProjectConfig>,
+    /// Opt-in restriction: when set, only these patterns (exact names or `PREFIX*`)
+    /// of the bd namespaces reach the CLI subprocess. None inherits the full environment.
+    #[serde(default)]
+    env_passthrough: Option<Vec<String>>,
+    /// Background auto-sync interval in seconds (0 = sync on reads)
+    #[serde(default)]
+    auto_sync_interval_secs: u64,
+    /// Periodic JSONL backups of every project opened this session (None = off)
+    #[serde(default)]
+    auto_backup: Option<AutoBackupConfig>,
+    /// Kill CLI calls running longer than this (0 = no limit); sync-like commands get longer
+    #[serde(default = "default_command_timeout_secs")]
+    command_timeout_secs: u64,
+    /// Project path → recent `bd_search` queries, newest first
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    recent_searches: HashMap<String, Vec<String>>,
+    /// File watcher debounce; clamped to WATCH_DEBOUNCE_RANGE_MS when applied
+    #[serde(default = "default_watch_debounce_ms")]
+    watch_debounce_ms: u64,
+    /// Write the app log file (debug panel toggle)
+    #[serde(default)]
+    logging_enabled: bool,
+    /// Include verbose entries in the log; timed sessions (`set_verbose_logging_timed`) aren't persisted
+    #[serde(default)]
+    verbose_logging: bool,
+}
+
+const DEFAULT_COMMAND_TIMEOUT_SECS: u64 = 30;
+
+fn default_command_timeout_secs() -> u64 {
+    DEFAULT_COMMAND_TIMEOUT_SECS
+}
+
+const DEFAULT_WATCH_DEBOUNCE_MS: u64 = 1000;
+/// Below this the watcher fires on every intermediate write; above it updates feel stuck
+const WATCH_DEBOUNCE_RANGE_MS: (u64, u64) = (200, 5000);
+
+fn default_watch_debounce_ms() -> u64 {
+    DEFAULT_WATCH_DEBOUNCE_MS
+}
+
+fn clamp_watch_debounce(ms: u64) -> u64 {
+    ms.clamp(WATCH_DEBOUNCE_RANGE_MS.0, WATCH_DEBOUNCE_RANGE_MS.1)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AutoBackupConfig {
+    dir: String,
+    interval_secs: u64,
+    /// Backups kept per project; older ones are deleted after each run
+    keep: usize,
+}
+
+/// bd-relevant env namespaces an opt-in allow-list restricts; other vars always pass
+const BD_ENV_NAMESPACES: &[&str] = &["BEADS_", "BD_", "DOLT_", "GIT_"];
+
+/// Per-project settings persisted in settings.json.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ProjectConfig {
+    /// Type applied by `bd_create` when the payload has none
+    #[serde(rename = "defaultIssueType", default, skip_serializing_if = "Option::is_none")]
+    pub default_issue_type: Option<String>,
+    /// Priority ("p0".."p4") applied by `bd_create` when the payload has none
+    #[serde(rename = "defaultPriority", default, skip_serializing_if = "Option::is_none")]
+    pub default_priority: Option<String>,
+    /// Lowercase labels on create/update/add (off by default: labels are case-sensitive)
+    #[serde(rename = "lowercaseLabels", default, skip_serializing_if = "std::ops::Not::not")]
+    pub lowercase_labels: bool,
 }
 
 fn default_cli_binary() -> String {
@@ -784,6 +1363,15 @@ impl Default for AppConfig {
     fn default() -> Self {
         Self {
             cli_binary: default_cli_binary(),
+            projects: HashMap::new(),
+            env_passthrough: None,
+            auto_sync_interval_secs: 0,
+            auto_backup: None,
+            command_timeout_secs: DEFAULT_COMMAND_TIMEOUT_SECS,
+            recent_searches: HashMap::new(),
+            watch_debounce_ms: DEFAULT_WATCH_DEBOUNCE_MS,
+            logging_enabled: false,
+            verbose_logging: false,
         }
     }
 }
@@ -796,36 +1384,204 @@ fn get_config_path() -> PathBuf {
 }
 
 fn load_config() -> AppConfig {
+    read_config().unwrap_or_else(|e| {
+        log::warn!("[config] {}", e);
+        AppConfig::default()
+    })
+}
+
+/// settings.json as stored; defaults when there is none yet, an error when it can't be
+/// read or parsed (so a writer never replaces a damaged file with defaults).
+fn read_config() -> Result<AppConfig, String> {
+    read_config_at(&get_config_path())
+}
+
+/// Last settings read or saved, with the file they belong to. Saves replace it, so
+/// reads don't re-parse settings.json (or, with no file yet, re-run the `--version`
+/// probes of the default binary) on every call. Read errors are not cached.
+static CONFIG_CACHE: Mutex<Option<(PathBuf, AppConfig)>> = Mutex::new(None);
+
+fn read_config_at(path: &std::path::Path) -> Result<AppConfig, String> {
+    if let Some((cached_path, config)) = CONFIG_CACHE.lock().unwrap().as_ref() {
+        if cached_path == path {
+            return Ok(config.clone());
+        }
+    }
+    let config = if path.exists() {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read settings.json: {}", e))?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse settings.json: {}", e))?
+    } else {
+        AppConfig::default()
+    };
+    let mut cache = CONFIG_CACHE.lock().unwrap();
+    // A save that finished while this read was on disk has the newer settings
+    if !matches!(cache.as_ref(), Some((cached_path, _)) if cached_path == path) {
+        *cache = Some((path.to_path_buf(), config.clone()));
+    }
+    Ok(config)
+}
+
+/// Run `read` on the current settings without cloning all of them (defaults when
+/// settings.json is unreadable, like `load_config`).
+fn with_config<T>(read: impl FnOnce(&AppConfig) -> T) -> T {
     let path = get_config_path();
-    if path.exists() {
-        match fs::read_to_string(&path) {
-            Ok(content) => match serde_json::from_str(&content) {
-                Ok(config) => return config,
-                Err(e) => log::warn!("[config] Failed to parse settings.json: {}", e),
-            },
-            Err(e) => log::warn!("[config] Failed to read settings.json: {}", e),
+    if let Some((cached_path, config)) = CONFIG_CACHE.lock().unwrap().as_ref() {
+        if *cached_path == path {
+            return read(config);
         }
     }
-    AppConfig::default()
+    read(&load_config())
+}
+
+/// Serializes settings.json writers: each one reads, changes and writes the file.
+static CONFIG_LOCK: Mutex<()> = Mutex::new(());
+
+/// Apply `change` to the stored settings and save them, under CONFIG_LOCK.
+/// Nothing is written when the current file can't be read.
+fn update_config<T>(change: impl FnOnce(&mut AppConfig) -> T) -> Result<T, String> {
+    update_config_at(&get_config_path(), change)
 }
 
+fn update_config_at<T>(path: &std::path::Path, change: impl FnOnce(&mut AppConfig) -> T) -> Result<T, String> {
+    let _guard = CONFIG_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut config = read_config_at(path).map_err(|e| format!("Settings not saved: {}", e))?;
+    let out = change(&mut config);
+    save_config_to(path, &config)?;
+    Ok(out)
+}
+
+/// Write settings.json. Callers other than `update_config` hold CONFIG_LOCK.
 fn save_config(config: &AppConfig) -> Result<(), String> {
-    let path = get_config_path();
+    save_config_to(&get_config_path(), config)
+}
+
+fn save_config_to(path: &std::path::Path, config: &AppConfig) -> Result<(), String> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)
             .map_err(|e| format!("Failed to create config directory: {}", e))?;
     }
     let json = serde_json::to_string_pretty(config)
         .map_err(|e| format!("Failed to serialize config: {}", e))?;
-    fs::write(&path, json)
+    // Write to a temp file and rename so a crash never leaves a half-written settings.json
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, json)
+        .map_err(|e| format!("Failed to write config: {}", e))?;
+    fs::rename(&tmp_path, path)
         .map_err(|e| format!("Failed to write config: {}", e))?;
+    *CONFIG_CACHE.lock().unwrap() = Some((path.to_path_buf(), config.clone()));
     Ok(())
 }
 
+/// Push persisted settings into the runtime globals (startup and `set_config`).
+fn apply_config(config: &AppConfig) {
+    {
+        let mut binary = CLI_BINARY.lock().unwrap();
+        if *binary != config.cli_binary {
+            *binary = config.cli_binary.clone();
+            drop(binary);
+            reset_bd_version_cache();
+        }
+    }
+    *ENV_PASSTHROUGH.lock().unwrap() = config.env_passthrough.clone();
+    COMMAND_TIMEOUT_SECS.store(config.command_timeout_secs, Ordering::Relaxed);
+    WATCH_DEBOUNCE_MS.store(clamp_watch_debounce(config.watch_debounce_ms), Ordering::Relaxed);
+    LOGGING_ENABLED.store(config.logging_enabled, Ordering::Relaxed);
+    if VERBOSE_LOGGING.load(Ordering::Relaxed) != config.verbose_logging {
+        VERBOSE_LOGGING_GENERATION.fetch_add(1, Ordering::Relaxed);
+        VERBOSE_LOGGING.store(config.verbose_logging, Ordering::Relaxed);
+    }
+    if AUTO_SYNC_INTERVAL_SECS.load(Ordering::Relaxed) != config.auto_sync_interval_secs {
+        start_auto_sync(config.auto_sync_interval_secs);
+    }
+    if *AUTO_BACKUP.lock().unwrap() != config.auto_backup {
+        start_auto_backup(config.auto_backup.clone());
+    }
+}
+
 fn get_cli_binary() -> String {
     CLI_BINARY.lock().unwrap().clone()
 }
 
+/// Whether `name` matches an allow-list pattern (exact, or prefix with trailing `*`).
+fn env_pattern_matches(pattern: &str, name: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => name.starts_with(prefix),
+        None => name == pattern,
+    }
+}
+
+/// Strip bd-namespace vars (BEADS_*, BD_*, DOLT_*, GIT_*) that `allow` doesn't match.
+/// Without an allow-list the command inherits the environment untouched.
+fn restrict_env(cmd: &mut Command, vars: impl Iterator<Item = (String, String)>, allow: Option<&[String]>) {
+    let Some(allow) = allow else { return };
+    for (name, _) in vars {
+        let in_namespace = BD_ENV_NAMESPACES.iter().any(|ns| name.starts_with(ns));
+        if in_namespace && !allow.iter().any(|p| env_pattern_matches(p, &name)) {
+            cmd.env_remove(name);
+        }
+    }
+}
+
+/// Apply the opt-in env restriction to a CLI command. Call before setting PATH/BEADS_PATH
+/// so those explicit values win.
+fn apply_env_passthrough(cmd: &mut Command) {
+    let allow = ENV_PASSTHROUGH.lock().unwrap().clone();
+    restrict_env(cmd, env::vars(), allow.as_deref());
+}
+
+/// Working directory for a command: explicit cwd, then BEADS_PATH, then the process cwd.
+/// Also the key for per-project state (`AppConfig.projects`, caches).
+fn resolve_working_dir(cwd: Option<&str>) -> String {
+    cwd.map(String::from)
+        .or_else(|| env::var("BEADS_PATH").ok())
+        .unwrap_or_else(|| {
+            env::current_dir()
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_else(|_| ".".to_string())
+        })
+}
+
+fn load_project_config(cwd: Option<&str>) -> ProjectConfig {
+    let key = resolve_working_dir(cwd);
+    with_config(|config| config.projects.get(&key).cloned().unwrap_or_default())
+}
+
+/// The project's `lowercase_labels` setting, read from the cached settings.
+fn project_lowercase_labels(cwd: Option<&str>) -> bool {
+    let key = resolve_working_dir(cwd);
+    with_config(|config| config.projects.get(&key).is_some_and(|p| p.lowercase_labels))
+}
+
+/// Trim, optionally lowercase, drop empties and de-duplicate labels (first occurrence wins).
+fn normalize_labels(labels: &[String], lowercase: bool) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for label in labels {
+        let trimmed = label.trim();
+        let label = if lowercase { trimmed.to_lowercase() } else { trimmed.to_string() };
+        if !label.is_empty() && !out.contains(&label) {
+            out.push(label);
+        }
+    }
+    out
+}
+
+/// Fill in type/priority from the project defaults when the payload omits them.
+/// Without configured defaults the CLI keeps deciding (its own task/p2 fallback).
+fn apply_project_defaults(payload: &mut CreatePayload, config: &ProjectConfig) {
+    if payload.issue_type.as_deref().map_or(true, str::is_empty) {
+        if let Some(ref t) = config.default_issue_type {
+            payload.issue_type = Some(t.clone());
+        }
+    }
+    if payload.priority.as_deref().map_or(true, str::is_empty) {
+        if let Some(ref p) = config.default_priority {
+            payload.priority = Some(p.clone());
+        }
+    }
+}
+
 // ============================================================================
 // CLI Client Detection (bd vs br)
 // ============================================================================
@@ -868,17 +1624,41 @@ fn parse_bd_version(version_str: &str) -> Option<(u32, u32, u32)> {
 
 /// Detect and cache the CLI client type and version. Runs `binary --version` once.
 fn get_cli_client_info() -> Option<(CliClient, u32, u32, u32)> {
+    let key = CLI_KEY.lock().unwrap().clone();
+    let path_var = get_extended_path();
+    let key = key.unwrap_or_else(|| {
+        let key = cli_cache_key(&get_cli_binary(), &path_var);
+        *CLI_KEY.lock().unwrap() = Some(key.clone());
+        key
+    });
+    cli_client_info_keyed(&key, &get_cli_binary(), &path_var)
+}
+
+/// Cache key for a binary: the executable it resolves to on `path_var`, so the same
+/// name pointing at another install (Homebrew vs manual) is detected separately.
+fn cli_cache_key(binary: &str, path_var: &str) -> String {
+    find_in_path(binary, path_var)
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|| binary.to_string())
+}
+
+#[cfg(test)]
+fn cli_client_info_for(binary: &str, path_var: &str) -> Option<(CliClient, u32, u32, u32)> {
+    cli_client_info_keyed(&cli_cache_key(binary, path_var), binary, path_var)
+}
+
+/// Client info for `binary`, whose `cli_cache_key` is `key`.
+fn cli_client_info_keyed(key: &str, binary: &str, path_var: &str) -> Option<(CliClient, u32, u32, u32)> {
     let mut cached = CLI_CLIENT_INFO.lock().unwrap();
-    if let Some(info) = *cached {
-        return Some(info);
+    if let Some(info) = cached.get(key) {
+        return Some(*info);
     }
 
-    let binary = get_cli_binary();
     // Run from temp dir to avoid bd auto-migrating projects in cwd
-    let output = new_command(&binary)
+    let output = new_command(key)
         .arg("--version")
         .current_dir(std::env::temp_dir())
-        .env("PATH", get_extended_path())
+        .env("PATH", path_var)
         .output()
         .ok()?;
 
@@ -900,7 +1680,7 @@ fn get_cli_client_info() -> Option<(CliClient, u32, u32, u32)> {
             CliClient::Unknown => "unknown",
         };
         log_info!("[cli_detect] Detected {} client v{}.{}.{}", client_name, major, minor, patch);
-        *cached = Some(info);
+        cached.insert(key.to_string(), info);
         Some(info)
     } else {
         log_warn!("[cli_detect] Could not parse version from: {}", trimmed);
@@ -963,13 +1743,42 @@ fn supports_delete_hard_flag() -> bool {
     }
 }
 
+/// Returns true if the CLI can delete a single comment (`comments delete <issue> <comment>`).
+/// - bd >= 0.56.0: YES
+/// - bd < 0.56.0: NO (comments can only be added)
+/// - br / unknown: NO (safe default)
+fn supports_comment_delete(client: Option<(CliClient, u32, u32, u32)>) -> bool {
+    match client {
+        Some((CliClient::Bd, major, minor, _)) => major > 0 || minor >= 56,
+        _ => false,
+    }
+}
+
+/// Returns true if `create`/`update` accept `--due`.
+/// - bd >= 0.40.0: YES
+/// - bd < 0.40.0: NO
+/// - br: YES
+/// - unknown: NO (safe default)
+fn supports_due_flag(client: Option<(CliClient, u32, u32, u32)>) -> bool {
+    match client {
+        Some((CliClient::Bd, major, minor, _)) => major > 0 || minor >= 40,
+        Some((CliClient::Br, _, _, _)) => true,
+        _ => false,
+    }
+}
+
 /// Returns true if the CLI uses the Dolt backend (inverse of uses_jsonl_files).
 /// - br: NEVER (frozen on SQLite+JSONL architecture)
 /// - bd >= 0.50.0: YES (Dolt only)
 /// - bd < 0.50.0: NO (SQLite+JSONL)
 /// - unknown: NO (safe default)
 fn uses_dolt_backend() -> bool {
-    match get_cli_client_info() {
+    client_uses_dolt(get_cli_client_info())
+}
+
+/// `uses_dolt_backend` for an already-detected client.
+fn client_uses_dolt(client: Option<(CliClient, u32, u32, u32)>) -> bool {
+    match client {
         Some((CliClient::Br, _, _, _)) => false, // br never uses Dolt
         Some((CliClient::Bd, major, minor, _)) => major > 0 || minor >= 50,
         Some((CliClient::Unknown, _, _, _)) => false,
@@ -983,85 +1792,338 @@ fn uses_dolt_backend() -> bool {
 /// - bd < 0.50.0: NEVER (CLI doesn't support Dolt)
 /// - bd >= 0.50.0: checks if `.dolt/` directory exists inside the beads dir
 fn project_uses_dolt(beads_dir: &std::path::Path) -> bool {
-    match get_cli_client_info() {
-        Some((CliClient::Br, _, _, _)) => false,
-        Some((CliClient::Bd, major, minor, _)) if major == 0 && minor < 50 => false,
-        _ => {
-            // Check .beads/.dolt (legacy) or .beads/dolt/<name>/.dolt (bd 0.52+)
-            if beads_dir.join(".dolt").is_dir() {
-                return true;
-            }
-            // Check metadata.json for backend: "dolt"
-            let metadata_path = beads_dir.join("metadata.json");
-            if let Ok(content) = std::fs::read_to_string(&metadata_path) {
-                if content.contains("\"backend\":\"dolt\"") || content.contains("\"backend\": \"dolt\"") {
-                    // Verify dolt database actually exists
-                    let dolt_dir = beads_dir.join("dolt");
-                    if dolt_dir.is_dir() {
-                        // Check if any subdirectory has .dolt
-                        if let Ok(entries) = std::fs::read_dir(&dolt_dir) {
-                            for entry in entries.flatten() {
-                                if entry.path().join(".dolt").is_dir() {
-                                    return true;
-                                }
-                            }
+    resolve_project_backend(get_cli_client_info(), beads_dir) == "dolt"
+}
+
+/// Effective backend serving a project: "br", "dolt" or "bd" ("unknown" if the
+/// CLI couldn't be detected and the project has no Dolt database).
+/// The CLI client is global but the storage backend is per-project.
+fn resolve_project_backend(client: Option<(CliClient, u32, u32, u32)>, beads_dir: &std::path::Path) -> &'static str {
+    match client {
+        Some((CliClient::Br, _, _, _)) => "br",
+        Some((CliClient::Bd, major, minor, _)) if major == 0 && minor < 50 => "bd",
+        _ if beads_dir_has_dolt(beads_dir) => "dolt",
+        Some((CliClient::Bd, _, _, _)) => "bd",
+        _ => "unknown",
+    }
+}
+
+/// Filesystem check for a Dolt database inside a `.beads` directory.
+fn beads_dir_has_dolt(beads_dir: &std::path::Path) -> bool {
+    // Check .beads/.dolt (legacy) or .beads/dolt/<name>/.dolt (bd 0.52+)
+    if beads_dir.join(".dolt").is_dir() {
+        return true;
+    }
+    // Check metadata.json for backend: "dolt"
+    let metadata_path = beads_dir.join("metadata.json");
+    if let Ok(content) = std::fs::read_to_string(&metadata_path) {
+        if content.contains("\"backend\":\"dolt\"") || content.contains("\"backend\": \"dolt\"") {
+            // Verify dolt database actually exists
+            let dolt_dir = beads_dir.join("dolt");
+            if dolt_dir.is_dir() {
+                // Check if any subdirectory has .dolt
+                if let Ok(entries) = std::fs::read_dir(&dolt_dir) {
+                    for entry in entries.flatten() {
+                        if entry.path().join(".dolt").is_dir() {
+                            return true;
                         }
                     }
                 }
             }
-            false
         }
     }
+    false
 }
 
-/// Reset the cached client info (called when CLI binary path changes).
+/// Re-resolve the current binary and forget its cached client info (called when the
+/// CLI binary path changes, so a replaced binary at the same path is detected afresh).
 fn reset_bd_version_cache() {
-    let mut cached = CLI_CLIENT_INFO.lock().unwrap();
-    *cached = None;
-}
-
-fn execute_bd(command: &str, args: &[String], cwd: Option<&str>) -> Result<String, String> {
-    let working_dir = cwd
-        .map(String::from)
-        .or_else(|| env::var("BEADS_PATH").ok())
-        .unwrap_or_else(|| {
-            env::current_dir()
-                .map(|p| p.to_string_lossy().to_string())
-                .unwrap_or_else(|_| ".".to_string())
-        });
+    let key = forget_cli_info(&get_cli_binary(), &get_extended_path());
+    *CLI_KEY.lock().unwrap() = Some(key);
+}
+
+/// Drop the cached client info of `binary`, returning its cache key.
+fn forget_cli_info(binary: &str, path_var: &str) -> String {
+    let key = cli_cache_key(binary, path_var);
+    CLI_CLIENT_INFO.lock().unwrap().remove(&key);
+    key
+}
+
+/// Build the full argument list for a CLI invocation.
+/// Splits subcommands like "comments add" on whitespace, then appends the caller's args,
+/// `--no-daemon` (when supported) and `--json` (when requested).
+fn build_bd_args(command: &str, args: &[String], no_daemon: bool, json: bool) -> Vec<String> {
+    let mut full_args: Vec<String> = command.split_whitespace().map(String::from).collect();
+    full_args.extend(args.iter().cloned());
+    if no_daemon {
+        full_args.push("--no-daemon".to_string());
+    }
+    if json {
+        full_args.push("--json".to_string());
+    }
+    full_args
+}
+
+/// CLI subcommands that never modify issue data.
+fn is_read_only_command(command: &str) -> bool {
+    let sub = command.split_whitespace().next().unwrap_or("");
+    matches!(sub, "list" | "show" | "ready" | "search" | "count" | "status" | "stats" | "export")
+        || command == "comments"
+}
+
+/// Typed command error, serialized as `{ kind, message }` so the frontend can branch
+/// on `kind` instead of matching message text. `message` is the same text the
+/// `String` errors carry (prefixes like `HAS_OPEN_CHILDREN:` included).
+#[derive(Debug, Clone, PartialEq)]
+pub enum BackendError {
+    NotFound(String),
+    SchemaMigration(String),
+    InvalidInput(String),
+    Io(String),
+    Bd(String),
+}
+
+impl BackendError {
+    fn kind(&self) -> &'static str {
+        match self {
+            BackendError::NotFound(_) => "notFound",
+            BackendError::SchemaMigration(_) => "schemaMigration",
+            BackendError::InvalidInput(_) => "invalidInput",
+            BackendError::Io(_) => "io",
+            BackendError::Bd(_) => "bd",
+        }
+    }
 
-    // Split command by spaces to handle subcommands like "comments add"
-    let mut full_args: Vec<&str> = command.split_whitespace().collect();
-    for arg in args {
-        full_args.push(arg);
+    fn message(&self) -> &str {
+        match self {
+            BackendError::NotFound(m)
+            | BackendError::SchemaMigration(m)
+            | BackendError::InvalidInput(m)
+            | BackendError::Io(m)
+            | BackendError::Bd(m) => m,
+        }
     }
-    if supports_daemon_flag() {
-        full_args.push("--no-daemon");
+}
+
+impl std::fmt::Display for BackendError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.message())
     }
-    full_args.push("--json");
+}
+
+impl Serialize for BackendError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("BackendError", 2)?;
+        state.serialize_field("kind", self.kind())?;
+        state.serialize_field("message", self.message())?;
+        state.end()
+    }
+}
+
+/// `String` errors from the CLI layer. Only the markers this file itself emits are
+/// mapped to a kind; anything else is the CLI's own text and stays `Bd`. Failures
+/// whose kind is known (missing issue, bad input) are built typed at the call site.
+impl From<String> for BackendError {
+    fn from(message: String) -> Self {
+        if message.starts_with("SCHEMA_MIGRATION_ERROR") {
+            BackendError::SchemaMigration(message)
+        } else if message.starts_with(SPAWN_FAILURE_PREFIX) {
+            BackendError::Io(message)
+        } else {
+            BackendError::Bd(message)
+        }
+    }
+}
+
+/// Start of the error when the CLI process couldn't be started at all
+const SPAWN_FAILURE_PREFIX: &str = "Failed to execute ";
+
+fn execute_bd(command: &str, args: &[String], cwd: Option<&str>) -> Result<String, String> {
+    execute_bd_raw(command, args, cwd, true)
+}
+
+/// Subcommands that legitimately run long (network, full rewrites)
+const SLOW_COMMANDS: &[&str] = &["sync", "import", "migrate", "doctor", "admin"];
+const SLOW_COMMAND_TIMEOUT_FACTOR: u32 = 4;
+
+/// Timeout for a CLI subcommand: the configured one, longer for slow commands.
+/// None when timeouts are disabled.
+fn command_timeout(command: &str) -> Option<Duration> {
+    let secs = COMMAND_TIMEOUT_SECS.load(Ordering::Relaxed);
+    if secs == 0 {
+        return None;
+    }
+    let base = Duration::from_secs(secs);
+    let sub = command.split_whitespace().next().unwrap_or("");
+    Some(if SLOW_COMMANDS.contains(&sub) { base * SLOW_COMMAND_TIMEOUT_FACTOR } else { base })
+}
+
+/// `cmd.output()` with a deadline: the child is killed and an error returned when it
+/// runs past `timeout`. Pipes are drained on threads so a chatty child can't block.
+fn output_with_timeout(cmd: &mut Command, timeout: Option<Duration>) -> std::io::Result<std::process::Output> {
+    use std::io::Read;
+    let timeout = match timeout {
+        Some(timeout) => timeout,
+        None => return cmd.output(),
+    };
+
+    let mut child = cmd
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()?;
+    let drain = |pipe: Option<Box<dyn Read + Send>>| {
+        std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut buf);
+            }
+            buf
+        })
+    };
+    let stdout = drain(child.stdout.take().map(|p| Box::new(p) as Box<dyn Read + Send>));
+    let stderr = drain(child.stderr.take().map(|p| Box::new(p) as Box<dyn Read + Send>));
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            // Reader threads are left to finish on their own: a grandchild may still hold the pipes
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                format!("bd command timed out after {}s", timeout.as_secs()),
+            ));
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    };
+
+    Ok(std::process::Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+/// Backoff before each retry of a read that hit an embedded-Dolt crash.
+const DOLT_CRASH_RETRY_DELAYS: &[Duration] = &[Duration::from_millis(100), Duration::from_millis(300)];
+
+/// bd 0.55 embedded Dolt can still crash under concurrent access despite the project lock.
+fn is_dolt_crash(stderr: &str) -> bool {
+    stderr.contains("SIGSEGV") || stderr.contains("signal: segmentation") || stderr.contains("nil pointer")
+}
+
+/// Subcommands safe to run again after a crash — never anything that writes.
+fn is_crash_retryable(command: &str) -> bool {
+    let sub = command.split_whitespace().next().unwrap_or("");
+    matches!(sub, "list" | "show" | "ready" | "status")
+}
+
+/// `output_with_timeout`, re-running idempotent reads that failed with a Dolt crash.
+/// The last attempt's output is returned, successful or not.
+fn output_with_crash_retry(
+    command: &str,
+    cmd: &mut Command,
+    timeout: Option<Duration>,
+) -> std::io::Result<std::process::Output> {
+    let mut output = output_with_timeout(cmd, timeout)?;
+    if !is_crash_retryable(command) {
+        return Ok(output);
+    }
+    for (attempt, delay) in DOLT_CRASH_RETRY_DELAYS.iter().enumerate() {
+        if output.status.success() || !is_dolt_crash(&String::from_utf8_lossy(&output.stderr)) {
+            break;
+        }
+        log_warn!(
+            "[bd] '{}' crashed (Dolt), retry {}/{} in {}ms",
+            command,
+            attempt + 1,
+            DOLT_CRASH_RETRY_DELAYS.len(),
+            delay.as_millis()
+        );
+        std::thread::sleep(*delay);
+        output = output_with_timeout(cmd, timeout)?;
+    }
+    Ok(output)
+}
+
+fn project_lock(working_dir: &str) -> std::sync::Arc<Mutex<()>> {
+    let mut locks = BD_PROJECT_LOCKS.lock().unwrap();
+    locks.entry(working_dir.to_string())
+        .or_insert_with(|| std::sync::Arc::new(Mutex::new(())))
+        .clone()
+}
+
+/// Run `f` holding the project's CLI lock once, so a batch of calls isn't interleaved
+/// with other commands. CLI calls inside `f` (same thread) reuse the held lock.
+fn with_project_lock<T>(cwd: Option<&str>, f: impl FnOnce() -> T) -> T {
+    struct Release;
+    impl Drop for Release {
+        fn drop(&mut self) {
+            HELD_PROJECT_LOCK.with(|h| *h.borrow_mut() = None);
+        }
+    }
+
+    let working_dir = resolve_working_dir(cwd);
+    let lock = project_lock(&working_dir);
+    let _guard = lock.lock().unwrap();
+    HELD_PROJECT_LOCK.with(|h| *h.borrow_mut() = Some(working_dir));
+    let _release = Release;
+    f()
+}
+
+/// Run a CLI command with the shared locking/PATH/logging infrastructure.
+/// `json` controls whether `--json` is appended — some subcommands (admin, export)
+/// reject it, so they pass `false` and handle the plain-text output themselves.
+fn execute_bd_raw(command: &str, args: &[String], cwd: Option<&str>, json: bool) -> Result<String, String> {
+    execute_bd_with_timeout(command, args, cwd, json, command_timeout(command))
+}
+
+/// `execute_bd_raw` with an explicit timeout instead of the per-command default.
+/// The project lock is a scoped guard, so it is released on timeout like on any error.
+fn execute_bd_with_timeout(
+    command: &str,
+    args: &[String],
+    cwd: Option<&str>,
+    json: bool,
+    timeout: Option<Duration>,
+) -> Result<String, String> {
+    let working_dir = resolve_working_dir(cwd);
+
+    let full_args = build_bd_args(command, args, supports_daemon_flag(), json);
 
     let binary = get_cli_binary();
     log_info!("[bd] {} {} | cwd: {}", binary, full_args.join(" "), working_dir);
 
-    // Acquire per-project lock to prevent concurrent Dolt access (causes SIGSEGV).
-    let project_lock = {
-        let mut locks = BD_PROJECT_LOCKS.lock().unwrap();
-        locks.entry(working_dir.clone())
-            .or_insert_with(|| std::sync::Arc::new(Mutex::new(())))
-            .clone()
-    };
-    let _guard = project_lock.lock().unwrap();
+    // Acquire per-project lock to prevent concurrent Dolt access (causes SIGSEGV),
+    // unless a batch on this thread already holds it.
+    let held = HELD_PROJECT_LOCK.with(|h| h.borrow().as_deref() == Some(working_dir.as_str()));
+    let lock = project_lock(&working_dir);
+    let _guard = if held { None } else { Some(lock.lock().unwrap()) };
 
-    let output = new_command(&binary)
-        .args(&full_args)
+    let mut cmd = new_command(&binary);
+    apply_env_passthrough(&mut cmd);
+    cmd.args(&full_args)
         .current_dir(&working_dir)
         .env("PATH", get_extended_path())
-        .env("BEADS_PATH", &working_dir)
-        .output()
-        .map_err(|e| {
-            log_error!("[bd] Failed to execute {}: {}", binary, e);
-            format!("Failed to execute {}: {}", binary, e)
-        })?;
+        .env("BEADS_PATH", &working_dir);
+    let output = output_with_crash_retry(command, &mut cmd, timeout).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::TimedOut {
+            log_error!("[bd] {} {}: {}", binary, full_args.join(" "), e);
+            return e.to_string();
+        }
+        log_error!("[bd] Failed to execute {}: {}", binary, e);
+        format!("{}{}: {}", SPAWN_FAILURE_PREFIX, binary, e)
+    })?;
+
+    // Anything but a read may have changed issues (even on failure) — drop cached details
+    if !is_read_only_command(command) {
+        show_cache_invalidate(&working_dir);
+    }
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -1239,17 +2301,10 @@ fn ensure_refs_migrated_v3(beads_dir: &std::path::Path, working_dir: &str) {
             for entry in dirs {
                 let folder_name = entry.file_name().to_string_lossy().to_string();
                 let short = issue_short_id(&folder_name);
-                if short != folder_name {
-                    let target = attachments_dir.join(short);
-                    if target.exists() {
-                        log_warn!("[sync] Cannot rename '{}' → '{}': target already exists", folder_name, short);
-                        continue;
-                    }
-                    if std::fs::rename(entry.path(), &target).is_ok() {
-                        renamed += 1;
-                    } else {
-                        log_warn!("[sync] Failed to rename '{}' → '{}'", folder_name, short);
-                    }
+                match rename_attachment_folder(&attachments_dir, &folder_name, short) {
+                    Ok(true) => renamed += 1,
+                    Ok(false) => {}
+                    Err(e) => log_warn!("[sync] {}", e),
                 }
             }
         }
@@ -1266,32 +2321,39 @@ fn ensure_refs_migrated_v3(beads_dir: &std::path::Path, working_dir: &str) {
 
 /// Sync the beads database before read operations to ensure data is up-to-date
 /// Uses bidirectional sync to preserve local changes while getting remote updates
-/// Has a cooldown to avoid redundant syncs within the same poll cycle
-fn sync_bd_database(cwd: Option<&str>) {
-    let working_dir = cwd
-        .map(String::from)
-        .or_else(|| env::var("BEADS_PATH").ok())
-        .unwrap_or_else(|| {
-            env::current_dir()
-                .map(|p| p.to_string_lossy().to_string())
-                .unwrap_or_else(|_| ".".to_string())
-        });
+/// Has a cooldown to avoid redundant syncs within the same poll cycle.
+/// Returns false when the sync was skipped (safe mode, Dolt, cooldown).
+fn sync_bd_database(cwd: Option<&str>) -> bool {
+    sync_bd_database_in_mode(cwd, SAFE_MODE.load(Ordering::Relaxed))
+}
+
+fn sync_bd_database_in_mode(cwd: Option<&str>, safe_mode: bool) -> bool {
+    if safe_mode {
+        log_info!("[sync] Skipping — safe mode");
+        return false;
+    }
+    let working_dir = resolve_working_dir(cwd);
+    {
+        let mut seen = SESSION_PROJECTS.lock().unwrap();
+        if !seen.contains(&working_dir) {
+            seen.push(working_dir.clone());
+        }
+    }
 
     // Dolt backend handles its own sync via git — skip bd sync
     let beads_dir = std::path::Path::new(&working_dir).join(".beads");
     if project_uses_dolt(&beads_dir) {
         log_info!("[sync] Skipping — Dolt backend handles sync via git");
-        return;
+        return false;
     }
 
-    // Check cooldown — skip if synced recently
+    // Check cooldown / auto-sync interval — skip if synced recently
     {
         let last = LAST_SYNC_TIME.lock().unwrap();
-        if let Some(t) = *last {
-            if t.elapsed().as_secs() < SYNC_COOLDOWN_SECS {
-                log_info!("[sync] Skipping — cooldown active ({:.1}s ago)", t.elapsed().as_secs_f32());
-                return;
-            }
+        let elapsed = last.get(&working_dir).map(|t| t.elapsed());
+        if !sync_due(elapsed, AUTO_SYNC_INTERVAL_SECS.load(Ordering::Relaxed)) {
+            log_info!("[sync] Skipping — synced {:.1}s ago", elapsed.unwrap_or_default().as_secs_f32());
+            return false;
         }
     }
 
@@ -1306,18 +2368,18 @@ fn sync_bd_database(cwd: Option<&str>) {
     if supports_daemon_flag() {
         sync_args.push("--no-daemon");
     }
-    match new_command(&binary)
-        .args(&sync_args)
+    let mut cmd = new_command(&binary);
+    cmd.args(&sync_args)
         .current_dir(&working_dir)
         .env("PATH", get_extended_path())
-        .env("BEADS_PATH", &working_dir)
-        .output()
-    {
+        .env("BEADS_PATH", &working_dir);
+    match output_with_timeout(&mut cmd, command_timeout("sync")) {
         Ok(output) if output.status.success() => {
             log_info!("[sync] Sync completed successfully");
             // Update cooldown timestamp
-            let mut last = LAST_SYNC_TIME.lock().unwrap();
-            *last = Some(Instant::now());
+            LAST_SYNC_TIME.lock().unwrap().insert(working_dir.clone(), Instant::now());
+            // Remote changes may have been imported
+            show_cache_invalidate(&working_dir);
         }
         Ok(output) => {
             log_warn!(
@@ -1330,14 +2392,344 @@ fn sync_bd_database(cwd: Option<&str>) {
             log_error!("[sync] Failed to run {} sync: {}", binary, e);
         }
     }
+
+    if let Some(e) = cached_merge_conflict(&working_dir, &beads_dir) {
+        log_error!("[sync] {}", e);
+    }
+    true
+}
+
+#[tauri::command]
+async fn set_safe_mode(enabled: bool) {
+    SAFE_MODE.store(enabled, Ordering::Relaxed);
+    log::info!("[safe_mode] {}", if enabled { "enabled" } else { "disabled" });
+}
+
+#[tauri::command]
+async fn get_safe_mode() -> bool {
+    SAFE_MODE.load(Ordering::Relaxed)
+}
+
+/// In safe mode, refuse `action` unless the caller passed `confirm: true`.
+fn require_safe_mode_confirmation(action: &str, confirm: Option<bool>) -> Result<(), String> {
+    check_safe_mode_confirmation(SAFE_MODE.load(Ordering::Relaxed), action, confirm)
+}
+
+fn check_safe_mode_confirmation(safe_mode: bool, action: &str, confirm: Option<bool>) -> Result<(), String> {
+    if safe_mode && !confirm.unwrap_or(false) {
+        return Err(format!("SAFE_MODE: {} is disabled in safe mode unless explicitly confirmed", action));
+    }
+    Ok(())
+}
+
+/// Fail with `MERGE_CONFLICT:<ids>: ...` when a git merge left conflict markers in
+/// issues.jsonl — otherwise the file silently fails to parse and the list comes back empty.
+fn check_jsonl_merge_conflict(beads_dir: &std::path::Path) -> Result<(), String> {
+    let content = match fs::read_to_string(beads_dir.join("issues.jsonl")) {
+        Ok(content) => content,
+        Err(_) => return Ok(()),
+    };
+    match jsonl_conflict_ids(&content) {
+        Some(ids) => Err(format!(
+            "MERGE_CONFLICT:{}: issues.jsonl has unresolved git merge conflicts — resolve them, then sync again",
+            ids.join(",")
+        )),
+        None => Ok(()),
+    }
+}
+
+/// issues.jsonl mtime at a conflict check, and the MERGE_CONFLICT error it found.
+type MergeConflictCheck = (Option<std::time::SystemTime>, Option<String>);
+
+/// Last conflict check per project.
+static MERGE_CONFLICT_CHECKS: Mutex<std::collections::BTreeMap<String, MergeConflictCheck>> =
+    Mutex::new(std::collections::BTreeMap::new());
+
+/// `check_jsonl_merge_conflict` for a background sync or poll: issues.jsonl is only
+/// re-read when its mtime changed since the last check of `working_dir`.
+fn cached_merge_conflict(working_dir: &str, beads_dir: &std::path::Path) -> Option<String> {
+    let mtime = fs::metadata(beads_dir.join("issues.jsonl")).and_then(|m| m.modified()).ok();
+    let mut checks = MERGE_CONFLICT_CHECKS.lock().unwrap();
+    if let Some((checked_mtime, conflict)) = checks.get(working_dir) {
+        if *checked_mtime == mtime {
+            return conflict.clone();
+        }
+    }
+    let conflict = check_jsonl_merge_conflict(beads_dir).err();
+    checks.insert(working_dir.to_string(), (mtime, conflict.clone()));
+    conflict
+}
+
+/// Ids of the issues inside git conflict hunks, or None when there are no markers.
+/// Lines inside a hunk that aren't parseable JSON with an `id` are skipped.
+fn jsonl_conflict_ids(content: &str) -> Option<Vec<String>> {
+    let mut found_markers = false;
+    let mut in_hunk = false;
+    let mut ids: Vec<String> = Vec::new();
+    for line in content.lines() {
+        if line.starts_with("<<<<<<<") {
+            found_markers = true;
+            in_hunk = true;
+        } else if line.starts_with(">>>>>>>") {
+            in_hunk = false;
+        } else if line.starts_with("=======") || line.starts_with("|||||||") {
+            found_markers = true;
+        } else if in_hunk {
+            let id = serde_json::from_str::<serde_json::Value>(line)
+                .ok()
+                .and_then(|v| v.get("id").and_then(|id| id.as_str()).map(String::from));
+            if let Some(id) = id {
+                if !ids.contains(&id) {
+                    ids.push(id);
+                }
+            }
+        }
+    }
+    found_markers.then_some(ids)
+}
+
+/// Whether a sync should run given the time since the last one.
+/// With auto-sync off (`interval_secs == 0`) reads sync after the short cooldown;
+/// with auto-sync on, only once the data is older than the interval.
+fn sync_due(since_last: Option<Duration>, interval_secs: u64) -> bool {
+    let min_gap = if interval_secs == 0 { SYNC_COOLDOWN_SECS } else { interval_secs };
+    since_last.map_or(true, |elapsed| elapsed.as_secs() >= min_gap)
+}
+
+/// Set the background auto-sync interval in seconds (0 disables it and restores sync-on-read).
+#[tauri::command]
+async fn set_auto_sync_interval(secs: u64) -> Result<(), String> {
+    update_config(|config| config.auto_sync_interval_secs = secs)?;
+    start_auto_sync(secs);
+    Ok(())
+}
+
+/// (Re)start the background auto-sync task; any previous task exits on its next tick.
+fn start_auto_sync(secs: u64) {
+    AUTO_SYNC_INTERVAL_SECS.store(secs, Ordering::Relaxed);
+    let generation = AUTO_SYNC_GENERATION.fetch_add(1, Ordering::Relaxed) + 1;
+    log_info!("[sync] Auto-sync interval: {}", if secs == 0 { "off".to_string() } else { format!("{}s", secs) });
+
+    if secs == 0 {
+        return;
+    }
+    std::thread::spawn(move || loop {
+        std::thread::sleep(Duration::from_secs(secs));
+        if AUTO_SYNC_GENERATION.load(Ordering::Relaxed) != generation {
+            break;
+        }
+        for project in auto_sync_projects() {
+            sync_bd_database(Some(&project));
+        }
+    });
+}
+
+#[tauri::command]
+async fn get_auto_sync_interval() -> u64 {
+    AUTO_SYNC_INTERVAL_SECS.load(Ordering::Relaxed)
+}
+
+/// Projects the auto-sync task covers: those read this session, then favorites not yet opened.
+fn auto_sync_projects() -> Vec<String> {
+    let mut projects = SESSION_PROJECTS.lock().unwrap().clone();
+    for favorite in FAVORITE_PROJECTS.lock().unwrap().iter() {
+        if !projects.contains(favorite) {
+            projects.push(favorite.clone());
+        }
+    }
+    projects
+}
+
+/// Replace the favorite projects auto-sync covers in addition to the ones opened this session.
+#[tauri::command]
+async fn set_favorite_projects(paths: Vec<String>) {
+    *FAVORITE_PROJECTS.lock().unwrap() = paths;
+}
+
+/// Configure periodic backups; `dir: None` or `interval_secs == 0` turns them off.
+#[tauri::command]
+async fn set_auto_backup(dir: Option<String>, interval_secs: u64, keep: usize) -> Result<(), String> {
+    let backup = match dir.map(|d| d.trim().to_string()).filter(|d| !d.is_empty()) {
+        Some(dir) if interval_secs > 0 => {
+            fs::create_dir_all(&dir).map_err(|e| format!("Failed to create backup directory: {}", e))?;
+            Some(AutoBackupConfig { dir, interval_secs, keep: keep.max(1) })
+        }
+        _ => None,
+    };
+    update_config(|config| config.auto_backup = backup.clone())?;
+    start_auto_backup(backup);
+    Ok(())
+}
+
+/// Back up a project right away into the configured backup directory.
+#[tauri::command]
+async fn run_backup_now(cwd: Option<String>) -> Result<ExportResult, String> {
+    let backup = AUTO_BACKUP.lock().unwrap().clone()
+        .ok_or_else(|| "Auto-backup is not configured".to_string())?;
+    run_backup(&resolve_working_dir(cwd.as_deref()), &backup)
+}
+
+/// (Re)start the background backup task; any previous task exits on its next tick.
+fn start_auto_backup(backup: Option<AutoBackupConfig>) {
+    *AUTO_BACKUP.lock().unwrap() = backup.clone();
+    let generation = AUTO_BACKUP_GENERATION.fetch_add(1, Ordering::Relaxed) + 1;
+    let backup = match backup {
+        Some(backup) => backup,
+        None => {
+            log_info!("[backup] Auto-backup: off");
+            return;
+        }
+    };
+    log_info!("[backup] Auto-backup every {}s to {} (keep {})", backup.interval_secs, backup.dir, backup.keep);
+
+    std::thread::spawn(move || loop {
+        std::thread::sleep(Duration::from_secs(backup.interval_secs));
+        if AUTO_BACKUP_GENERATION.load(Ordering::Relaxed) != generation {
+            break;
+        }
+        let projects = SESSION_PROJECTS.lock().unwrap().clone();
+        for project in projects {
+            if let Err(e) = run_backup(&project, &backup) {
+                log_error!("[backup] {}: {}", project, e);
+            }
+        }
+    });
+}
+
+/// Write `<project>-<hash>-<YYYYMMDD-HHMMSS>.jsonl` into the backup dir, then prune old ones.
+fn run_backup(working_dir: &str, backup: &AutoBackupConfig) -> Result<ExportResult, String> {
+    let prefix = backup_prefix(working_dir);
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64;
+    let stamp: String = format_timestamp_utc(now)
+        .chars()
+        .filter_map(|c| match c {
+            'T' => Some('-'),
+            c if c.is_ascii_digit() => Some(c),
+            _ => None,
+        })
+        .collect();
+    let path = std::path::Path::new(&backup.dir).join(format!("{}{}.jsonl", prefix, stamp));
+
+    let output = execute_bd_raw("export", &[], Some(working_dir), false)?;
+    let line_count = write_jsonl_export(&path, &output)?;
+    log_info!("[backup] Wrote {} ({} issues)", path.display(), line_count);
+
+    for removed in prune_backups(std::path::Path::new(&backup.dir), &prefix, backup.keep) {
+        log_info!("[backup] Pruned {}", removed.display());
+    }
+    Ok(ExportResult { path: path.to_string_lossy().to_string(), line_count, used_list_fallback: false })
+}
+
+/// Backup file prefix for a project: its directory name and a short hash of its
+/// canonical path, so `~/a/app` and `~/b/app` never prune each other's backups.
+fn backup_prefix(working_dir: &str) -> String {
+    let path = std::path::Path::new(working_dir);
+    let name = path
+        .file_name()
+        .map(|n| sanitize_filename(&n.to_string_lossy()))
+        .unwrap_or_else(|| "project".to_string());
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    format!("{}-{:08x}-", name, fnv1a_32(canonical.to_string_lossy().as_bytes()))
+}
+
+/// FNV-1a: a tiny hash that, unlike `DefaultHasher`, stays the same across Rust
+/// releases, so backup names don't change after an app update.
+fn fnv1a_32(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0x811c_9dc5, |hash: u32, b| (hash ^ *b as u32).wrapping_mul(0x0100_0193))
+}
+
+/// Whether `name` is exactly `<prefix><YYYYMMDD-HHMMSS>.jsonl`, so project `app`
+/// never claims the backups of `app-v2`.
+fn is_backup_of(name: &str, prefix: &str) -> bool {
+    let Some(stamp) = name.strip_prefix(prefix).and_then(|rest| rest.strip_suffix(".jsonl")) else {
+        return false;
+    };
+    let bytes = stamp.as_bytes();
+    bytes.len() == 15
+        && bytes[8] == b'-'
+        && bytes.iter().enumerate().all(|(i, b)| i == 8 || b.is_ascii_digit())
+}
+
+/// Delete all but the newest `keep` backups with `prefix` (timestamps sort by name).
+fn prune_backups(dir: &std::path::Path, prefix: &str, keep: usize) -> Vec<PathBuf> {
+    let mut backups: Vec<PathBuf> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| {
+                let name = p.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                is_backup_of(&name, prefix)
+            })
+            .collect(),
+        Err(_) => return Vec::new(),
+    };
+    backups.sort();
+    let excess = backups.len().saturating_sub(keep);
+    backups.into_iter()
+        .take(excess)
+        .filter(|p| fs::remove_file(p).is_ok())
+        .collect()
 }
 
 // ============================================================================
 // Tauri Commands
 // ============================================================================
 
+/// What a sync changed locally, by issue id (each list sorted).
+#[derive(Debug, Default, Serialize, PartialEq)]
+pub struct SyncSummary {
+    pub created: Vec<String>,
+    pub updated: Vec<String>,
+    pub closed: Vec<String>,
+    pub deleted: Vec<String>,
+}
+
+/// id → (status, updated_at) for the live issues of a JSONL export; tombstones are left out.
+fn jsonl_snapshot(content: &str) -> HashMap<String, (String, String)> {
+    content.lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line.trim()).ok())
+        .filter_map(|v| {
+            let field = |key: &str| v.get(key).and_then(|f| f.as_str()).unwrap_or_default().to_string();
+            let id = field("id");
+            let status = field("status");
+            if id.is_empty() || status == "tombstone" {
+                return None;
+            }
+            Some((id, (status, field("updated_at"))))
+        })
+        .collect()
+}
+
+/// Compare the JSONL before and after a sync. An issue that became closed counts
+/// as closed rather than updated.
+fn diff_jsonl_snapshots(before: &str, after: &str) -> SyncSummary {
+    let before = jsonl_snapshot(before);
+    let after = jsonl_snapshot(after);
+    let mut summary = SyncSummary::default();
+    for (id, (status, updated_at)) in &after {
+        match before.get(id) {
+            None => summary.created.push(id.clone()),
+            Some((old_status, _)) if status == "closed" && old_status != "closed" => summary.closed.push(id.clone()),
+            Some((old_status, old_updated)) if old_status != status || old_updated != updated_at => {
+                summary.updated.push(id.clone())
+            }
+            Some(_) => {}
+        }
+    }
+    summary.deleted = before.keys().filter(|id| !after.contains_key(*id)).cloned().collect();
+    for ids in [&mut summary.created, &mut summary.updated, &mut summary.closed, &mut summary.deleted] {
+        ids.sort();
+    }
+    summary
+}
+
+/// Sync a JSONL project and report which issues changed. Dolt projects sync through
+/// git on their own, so they are skipped with an empty summary.
 #[tauri::command]
-async fn bd_sync(cwd: Option<String>) -> Result<(), String> {
+async fn bd_sync(cwd: Option<String>) -> Result<SyncSummary, String> {
     let working_dir = cwd
         .or_else(|| env::var("BEADS_PATH").ok())
         .unwrap_or_else(|| {
@@ -1350,24 +2742,40 @@ async fn bd_sync(cwd: Option<String>) -> Result<(), String> {
     let beads_dir = std::path::Path::new(&working_dir).join(".beads");
     if project_uses_dolt(&beads_dir) {
         log_info!("[bd_sync] Skipping — Dolt backend handles sync via git");
-        return Ok(());
+        return Ok(SyncSummary::default());
     }
 
     let binary = get_cli_binary();
     log_info!("[bd_sync] Manual sync requested for: {}", working_dir);
+    let jsonl_path = beads_dir.join("issues.jsonl");
+    // The baseline is the database, not the JSONL: sync starts by exporting local
+    // changes into the JSONL, which would then show up as changed by the sync
+    let before = match execute_bd_raw("export", &[], Some(&working_dir), false) {
+        Ok(export) => export,
+        Err(e) => {
+            log_warn!("[bd_sync] Export before sync failed, diffing against issues.jsonl: {}", e);
+            fs::read_to_string(&jsonl_path).unwrap_or_default()
+        }
+    };
 
     let mut sync_args = vec!["sync"];
     if supports_daemon_flag() {
         sync_args.push("--no-daemon");
     }
-    let output = new_command(&binary)
-        .args(&sync_args)
+    let mut cmd = new_command(&binary);
+    cmd.args(&sync_args)
         .current_dir(&working_dir)
         .env("PATH", get_extended_path())
-        .env("BEADS_PATH", &working_dir)
-        .output()
+        .env("BEADS_PATH", &working_dir);
+    let output = output_with_timeout(&mut cmd, command_timeout("sync"))
         .map_err(|e| format!("Failed to run {} sync: {}", binary, e))?;
 
+    // A conflicted JSONL matters more than bd's stderr — report it either way
+    if let Err(e) = check_jsonl_merge_conflict(&beads_dir) {
+        log_error!("[bd_sync] {}", e);
+        return Err(e);
+    }
+
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         log_error!("[bd_sync] Sync failed: {}", stderr.trim());
@@ -1376,9 +2784,80 @@ async fn bd_sync(cwd: Option<String>) -> Result<(), String> {
 
     log_info!("[bd_sync] Sync completed successfully");
     // Reset cooldown so subsequent reads pick up the fresh sync
-    let mut last = LAST_SYNC_TIME.lock().unwrap();
-    *last = Some(Instant::now());
-    Ok(())
+    LAST_SYNC_TIME.lock().unwrap().insert(working_dir.clone(), Instant::now());
+    show_cache_invalidate(&working_dir);
+
+    let after = fs::read_to_string(&jsonl_path).unwrap_or_default();
+    let summary = diff_jsonl_snapshots(&before, &after);
+    log_info!(
+        "[bd_sync] {} created, {} updated, {} closed, {} deleted",
+        summary.created.len(), summary.updated.len(), summary.closed.len(), summary.deleted.len()
+    );
+    Ok(summary)
+}
+
+#[derive(Debug, serde::Serialize)]
+struct ExportResult {
+    path: String,
+    #[serde(rename = "lineCount")]
+    line_count: usize,
+    /// Set when the CLI has no `export` and the file was rebuilt from `list --all`.
+    #[serde(rename = "usedListFallback")]
+    used_list_fallback: bool,
+}
+
+/// Export the project via the CLI's native `export` (canonical JSONL, all fields)
+/// rather than reconstructing it from `list --json` output. CLIs without an
+/// `export` subcommand fall back to `list --all`, written one `BdRawIssue` per line;
+/// any other export failure is returned as-is.
+#[tauri::command]
+async fn bd_export(cwd: Option<String>, out_path: String) -> Result<ExportResult, String> {
+    log_info!("[bd_export] Exporting to: {}", out_path);
+
+    let parent = std::path::Path::new(&out_path).parent().filter(|p| !p.as_os_str().is_empty());
+    if let Some(parent) = parent {
+        if !parent.is_dir() {
+            return Err(format!("Export directory does not exist: {}", parent.display()));
+        }
+    }
+
+    // export writes JSONL to stdout and rejects --json
+    let (output, used_list_fallback) = match execute_bd_raw("export", &[], cwd.as_deref(), false) {
+        Ok(output) => (output, false),
+        Err(e) if is_unknown_command(&e) => {
+            log_warn!("[bd_export] CLI has no export command, falling back to list --all");
+            (raw_issues_to_jsonl(&fetch_all_raw_issues(cwd.as_deref(), "bd_export")?)?, true)
+        }
+        Err(e) => return Err(e),
+    };
+    let line_count = write_jsonl_export(std::path::Path::new(&out_path), &output)?;
+
+    log_info!("[bd_export] Wrote {} lines to {}", line_count, out_path);
+    Ok(ExportResult { path: out_path, line_count, used_list_fallback })
+}
+
+/// One JSON object per line, in the shape `bd import` reads back.
+fn raw_issues_to_jsonl(issues: &[BdRawIssue]) -> Result<String, String> {
+    let mut out = String::new();
+    for issue in issues {
+        let line = serde_json::to_string(issue)
+            .map_err(|e| format!("Failed to serialize issue {}: {}", issue.id, e))?;
+        out.push_str(&line);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Write JSONL export output to `path`, returning the number of non-empty lines.
+fn write_jsonl_export(path: &std::path::Path, content: &str) -> Result<usize, String> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create export directory: {}", e))?;
+        }
+    }
+    fs::write(path, content).map_err(|e| format!("Failed to write export: {}", e))?;
+    Ok(content.lines().filter(|l| !l.trim().is_empty()).count())
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -1389,7 +2868,8 @@ struct RepairResult {
 }
 
 #[tauri::command]
-async fn bd_repair_database(cwd: Option<String>) -> Result<RepairResult, String> {
+async fn bd_repair_database(cwd: Option<String>, confirm: Option<bool>) -> Result<RepairResult, String> {
+    require_safe_mode_confirmation("Database repair", confirm)?;
     let working_dir = cwd
         .or_else(|| env::var("BEADS_PATH").ok())
         .unwrap_or_else(|| {
@@ -1398,6 +2878,13 @@ async fn bd_repair_database(cwd: Option<String>) -> Result<RepairResult, String>
                 .unwrap_or_else(|_| ".".to_string())
         });
 
+    let result = repair_database(working_dir.clone());
+    // Rebuilt (or partly touched on failure) outside execute_bd: drop cached details
+    show_cache_invalidate(&working_dir);
+    result
+}
+
+fn repair_database(working_dir: String) -> Result<RepairResult, String> {
     log_info!("[bd_repair] Starting database repair for: {}", working_dir);
 
     let beads_dir = std::path::Path::new(&working_dir).join(".beads");
@@ -1517,6 +3004,75 @@ async fn bd_repair_database(cwd: Option<String>) -> Result<RepairResult, String>
 struct MigrateResult {
     success: bool,
     message: String,
+    /// Set when the migration was asked to regenerate issues.jsonl afterwards
+    #[serde(rename = "jsonlExport", skip_serializing_if = "Option::is_none")]
+    jsonl_export: Option<JsonlExportCheck>,
+    /// Set by a dry run: what the init+import fallback would do. Nothing was changed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    preview: Option<MigrationPreview>,
+}
+
+#[derive(Debug, Default, PartialEq, serde::Serialize)]
+struct MigrationPreview {
+    /// Most common id prefix in issues.jsonl (None without JSONL data: init-only migration)
+    prefix: Option<String>,
+    /// Live issues that would be imported
+    #[serde(rename = "issueCount")]
+    issue_count: usize,
+    /// Tombstoned issues that would be skipped
+    tombstones: usize,
+    /// Unparseable JSONL lines that would be skipped
+    malformed: usize,
+    /// Issues whose id would be re-prefixed to `prefix`
+    reprefixed: usize,
+    /// Issues whose multi-line/long external_ref would be cut to its first line
+    #[serde(rename = "refsToTruncate")]
+    refs_to_truncate: usize,
+    /// SQLite databases that would be renamed to `<name>.backup`
+    #[serde(rename = "filesToBackup")]
+    files_to_backup: Vec<String>,
+    /// SQLite side files (-shm, -wal) that would be deleted
+    #[serde(rename = "filesToRemove")]
+    files_to_remove: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, serde::Serialize)]
+struct JsonlExportCheck {
+    /// Issues in the regenerated export
+    exported: usize,
+    /// Live issues in issues.jsonl before the migration (what got imported);
+    /// None when there was no readable issues.jsonl to compare against
+    expected: Option<usize>,
+    matches: bool,
+    /// Whether issues.jsonl was overwritten; a mismatching export leaves it untouched
+    replaced: bool,
+}
+
+/// Non-tombstone issues in JSONL content — the ones a migration imports.
+fn count_jsonl_issues(content: &str) -> usize {
+    content.lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line.trim()).ok())
+        .filter(|v| v.get("status").and_then(|s| s.as_str()) != Some("tombstone"))
+        .count()
+}
+
+fn verify_export_count(expected: Option<usize>, exported: usize) -> JsonlExportCheck {
+    JsonlExportCheck { exported, expected, matches: expected.map_or(true, |e| e == exported), replaced: false }
+}
+
+/// Write `output` next to `jsonl_path` and move it over the original only when its
+/// issue count matches `expected`, so a short export never replaces the source file.
+fn replace_jsonl_if_verified(jsonl_path: &std::path::Path, expected: Option<usize>, output: &str) -> Result<JsonlExportCheck, String> {
+    let tmp_path = jsonl_path.with_extension("jsonl.tmp");
+    write_jsonl_export(&tmp_path, output)?;
+    let mut check = verify_export_count(expected, count_jsonl_issues(output));
+    if check.matches {
+        fs::rename(&tmp_path, jsonl_path).map_err(|e| format!("Failed to write export: {}", e))?;
+        check.replaced = true;
+    } else {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    Ok(check)
 }
 
 /// Remove orphaned Dolt lock files that block database access.
@@ -1570,6 +3126,13 @@ async fn bd_check_needs_migration(cwd: Option<String>) -> Result<MigrationStatus
 
     let beads_dir = std::path::Path::new(&working_dir).join(".beads");
 
+    if SAFE_MODE.load(Ordering::Relaxed) {
+        return Ok(MigrationStatus {
+            needs_migration: false,
+            reason: "Safe mode — migration checks disabled".to_string(),
+        });
+    }
+
     if !beads_dir.exists() {
         return Ok(MigrationStatus {
             needs_migration: false,
@@ -1635,27 +3198,168 @@ async fn bd_check_needs_migration(cwd: Option<String>) -> Result<MigrationStatus
     })
 }
 
-/// Re-prefix an issue ID if it uses a non-target prefix
-fn reprefix_id(id: &str, target_prefix: &str, prefix_counts: &std::collections::HashMap<String, usize>) -> String {
-    if let Some(last_dash) = id.rfind('-') {
-        let current_prefix = &id[..last_dash];
-        if current_prefix != target_prefix && prefix_counts.contains_key(current_prefix) {
-            return format!("{}{}", target_prefix, &id[last_dash..]);
+/// Count id prefixes in a JSONL export and pick the most common one as the target.
+fn detect_jsonl_prefix(jsonl: &str) -> Result<(String, HashMap<String, usize>), String> {
+    let mut prefix_counts: HashMap<String, usize> = HashMap::new();
+    for line in jsonl.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() { continue; }
+        if let Ok(v) = serde_json::from_str::<serde_json::Value>(trimmed) {
+            if let Some(id) = v.get("id").and_then(|i| i.as_str()) {
+                if let Some(last_dash) = id.rfind('-') {
+                    let suffix = &id[last_dash + 1..];
+                    if suffix.chars().all(|c| c.is_alphanumeric()) && !suffix.is_empty() {
+                        *prefix_counts.entry(id[..last_dash].to_string()).or_insert(0) += 1;
+                    }
+                }
+            }
         }
     }
-    id.to_string()
+    let prefix = prefix_counts
+        .iter()
+        .max_by_key(|(_, count)| *count)
+        .map(|(p, _)| p.clone())
+        .ok_or_else(|| "Could not detect issue prefix from issues.jsonl".to_string())?;
+    Ok((prefix, prefix_counts))
 }
 
-#[tauri::command]
-async fn bd_migrate_to_dolt(cwd: Option<String>) -> Result<MigrateResult, String> {
-    let working_dir = cwd
-        .or_else(|| env::var("BEADS_PATH").ok())
-        .unwrap_or_else(|| {
-            env::current_dir()
-                .map(|p| p.to_string_lossy().to_string())
-                .unwrap_or_else(|_| ".".to_string())
+fn is_tombstone_value(v: &serde_json::Value) -> bool {
+    v.get("status").and_then(|s| s.as_str()) == Some("tombstone")
+}
+
+/// Dolt's external_ref column can't hold multi-line values with long attachment paths
+fn external_ref_needs_truncate(v: &serde_json::Value) -> bool {
+    v.get("external_ref")
+        .and_then(|e| e.as_str())
+        .map(|s| s.contains('\n') || s.len() > 100)
+        .unwrap_or(false)
+}
+
+/// What the init+import fallback of `migrate_to_dolt` would do, without touching anything.
+fn preview_migration(beads_dir: &std::path::Path) -> Result<MigrationPreview, String> {
+    let mut preview = MigrationPreview::default();
+    if let Ok(entries) = fs::read_dir(beads_dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.ends_with(".db") {
+                preview.files_to_backup.push(name);
+            } else if name.ends_with(".db-shm") || name.ends_with(".db-wal") || name.ends_with(".db?mode=ro") {
+                preview.files_to_remove.push(name);
+            }
+        }
+    }
+    preview.files_to_backup.sort();
+    preview.files_to_remove.sort();
+
+    let jsonl = fs::read_to_string(beads_dir.join("issues.jsonl")).unwrap_or_default();
+    if jsonl.trim().is_empty() {
+        return Ok(preview);
+    }
+    let (prefix, _) = detect_jsonl_prefix(&jsonl)?;
+    for line in jsonl.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let v = match serde_json::from_str::<serde_json::Value>(line) {
+            Ok(v) => v,
+            Err(_) => {
+                preview.malformed += 1;
+                continue;
+            }
+        };
+        if is_tombstone_value(&v) {
+            preview.tombstones += 1;
+            continue;
+        }
+        preview.issue_count += 1;
+        let id = v.get("id").and_then(|i| i.as_str()).unwrap_or_default();
+        if id.rfind('-').is_some_and(|dash| id[..dash] != prefix) {
+            preview.reprefixed += 1;
+        }
+        if external_ref_needs_truncate(&v) {
+            preview.refs_to_truncate += 1;
+        }
+    }
+    preview.prefix = Some(prefix);
+    Ok(preview)
+}
+
+/// Re-prefix an issue ID if it uses a non-target prefix
+fn reprefix_id(id: &str, target_prefix: &str, prefix_counts: &std::collections::HashMap<String, usize>) -> String {
+    if let Some(last_dash) = id.rfind('-') {
+        let current_prefix = &id[..last_dash];
+        if current_prefix != target_prefix && prefix_counts.contains_key(current_prefix) {
+            return format!("{}{}", target_prefix, &id[last_dash..]);
+        }
+    }
+    id.to_string()
+}
+
+#[tauri::command]
+async fn bd_migrate_to_dolt(
+    cwd: Option<String>,
+    confirm: Option<bool>,
+    export_jsonl: Option<bool>,
+    dry_run: Option<bool>,
+) -> Result<MigrateResult, String> {
+    let working_dir = cwd
+        .or_else(|| env::var("BEADS_PATH").ok())
+        .unwrap_or_else(|| {
+            env::current_dir()
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_else(|_| ".".to_string())
+        });
+
+    // A preview changes nothing, so it needs no safe-mode confirmation
+    if dry_run.unwrap_or(false) {
+        let beads_dir = std::path::Path::new(&working_dir).join(".beads");
+        if !beads_dir.exists() {
+            return Err("No .beads directory found in this project".to_string());
+        }
+        if project_uses_dolt(&beads_dir) {
+            return Ok(MigrateResult {
+                success: true,
+                message: "Project already uses the Dolt backend.".to_string(),
+                jsonl_export: None,
+                preview: None,
+            });
+        }
+        let preview = preview_migration(&beads_dir)?;
+        log_info!("[bd_migrate] Dry run: {:?}", preview);
+        return Ok(MigrateResult {
+            success: true,
+            message: "Dry run: nothing was changed. `bd migrate` is tried first; if it fails, \
+                      the init+import fallback described here runs instead.".to_string(),
+            jsonl_export: None,
+            preview: Some(preview),
         });
+    }
+
+    require_safe_mode_confirmation("Dolt migration", confirm)?;
+
+    // Count before migrating: the fallback path rewrites the Dolt store from this file
+    let jsonl_path = std::path::Path::new(&working_dir).join(".beads").join("issues.jsonl");
+    let expected = fs::read_to_string(&jsonl_path).ok().map(|c| count_jsonl_issues(&c));
+
+    let result = migrate_to_dolt(working_dir.clone());
+    // The store was replaced outside execute_bd: drop cached details
+    show_cache_invalidate(&working_dir);
+    let mut result = result?;
+    if result.success && export_jsonl.unwrap_or(false) {
+        let output = execute_bd_raw("export", &[], Some(&working_dir), false)
+            .map_err(|e| format!("Migration succeeded but JSONL export failed: {}", e))?;
+        let check = replace_jsonl_if_verified(&jsonl_path, expected, &output)?;
+        if check.replaced {
+            log_info!("[bd_migrate] Regenerated issues.jsonl ({} issues)", check.exported);
+        } else {
+            log_warn!(
+                "[bd_migrate] Export has {} issues, expected {:?} — issues.jsonl left unchanged",
+                check.exported, check.expected
+            );
+        }
+        result.jsonl_export = Some(check);
+    }
+    Ok(result)
+}
 
+fn migrate_to_dolt(working_dir: String) -> Result<MigrateResult, String> {
     log_info!("[bd_migrate] Starting Dolt migration for: {}", working_dir);
 
     let beads_dir = std::path::Path::new(&working_dir).join(".beads");
@@ -1670,6 +3374,8 @@ async fn bd_migrate_to_dolt(cwd: Option<String>) -> Result<MigrateResult, String
         return Ok(MigrateResult {
             success: true,
             message: "Project already uses the Dolt backend.".to_string(),
+            jsonl_export: None,
+            preview: None,
         });
     }
 
@@ -1715,6 +3421,8 @@ async fn bd_migrate_to_dolt(cwd: Option<String>) -> Result<MigrateResult, String
         return Ok(MigrateResult {
             success: true,
             message: format!("Migration to Dolt completed successfully. {}", stdout.trim()),
+            jsonl_export: None,
+            preview: None,
         });
     }
 
@@ -1755,6 +3463,8 @@ async fn bd_migrate_to_dolt(cwd: Option<String>) -> Result<MigrateResult, String
             return Ok(MigrateResult {
                 success: true,
                 message: "Migration complete (empty project — initialized with Dolt backend)".to_string(),
+                jsonl_export: None,
+                preview: None,
             });
         }
         let init_stderr = String::from_utf8_lossy(&init_output.stderr);
@@ -1767,26 +3477,7 @@ async fn bd_migrate_to_dolt(cwd: Option<String>) -> Result<MigrateResult, String
     // Detect prefix from JSONL — use the most common prefix
     let jsonl_content = std::fs::read_to_string(&jsonl_path)
         .map_err(|e| format!("Failed to read issues.jsonl: {}", e))?;
-    let mut prefix_counts: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
-    for line in jsonl_content.lines() {
-        let trimmed = line.trim();
-        if trimmed.is_empty() { continue; }
-        if let Ok(v) = serde_json::from_str::<serde_json::Value>(trimmed) {
-            if let Some(id) = v.get("id").and_then(|i| i.as_str()) {
-                if let Some(last_dash) = id.rfind('-') {
-                    let suffix = &id[last_dash + 1..];
-                    if suffix.chars().all(|c| c.is_alphanumeric()) && !suffix.is_empty() {
-                        *prefix_counts.entry(id[..last_dash].to_string()).or_insert(0) += 1;
-                    }
-                }
-            }
-        }
-    }
-    let prefix = prefix_counts
-        .iter()
-        .max_by_key(|(_, count)| *count)
-        .map(|(p, _)| p.clone())
-        .ok_or_else(|| "Could not detect issue prefix from issues.jsonl".to_string())?;
+    let (prefix, prefix_counts) = detect_jsonl_prefix(&jsonl_content)?;
 
     if prefix_counts.len() > 1 {
         log_info!(
@@ -1875,7 +3566,7 @@ async fn bd_migrate_to_dolt(cwd: Option<String>) -> Result<MigrateResult, String
             }
             match serde_json::from_str::<serde_json::Value>(trimmed) {
                 Ok(mut v) => {
-                    if v.get("status").and_then(|s| s.as_str()) == Some("tombstone") {
+                    if is_tombstone_value(&v) {
                         skipped += 1;
                         continue;
                     }
@@ -1911,11 +3602,7 @@ async fn bd_migrate_to_dolt(cwd: Option<String>) -> Result<MigrateResult, String
                     // Truncate external_ref if it contains multiple lines (attachment paths)
                     // Dolt's external_ref column can't hold multi-line values with long paths
                     // Keep only the first line (the meaningful ref: redmine ID, URL, etc.)
-                    let needs_truncate = v.get("external_ref")
-                        .and_then(|e| e.as_str())
-                        .map(|s| s.contains('\n') || s.len() > 100)
-                        .unwrap_or(false);
-                    if needs_truncate {
+                    if external_ref_needs_truncate(&v) {
                         let ext_ref = v["external_ref"].as_str().unwrap();
                         let first_line = ext_ref.lines().next().unwrap_or("").to_string();
                         let issue_id = v.get("id").and_then(|i| i.as_str()).unwrap_or("?").to_string();
@@ -1949,6 +3636,8 @@ async fn bd_migrate_to_dolt(cwd: Option<String>) -> Result<MigrateResult, String
             return Ok(MigrateResult {
                 success: true,
                 message: "Migration complete (empty project — initialized with Dolt backend)".to_string(),
+                jsonl_export: None,
+                preview: None,
             });
         }
 
@@ -2199,6 +3888,8 @@ async fn bd_migrate_to_dolt(cwd: Option<String>) -> Result<MigrateResult, String
             deps_restored,
             comments_restored,
         ),
+        jsonl_export: None,
+        preview: None,
     })
 }
 
@@ -2215,44 +3906,183 @@ pub struct PollData {
     pub closed_issues: Vec<Issue>,
     #[serde(rename = "readyIssues")]
     pub ready_issues: Vec<Issue>,
+    /// Buckets that failed to load ("ready: <error>"); their lists are left empty
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+    /// Latest `updated_at` seen; pass back as `sinceTimestamp` for the next delta poll
+    #[serde(rename = "highWatermark", skip_serializing_if = "Option::is_none")]
+    pub high_watermark: Option<String>,
+    /// Delta polls only: open/closed hold just the changed issues, and these ids
+    /// were deleted (tombstoned) since the timestamp
+    #[serde(rename = "deletedIds", skip_serializing_if = "Option::is_none")]
+    pub deleted_ids: Option<Vec<String>>,
+    /// Delta polls only: every issue id that still exists (open and closed). Issues the
+    /// client holds outside this set were hard-deleted, which leaves no tombstone.
+    /// Left out when a bucket failed, since the set would be incomplete.
+    #[serde(rename = "liveIds", skip_serializing_if = "Option::is_none")]
+    pub live_ids: Option<Vec<String>>,
+    /// Issues the CLI returned but that couldn't be parsed (and so aren't in any list)
+    #[serde(rename = "parseWarnings", skip_serializing_if = "Vec::is_empty")]
+    pub parse_warnings: Vec<SkippedIssue>,
+    /// `MERGE_CONFLICT:<ids>: ...` while issues.jsonl has unresolved git conflict markers
+    #[serde(rename = "mergeConflict", skip_serializing_if = "Option::is_none")]
+    pub merge_conflict: Option<String>,
+}
+
+/// `bd_poll_data` response: the plain struct, or gzip+base64 JSON of it for big projects.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum PollPayload {
+    Compressed { compressed: bool, data: String },
+    Raw(PollData),
+}
+
+/// Serialized poll data below this size goes over IPC as-is — gzip isn't worth it.
+const POLL_COMPRESS_THRESHOLD_BYTES: usize = 256 * 1024;
+
+fn gzip_bytes(data: &[u8]) -> Result<Vec<u8>, String> {
+    use std::io::Write;
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+    encoder.write_all(data).map_err(|e| format!("Failed to compress poll data: {}", e))?;
+    encoder.finish().map_err(|e| format!("Failed to compress poll data: {}", e))
+}
+
+/// Compress `poll_data` when its JSON is at least `threshold` bytes, else return it raw.
+fn compress_poll_data(poll_data: PollData, threshold: usize) -> Result<PollPayload, String> {
+    let json = serde_json::to_vec(&poll_data)
+        .map_err(|e| format!("Failed to serialize poll data: {}", e))?;
+    if json.len() < threshold {
+        return Ok(PollPayload::Raw(poll_data));
+    }
+    let gz = gzip_bytes(&json)?;
+    log_info!("[bd_poll_data] Compressed payload {} -> {} bytes", json.len(), gz.len());
+    Ok(PollPayload::Compressed { compressed: true, data: base64_encode(&gz) })
+}
+
+/// Which `list` calls a poll cycle needs.
+#[derive(Debug, PartialEq)]
+enum PollListPlan {
+    /// First paint: open issues only, closed fetched later via `bd_closed`
+    OpenOnly,
+    /// bd >= 0.55: one `list --all`, partitioned locally
+    AllInOne,
+    /// Older CLIs: separate open and closed calls
+    OpenThenClosed,
+}
+
+fn poll_list_plan(include_closed: bool, supports_all: bool) -> PollListPlan {
+    match (include_closed, supports_all) {
+        (false, _) => PollListPlan::OpenOnly,
+        (true, true) => PollListPlan::AllInOne,
+        (true, false) => PollListPlan::OpenThenClosed,
+    }
 }
 
 /// Batched poll: sync once, then fetch all issues + ready in 2 commands (was 3).
 /// Replaces 3 separate IPC calls (bd_list + bd_list(closed) + bd_ready) with one.
+/// With `include_closed = false` (first paint) closed issues are skipped and come
+/// back empty; fetch them afterwards with `bd_closed`.
+/// With `compress = true` payloads over the threshold come back gzip+base64 encoded.
 #[tauri::command]
-async fn bd_poll_data(cwd: Option<String>) -> Result<PollData, String> {
+async fn bd_poll_data(
+    cwd: Option<String>,
+    include_closed: Option<bool>,
+    compress: Option<bool>,
+    since_timestamp: Option<String>,
+) -> Result<PollPayload, String> {
     log_info!("[bd_poll_data] Batched poll starting");
 
+    let since = match since_timestamp.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+        Some(value) => Some(
+            parse_timestamp_utc(value).ok_or_else(|| format!("Invalid sinceTimestamp: {}", value))?,
+        ),
+        None => None,
+    };
+    // Without the closed bucket an issue closed since the timestamp would be in
+    // neither list, and the client would keep its stale open copy
+    if since.is_some() && !include_closed.unwrap_or(true) {
+        return Err("sinceTimestamp requires includeClosed".to_string());
+    }
     let cwd_ref = cwd.as_deref();
 
     // Single sync for the entire poll cycle
     sync_bd_database(cwd_ref);
 
-    // Fetch issues: single --all call for bd >= 0.55, fallback to 2 calls for older versions
-    let (raw_open, raw_closed) = if supports_list_all_flag() {
-        let all_output = execute_bd("list", &["--all".to_string(), "--limit=0".to_string()], cwd_ref)?;
-        let raw_all = parse_issues_tolerant(&all_output, "bd_poll_data_all")?;
-        let (open, closed): (Vec<_>, Vec<_>) = raw_all.into_iter()
-            .partition(|issue: &BdRawIssue| issue.status != "closed");
-        (open, closed)
-    } else {
-        let open_output = execute_bd("list", &["--limit=0".to_string()], cwd_ref)?;
-        let closed_output = execute_bd("list", &["--status=closed".to_string(), "--limit=0".to_string()], cwd_ref)?;
-        (
-            parse_issues_tolerant(&open_output, "bd_poll_data_open")?,
-            parse_issues_tolerant(&closed_output, "bd_poll_data_closed")?,
-        )
+    let skipped: std::cell::RefCell<Vec<SkippedIssue>> = std::cell::RefCell::new(Vec::new());
+    let parse = |output: String, context: &str| {
+        parse_issues_report(&output, context).map(|parsed| {
+            skipped.borrow_mut().extend(parsed.skipped);
+            parsed.issues
+        })
+    };
+    let open_only = || {
+        execute_bd("list", &["--limit=0".to_string()], cwd_ref)
+            .and_then(|output| parse(output, "bd_poll_data_open"))
+    };
+
+    // Fetch issues: single --all call for bd >= 0.55, fallback to 2 calls for older versions.
+    // Each bucket may fail independently; the poll returns whatever succeeded.
+    let plan = poll_list_plan(include_closed.unwrap_or(true), supports_list_all_flag());
+    let (open_result, closed_result) = match plan {
+        PollListPlan::OpenOnly => (open_only(), Ok(Vec::new())),
+        PollListPlan::AllInOne => {
+            let all_result = execute_bd("list", &["--all".to_string(), "--limit=0".to_string()], cwd_ref)
+                .and_then(|output| parse(output, "bd_poll_data_all"));
+            match all_result {
+                Ok(raw_all) => {
+                    let (open, closed): (Vec<_>, Vec<_>) = raw_all.into_iter()
+                        .partition(|issue: &BdRawIssue| issue.status != "closed");
+                    (Ok(open), Ok(closed))
+                }
+                Err(e) => (Err(e.clone()), Err(e)),
+            }
+        }
+        PollListPlan::OpenThenClosed => (
+            open_only(),
+            execute_bd("list", &["--status=closed".to_string(), "--limit=0".to_string()], cwd_ref)
+                .and_then(|output| parse(output, "bd_poll_data_closed")),
+        ),
     };
 
     // Fetch ready issues
-    let ready_output = execute_bd("ready", &[], cwd_ref)?;
-    let raw_ready = parse_issues_tolerant(&ready_output, "bd_poll_data_ready")?;
+    let ready_result = execute_bd("ready", &[], cwd_ref)
+        .and_then(|output| parse(output, "bd_poll_data_ready"));
+
+    let watermark = latest_timestamp(
+        [&open_result, &closed_result, &ready_result].into_iter()
+            .flat_map(|r| r.iter().flatten())
+            .map(|issue| issue.updated_at.as_str()),
+    );
+    let (open_result, closed_result, deleted_ids, live_ids) = match since {
+        Some(since) => {
+            let live = match (&open_result, &closed_result) {
+                (Ok(open), Ok(closed)) => Some(live_issue_ids(open.iter().chain(closed))),
+                _ => None,
+            };
+            let mut deleted = Vec::new();
+            let open = open_result.map(|raw| changed_since(raw, since, &mut deleted));
+            let closed = closed_result.map(|raw| changed_since(raw, since, &mut deleted));
+            (open, closed, Some(deleted), live)
+        }
+        None => (open_result, closed_result, None, None),
+    };
 
-    log_info!("[bd_poll_data] Batched poll done: {} open, {} closed, {} ready",
-        raw_open.len(), raw_closed.len(), raw_ready.len());
+    let mut poll_data = assemble_poll_data(open_result, closed_result, ready_result)?;
+    poll_data.high_watermark = poll_watermark(watermark, since_timestamp, !poll_data.errors.is_empty());
+    poll_data.deleted_ids = deleted_ids;
+    poll_data.live_ids = live_ids;
+    poll_data.parse_warnings = dedupe_skipped(skipped.into_inner());
+    let working_dir = resolve_working_dir(cwd_ref);
+    poll_data.merge_conflict = cached_merge_conflict(&working_dir, &std::path::Path::new(&working_dir).join(".beads"));
+
+    log_info!("[bd_poll_data] Batched poll done: {} open, {} closed, {} ready, {} failed buckets",
+        poll_data.open_issues.len(), poll_data.closed_issues.len(), poll_data.ready_issues.len(),
+        poll_data.errors.len());
 
     // Update mtime AFTER our commands ran, so the next bd_check_changed
-    // only detects EXTERNAL changes (not our own poll's side effects)
+    // only detects EXTERNAL changes (not our own poll's side effects).
+    // After a failed bucket the entry is dropped instead, so the next check
+    // reports a change and the failed bucket is polled again.
     {
         let working_dir = cwd_ref
             .map(String::from)
@@ -2264,16 +4094,114 @@ async fn bd_poll_data(cwd: Option<String>) -> Result<PollData, String> {
         });
         let beads_dir = std::path::Path::new(&working_dir).join(".beads");
 
-        if let Some(mtime) = get_beads_mtime(&beads_dir) {
-            let mut map = LAST_KNOWN_MTIME.lock().unwrap();
+        let mut map = LAST_KNOWN_MTIME.lock().unwrap();
+        if !poll_data.errors.is_empty() {
+            map.remove(&working_dir);
+        } else if let Some(mtime) = get_beads_mtime(&beads_dir) {
             map.insert(working_dir, mtime);
         }
     }
 
+    if compress.unwrap_or(false) {
+        compress_poll_data(poll_data, POLL_COMPRESS_THRESHOLD_BYTES)
+    } else {
+        Ok(PollPayload::Raw(poll_data))
+    }
+}
+
+/// Closed issues on their own — the lazy second half of a first-paint poll.
+/// `limit` of 0 or None returns all of them.
+#[tauri::command]
+async fn bd_closed(cwd: Option<String>, limit: Option<usize>) -> Result<Vec<Issue>, String> {
+    let limit = limit.unwrap_or(0);
+    log_info!("[bd_closed] Fetching closed issues (limit={})", limit);
+    let args = vec!["--status=closed".to_string(), format!("--limit={}", limit)];
+    let output = execute_bd("list", &args, cwd.as_deref())?;
+    let raw = parse_issues_tolerant(&output, "bd_closed")?;
+    Ok(raw.into_iter().map(transform_issue).collect())
+}
+
+/// Ready issues are also in the open list, so a bad entry can be reported twice.
+fn dedupe_skipped(skipped: Vec<SkippedIssue>) -> Vec<SkippedIssue> {
+    let mut out: Vec<SkippedIssue> = Vec::new();
+    for skip in skipped {
+        let duplicate = skip.id.is_some() && out.iter().any(|s| s.id == skip.id);
+        if !duplicate {
+            out.push(skip);
+        }
+    }
+    out
+}
+
+/// Watermark to hand back: the latest `updated_at` seen, unless a bucket failed —
+/// then the caller's own `since` (or none), so the next delta re-fetches what was missed.
+fn poll_watermark(seen: Option<String>, since: Option<String>, had_errors: bool) -> Option<String> {
+    if had_errors {
+        since.filter(|s| !s.trim().is_empty())
+    } else {
+        seen
+    }
+}
+
+/// Keep issues updated at or after `since` (an edit in the same second as the
+/// watermark isn't lost; the client dedupes by id); tombstones among them are moved
+/// to `deleted`. Hard deletes leave no trace here, see `live_issue_ids`.
+fn changed_since(raw: Vec<BdRawIssue>, since: (i64, u32), deleted: &mut Vec<String>) -> Vec<BdRawIssue> {
+    raw.into_iter()
+        .filter(|issue| parse_timestamp_utc(&issue.updated_at).map_or(true, |updated| updated >= since))
+        .filter(|issue| {
+            if issue.status == "tombstone" {
+                deleted.push(issue.id.clone());
+                return false;
+            }
+            true
+        })
+        .collect()
+}
+
+/// Ids of the issues that still exist, tombstones excluded.
+fn live_issue_ids<'a>(raw: impl Iterator<Item = &'a BdRawIssue>) -> Vec<String> {
+    raw.filter(|issue| issue.status != "tombstone").map(|issue| issue.id.clone()).collect()
+}
+
+/// Build the poll response from independently fetched buckets.
+/// Failed buckets come back empty and are listed in `errors`; only when every
+/// bucket failed is the whole poll an error.
+fn assemble_poll_data(
+    open: Result<Vec<BdRawIssue>, String>,
+    closed: Result<Vec<BdRawIssue>, String>,
+    ready: Result<Vec<BdRawIssue>, String>,
+) -> Result<PollData, String> {
+    let mut errors: Vec<String> = Vec::new();
+    let mut bucket = |name: &str, result: Result<Vec<BdRawIssue>, String>| -> Vec<Issue> {
+        match result {
+            Ok(raw) => raw.into_iter().map(transform_issue).collect(),
+            Err(e) => {
+                log_warn!("[bd_poll_data] {} bucket failed: {}", name, e);
+                errors.push(format!("{}: {}", name, e));
+                Vec::new()
+            }
+        }
+    };
+
+    let open_issues = bucket("open", open);
+    let closed_issues = bucket("closed", closed);
+    let ready_issues = bucket("ready", ready);
+
+    if errors.len() == 3 {
+        return Err(errors.join("; "));
+    }
+
     Ok(PollData {
-        open_issues: raw_open.into_iter().map(transform_issue).collect(),
-        closed_issues: raw_closed.into_iter().map(transform_issue).collect(),
-        ready_issues: raw_ready.into_iter().map(transform_issue).collect(),
+        open_issues,
+        closed_issues,
+        ready_issues,
+        errors,
+        high_watermark: None,
+        deleted_ids: None,
+        live_ids: None,
+        parse_warnings: Vec::new(),
+        merge_conflict: None,
     })
 }
 
@@ -2348,6 +4276,22 @@ fn get_beads_mtime(beads_dir: &std::path::Path) -> Option<std::time::SystemTime>
     }
 }
 
+/// Report which backend serves the given project ("bd", "br", "dolt" or "unknown"),
+/// so the UI can badge projects individually.
+#[tauri::command]
+async fn bd_resolved_backend(cwd: Option<String>) -> String {
+    let working_dir = cwd
+        .or_else(|| env::var("BEADS_PATH").ok())
+        .unwrap_or_else(|| {
+            env::current_dir()
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_else(|_| ".".to_string())
+        });
+
+    let beads_dir = std::path::Path::new(&working_dir).join(".beads");
+    resolve_project_backend(get_cli_client_info(), &beads_dir).to_string()
+}
+
 /// Check if the beads database has changed since last check (via filesystem mtime).
 /// Returns true if changes detected or if this is the first check.
 /// This is extremely cheap — just a few stat() calls, no bd process spawns.
@@ -2361,8 +4305,27 @@ async fn bd_check_changed(cwd: Option<String>) -> Result<bool, String> {
                 .unwrap_or_else(|_| ".".to_string())
         });
 
+    Ok(check_project_changed(working_dir, POLL_ACTIVE.load(Ordering::Relaxed), get_beads_mtime))
+}
+
+/// Pause (false) or resume (true) change detection. Changes made while paused are
+/// picked up by the first check after resuming, since the stored mtime isn't touched.
+#[tauri::command]
+async fn set_poll_active(active: bool) {
+    POLL_ACTIVE.store(active, Ordering::Relaxed);
+    log_info!("[poll] {}", if active { "Resumed" } else { "Paused (window inactive)" });
+}
+
+fn check_project_changed(
+    working_dir: String,
+    active: bool,
+    stat: impl FnOnce(&std::path::Path) -> Option<std::time::SystemTime>,
+) -> bool {
+    if !active {
+        return false;
+    }
     let beads_dir = std::path::Path::new(&working_dir).join(".beads");
-    let current_mtime = get_beads_mtime(&beads_dir);
+    let current_mtime = stat(&beads_dir);
 
     let mut map = LAST_KNOWN_MTIME.lock().unwrap();
     let previous = map.get(&working_dir).copied();
@@ -2372,21 +4335,21 @@ async fn bd_check_changed(cwd: Option<String>) -> Result<bool, String> {
             if current != prev {
                 log_info!("[bd_check_changed] mtime changed — data may have been modified");
                 map.insert(working_dir, current);
-                Ok(true)
+                true
             } else {
                 log_debug!("[bd_check_changed] mtime unchanged — no changes");
-                Ok(false)
+                false
             }
         }
         (Some(current), None) => {
             // First check — store mtime, report changed so initial load happens
             map.insert(working_dir, current);
-            Ok(true)
+            true
         }
         (None, _) => {
             // No database file found
             log_warn!("[bd_check_changed] No beads database found in {}", working_dir);
-            Ok(true) // Report changed to let caller handle missing db
+            true // Report changed to let caller handle missing db
         }
     }
 }
@@ -2406,2547 +4369,7542 @@ async fn bd_reset_mtime(cwd: Option<String>) -> Result<(), String> {
     Ok(())
 }
 
-#[tauri::command]
-async fn bd_list(options: ListOptions) -> Result<Vec<Issue>, String> {
-    log_info!("[bd_list] cwd: {:?}", options.cwd);
-
-    // Sync database before reading to ensure data is up-to-date
-    sync_bd_database(options.cwd.as_deref());
+/// `bd_list` response: the issues, plus skipped entries when `includeParseWarnings` is set.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum ListResponse {
+    WithWarnings {
+        issues: Vec<Issue>,
+        #[serde(rename = "parseWarnings")]
+        parse_warnings: Vec<SkippedIssue>,
+    },
+    Issues(Vec<Issue>),
+}
 
-    let mut args: Vec<String> = Vec::new();
+#[tauri::command]
+async fn bd_list(options: ListOptions) -> Result<ListResponse, String> {
+    let (issues, parse_warnings) = fetch_list_report(&options)?;
+    if options.include_parse_warnings.unwrap_or(false) {
+        Ok(ListResponse::WithWarnings { issues, parse_warnings })
+    } else {
+        Ok(ListResponse::Issues(issues))
+    }
+}
 
-    // --all flag only works correctly on bd >= 0.55; for older versions, fallback to 2 calls
-    let use_all = options.include_all.unwrap_or(false);
-    if use_all && !supports_list_all_flag() {
-        // Fallback: fetch open + closed separately and merge
-        log_info!("[bd_list] --all requested but bd < 0.55 — falling back to 2 calls");
-        let mut fallback_args = args.clone();
-        fallback_args.push("--limit=0".to_string());
+/// Just enough of an issue for pickers and autocomplete
+#[derive(Debug, Serialize)]
+pub struct IssueSummary {
+    pub id: String,
+    pub title: String,
+    pub status: String,
+    #[serde(rename = "type")]
+    pub issue_type: String,
+}
 
-        let open_output = execute_bd("list", &fallback_args, options.cwd.as_deref())?;
-        let open_issues = parse_issues_tolerant(&open_output, "bd_list_open")?;
+impl From<Issue> for IssueSummary {
+    fn from(issue: Issue) -> Self {
+        IssueSummary {
+            id: issue.id,
+            title: issue.title,
+            status: issue.status,
+            issue_type: issue.issue_type,
+        }
+    }
+}
 
-        fallback_args.push("--status=closed".to_string());
-        let closed_output = execute_bd("list", &fallback_args, options.cwd.as_deref())?;
-        let closed_issues = parse_issues_tolerant(&closed_output, "bd_list_closed")?;
+/// `bd_list` projected to id/title/status/type — a much smaller IPC payload for pickers.
+#[tauri::command]
+async fn bd_list_minimal(options: ListOptions) -> Result<Vec<IssueSummary>, String> {
+    Ok(fetch_list_issues(&options)?.into_iter().map(IssueSummary::from).collect())
+}
 
-        let mut all_issues = open_issues;
-        all_issues.extend(closed_issues);
-        log_info!("[bd_list] Found {} issues (fallback)", all_issues.len());
-        return Ok(all_issues.into_iter().map(transform_issue).collect());
+/// `bd_list` as CSV text (header + one row per issue) for pasting into a spreadsheet.
+#[tauri::command]
+async fn bd_list_csv(options: ListOptions) -> Result<String, String> {
+    let issues = fetch_list_issues(&options)?;
+    log_info!("[bd_list_csv] {} issues", issues.len());
+    Ok(issues_to_csv(&issues))
+}
+
+const CSV_COLUMNS: [&str; 10] = [
+    "id", "title", "status", "priority", "type", "assignee", "labels", "createdAt", "updatedAt", "closedAt",
+];
+
+/// RFC 4180 CSV: fields with commas, quotes or line breaks are quoted, quotes doubled.
+/// Fields a spreadsheet would run as a formula get a leading `'` (see `csv_field`).
+/// Labels share one column, separated by `;`.
+fn issues_to_csv(issues: &[Issue]) -> String {
+    let mut out = String::new();
+    out.push_str(&CSV_COLUMNS.join(","));
+    out.push_str("\r\n");
+    for issue in issues {
+        let row = [
+            issue.id.as_str(),
+            issue.title.as_str(),
+            issue.status.as_str(),
+            issue.priority.as_str(),
+            issue.issue_type.as_str(),
+            issue.assignee.as_deref().unwrap_or(""),
+            &issue.labels.join(";"),
+            issue.created_at.as_str(),
+            issue.updated_at.as_str(),
+            issue.closed_at.as_deref().unwrap_or(""),
+        ];
+        out.push_str(&row.iter().map(|field| csv_field(field)).collect::<Vec<_>>().join(","));
+        out.push_str("\r\n");
     }
+    out
+}
 
-    if use_all {
-        args.push("--all".to_string());
-    }
-    if let Some(ref statuses) = options.status {
-        if !statuses.is_empty() {
-            args.push(format!("--status={}", statuses.join(",")));
-        }
+fn csv_field(value: &str) -> String {
+    // A title like `=HYPERLINK(...)` would otherwise be evaluated on open (CSV injection)
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
     }
-    if let Some(ref types) = options.issue_type {
-        if !types.is_empty() {
-            args.push(format!("--type={}", types.join(",")));
-        }
+}
+
+#[derive(Debug, Serialize)]
+pub struct IssueGroup {
+    pub group: String,
+    pub issues: Vec<Issue>,
+}
+
+/// Fields `bd_list_grouped` can group by
+const GROUP_BY_FIELDS: [&str; 5] = ["assignee", "priority", "type", "status", "label"];
+
+/// `bd_list` bucketed by `group_by` (assignee, priority, type, status or label).
+#[tauri::command]
+async fn bd_list_grouped(options: ListOptions, group_by: String) -> Result<Vec<IssueGroup>, String> {
+    if !GROUP_BY_FIELDS.contains(&group_by.as_str()) {
+        return Err(format!("Invalid groupBy: {} (expected one of {})", group_by, GROUP_BY_FIELDS.join(", ")));
     }
-    if let Some(ref priorities) = options.priority {
-        if !priorities.is_empty() {
-            let nums: Vec<String> = priorities.iter().map(|p| priority_to_number(p)).collect();
-            args.push(format!("--priority={}", nums.join(",")));
+    let issues = fetch_list_issues(&options)?;
+    Ok(group_issues(issues, &group_by))
+}
+
+/// Groups sorted by name, with the catch-all bucket ("unassigned"/"unlabeled") last.
+/// An issue with several labels appears under each of them.
+fn group_issues(issues: Vec<Issue>, group_by: &str) -> Vec<IssueGroup> {
+    let fallback = match group_by {
+        "assignee" => "unassigned",
+        "label" => "unlabeled",
+        _ => "",
+    };
+    let mut groups: HashMap<String, Vec<Issue>> = HashMap::new();
+    for issue in issues {
+        let keys: Vec<String> = match group_by {
+            "assignee" => vec![issue.assignee.clone().filter(|a| !a.is_empty()).unwrap_or_else(|| fallback.to_string())],
+            "priority" => vec![issue.priority.clone()],
+            "type" => vec![issue.issue_type.clone()],
+            "status" => vec![issue.status.clone()],
+            _ if issue.labels.is_empty() => vec![fallback.to_string()],
+            _ => issue.labels.clone(),
+        };
+        for key in keys {
+            groups.entry(key).or_default().push(issue.clone());
         }
     }
-    if let Some(ref assignee) = options.assignee {
-        args.push(format!("--assignee={}", assignee));
+    let mut groups: Vec<IssueGroup> = groups
+        .into_iter()
+        .map(|(group, issues)| IssueGroup { group, issues })
+        .collect();
+    groups.sort_by(|a, b| (a.group == fallback).cmp(&(b.group == fallback)).then_with(|| a.group.cmp(&b.group)));
+    groups
+}
+
+/// Cut-offs for `bd_needs_attention`; any field left out keeps its default.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct AttentionThresholds {
+    /// Ready + unassigned with no update for more than this many days
+    #[serde(rename = "unassignedDays")]
+    pub unassigned_days: u64,
+    /// In progress with no update for more than this many days
+    #[serde(rename = "staleDays")]
+    pub stale_days: u64,
+    /// Blocking at least this many open issues (directly or transitively)
+    #[serde(rename = "blockingCount")]
+    pub blocking_count: usize,
+}
+
+impl Default for AttentionThresholds {
+    fn default() -> Self {
+        Self { unassigned_days: 7, stale_days: 14, blocking_count: 3 }
     }
+}
 
-    // Always disable limit to get all issues (bd defaults to 50)
-    args.push("--limit=0".to_string());
-
-    let output = execute_bd("list", &args, options.cwd.as_deref())?;
-
-    let raw_issues = parse_issues_tolerant(&output, "bd_list")?;
-
-    log_info!("[bd_list] Found {} issues", raw_issues.len());
-    Ok(raw_issues.into_iter().map(transform_issue).collect())
+#[derive(Debug, Serialize)]
+pub struct AttentionIssue {
+    #[serde(flatten)]
+    pub issue: Issue,
+    /// Why the issue surfaced, one entry per matching heuristic
+    pub reasons: Vec<String>,
 }
 
+/// Open issues worth a look: urgent, ready but unowned for a while, stalled in
+/// progress, or blocking a lot of other work.
 #[tauri::command]
-async fn bd_count(options: CwdOptions) -> Result<CountResult, String> {
-    // Sync database before reading to ensure data is up-to-date
-    sync_bd_database(options.cwd.as_deref());
-
-    // Fetch all issues: single --all call for bd >= 0.55, fallback to 2 calls for older versions
-    let raw_issues = if supports_list_all_flag() {
-        let all_output = execute_bd("list", &["--all".to_string(), "--limit=0".to_string()], options.cwd.as_deref())?;
-        parse_issues_tolerant(&all_output, "bd_count_all")?
-    } else {
-        let open_output = execute_bd("list", &["--limit=0".to_string()], options.cwd.as_deref())?;
-        let closed_output = execute_bd("list", &["--status=closed".to_string(), "--limit=0".to_string()], options.cwd.as_deref())?;
-        let mut issues = parse_issues_tolerant(&open_output, "bd_count_open")?;
-        issues.extend(parse_issues_tolerant(&closed_output, "bd_count_closed")?);
-        issues
+async fn bd_needs_attention(
+    cwd: Option<String>,
+    thresholds: Option<AttentionThresholds>,
+) -> Result<Vec<AttentionIssue>, String> {
+    let list_options = ListOptions { include_all: Some(true), cwd, ..Default::default() };
+    let issues = fetch_list_issues(&list_options)?;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64;
+    let flagged = needs_attention(issues, &thresholds.unwrap_or_default(), now);
+    log_info!("[bd_needs_attention] {} issues need attention", flagged.len());
+    Ok(flagged)
+}
+
+/// Apply the attention heuristics at `now` (unix seconds). Age is measured from
+/// `updated_at` — the CLI doesn't record when an issue was last unassigned.
+/// Results are ordered by priority, then by number of reasons.
+fn needs_attention(issues: Vec<Issue>, thresholds: &AttentionThresholds, now: i64) -> Vec<AttentionIssue> {
+    let closed: std::collections::HashSet<&str> = issues.iter()
+        .filter(|i| i.status == "closed")
+        .map(|i| i.id.as_str())
+        .collect();
+    let days_idle = |issue: &Issue| {
+        parse_timestamp_utc(&issue.updated_at).map(|(secs, _)| (now - secs).max(0) as u64 / 86400)
     };
 
-    let mut by_type: HashMap<String, usize> = HashMap::new();
-    by_type.insert("bug".to_string(), 0);
-    by_type.insert("task".to_string(), 0);
-    by_type.insert("feature".to_string(), 0);
-    by_type.insert("epic".to_string(), 0);
-    by_type.insert("chore".to_string(), 0);
+    let blocking_graph = BlockingGraph::new(&issues);
 
-    let mut by_priority: HashMap<String, usize> = HashMap::new();
-    by_priority.insert("p0".to_string(), 0);
-    by_priority.insert("p1".to_string(), 0);
-    by_priority.insert("p2".to_string(), 0);
-    by_priority.insert("p3".to_string(), 0);
-    by_priority.insert("p4".to_string(), 0);
-
-    let mut last_updated: Option<String> = None;
-
-    for issue in &raw_issues {
-        let issue_type = issue.issue_type.to_lowercase();
-        if by_type.contains_key(&issue_type) {
-            *by_type.get_mut(&issue_type).unwrap() += 1;
+    let mut flagged: Vec<AttentionIssue> = Vec::new();
+    for issue in &issues {
+        if issue.status == "closed" || issue.status == "tombstone" {
+            continue;
         }
-
-        let priority_key = format!("p{}", issue.priority);
-        if by_priority.contains_key(&priority_key) {
-            *by_priority.get_mut(&priority_key).unwrap() += 1;
+        let mut reasons = Vec::new();
+        if issue.priority == "p0" || issue.priority == "p1" {
+            reasons.push(format!("{} priority", issue.priority.to_uppercase()));
         }
-
-        if last_updated.is_none() || issue.updated_at > *last_updated.as_ref().unwrap() {
-            last_updated = Some(issue.updated_at.clone());
+        let ready = issue.status == "open"
+            && issue.blocked_by.iter().flatten().all(|b| closed.contains(b.as_str()));
+        let unassigned = issue.assignee.as_deref().map_or(true, str::is_empty);
+        if let Some(idle) = days_idle(issue) {
+            if ready && unassigned && idle > thresholds.unassigned_days {
+                reasons.push(format!("Ready and unassigned for {} days", idle));
+            }
+            if issue.status == "in_progress" && idle > thresholds.stale_days {
+                reasons.push(format!("In progress with no update for {} days", idle));
+            }
+        }
+        let blocking = blocking_graph.downstream_count(&issue.id);
+        if thresholds.blocking_count > 0 && blocking >= thresholds.blocking_count {
+            reasons.push(format!("Blocking {} open issues", blocking));
+        }
+        if !reasons.is_empty() {
+            flagged.push(AttentionIssue { issue: issue.clone(), reasons });
         }
     }
-
-    Ok(CountResult {
-        count: raw_issues.len(),
-        by_type,
-        by_priority,
-        last_updated,
-    })
+    flagged.sort_by(|a, b| a.issue.priority.cmp(&b.issue.priority).then_with(|| b.reasons.len().cmp(&a.reasons.len())));
+    flagged
 }
 
-#[tauri::command]
-async fn bd_ready(options: CwdOptions) -> Result<Vec<Issue>, String> {
-    log_info!("[bd_ready] Called with cwd: {:?}", options.cwd);
-
-    // Sync database before reading to ensure data is up-to-date
-    sync_bd_database(options.cwd.as_deref());
-
-    let output = execute_bd("ready", &[], options.cwd.as_deref())?;
-
-    let raw_issues = parse_issues_tolerant(&output, "bd_ready")?;
-
-    log_info!("[bd_ready] Found {} ready issues", raw_issues.len());
-    Ok(raw_issues.into_iter().map(transform_issue).collect())
+/// Shared implementation of `bd_list`: syncs, runs `bd list` with the given filters
+/// and returns the transformed issues.
+fn fetch_list_issues(options: &ListOptions) -> Result<Vec<Issue>, String> {
+    fetch_list_report(options).map(|(issues, _)| issues)
 }
 
-#[tauri::command]
-async fn bd_status(options: CwdOptions) -> Result<serde_json::Value, String> {
-    let output = execute_bd("status", &[], options.cwd.as_deref())?;
+/// `fetch_list_issues`, also returning the CLI entries that couldn't be parsed.
+fn fetch_list_report(options: &ListOptions) -> Result<(Vec<Issue>, Vec<SkippedIssue>), String> {
+    let (issues, skipped) = fetch_cli_list_report(options)?;
+    let mut issues = apply_list_post_filters(issues, options)?;
+    if options.include_latest_comment.unwrap_or(false) {
+        let cwd = options.cwd.as_deref();
+        attach_latest_comments(&mut issues, LATEST_COMMENT_MAX_SHOWS, |id| fetch_show_issue(id, cwd));
+    }
+    if options.include_ready_since.unwrap_or(false) {
+        // Blockers are usually closed, so they're looked up in the full list
+        let all_options = ListOptions { include_all: Some(true), cwd: options.cwd.clone(), ..Default::default() };
+        let all = fetch_cli_list_issues(&all_options)?;
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        attach_ready_since(&mut issues, &all, now);
+    }
+    Ok((issues, skipped))
+}
 
-    serde_json::from_str(&output)
-        .map_err(|e| format!("Failed to parse status: {}", e))
+/// Set `ready_since`/`days_ready` on the ready issues among `issues`, looking blockers
+/// up in `all`. An issue with a blocker that isn't closed (or can't be found) isn't ready.
+fn attach_ready_since(issues: &mut [Issue], all: &[Issue], now: i64) {
+    let by_id: HashMap<&str, &Issue> = all.iter().map(|i| (i.id.as_str(), i)).collect();
+    for issue in issues.iter_mut() {
+        if issue.status != "open" {
+            continue;
+        }
+        let mut since = Some(issue.created_at.clone());
+        let mut latest: Option<(i64, u32)> = None;
+        for blocker in issue.blocked_by.iter().flatten() {
+            let closed_at = by_id.get(blocker.as_str())
+                .filter(|b| b.status == "closed")
+                .and_then(|b| b.closed_at.as_deref());
+            let Some(closed_at) = closed_at else {
+                since = None;
+                break;
+            };
+            let ts = parse_timestamp_utc(closed_at);
+            if latest.is_none() || ts > latest {
+                latest = ts;
+                since = Some(closed_at.to_string());
+            }
+        }
+        issue.days_ready = since.as_deref()
+            .and_then(parse_timestamp_utc)
+            .map(|(secs, _)| (now - secs).max(0) as u64 / 86400);
+        issue.ready_since = since;
+    }
 }
 
-#[tauri::command]
-async fn bd_show(id: String, options: CwdOptions) -> Result<Option<Issue>, String> {
-    log_info!("[bd_show] Called for issue: {} with cwd: {:?}", id, options.cwd);
+/// Characters kept from the newest comment in `latestComment`
+const LATEST_COMMENT_EXCERPT_CHARS: usize = 120;
 
-    // Sync database before reading to ensure data is up-to-date
-    sync_bd_database(options.cwd.as_deref());
+/// Upper bound on `bd show` calls made to fill `latestComment` for one list
+const LATEST_COMMENT_MAX_SHOWS: usize = 50;
 
-    let output = match execute_bd("show", std::slice::from_ref(&id), options.cwd.as_deref()) {
-        Ok(output) => output,
-        Err(e) => {
-            // Handle "not found" errors gracefully (future bd versions may use non-zero exit)
-            let err_lower = e.to_lowercase();
-            if err_lower.contains("no issue found") || err_lower.contains("not found") {
-                log_info!("[bd_show] Issue {} not found (error from bd): {}", id, e);
-                return Ok(None);
+/// The newest comment by `created_at`; on equal timestamps the later one in the list wins.
+fn latest_comment(comments: &[Comment]) -> Option<LatestComment> {
+    let key = |c: &Comment| parse_timestamp_utc(&c.created_at);
+    comments.iter()
+        .fold(None, |best: Option<&Comment>, c| match best {
+            Some(b) if key(b) > key(c) => Some(b),
+            _ => Some(c),
+        })
+        .map(|c| LatestComment {
+            author: c.author.clone(),
+            excerpt: excerpt(c.content.trim(), LATEST_COMMENT_EXCERPT_CHARS),
+            created_at: c.created_at.clone(),
+        })
+}
+
+/// Set `latest_comment` on each issue. `bd list` only reports a comment count, so
+/// issues with comments but no comments array are loaded with `show`, at most
+/// `max_shows` of them; the rest are left without a latest comment.
+fn attach_latest_comments(
+    issues: &mut [Issue],
+    max_shows: usize,
+    show: impl Fn(&str) -> Result<Option<Issue>, String>,
+) {
+    let mut shows = 0;
+    for issue in issues.iter_mut() {
+        if issue.comments.is_empty() && issue.comment_count.unwrap_or(0) > 0 {
+            if shows == max_shows {
+                log_warn!("[bd_list] Latest comments limited to {} shows, skipping {}", max_shows, issue.id);
+                continue;
+            }
+            shows += 1;
+            match show(&issue.id) {
+                Ok(Some(full)) => issue.comments = full.comments,
+                Ok(None) => {}
+                Err(e) => log_warn!("[bd_list] Could not load comments for {}: {}", issue.id, e),
             }
-            return Err(e);
         }
-    };
-
-    // Handle empty output (current bd behavior for missing issues: exit 0, empty stdout)
-    let trimmed = output.trim();
-    if trimmed.is_empty() {
-        log_info!("[bd_show] Issue {} not found (empty output from bd)", id);
-        return Ok(None);
+        issue.latest_comment = latest_comment(&issue.comments);
     }
+}
 
-    // bd show can return either a single object or an array
-    let result: serde_json::Value = serde_json::from_str(trimmed)
-        .map_err(|e| {
-            log_error!("[bd_show] Failed to parse JSON for {}: {}", id, e);
-            format!("Failed to parse issue: {}", e)
-        })?;
-
-    let raw_issue: Option<BdRawIssue> = if result.is_array() {
-        result.as_array()
-            .and_then(|arr| arr.first())
-            .and_then(|v| serde_json::from_value(v.clone()).ok())
-    } else {
-        serde_json::from_value(result).ok()
-    };
+/// Non-empty entries of `options.assignee`.
+fn requested_assignees(options: &ListOptions) -> Vec<&str> {
+    options.assignee.iter().flatten()
+        .map(|a| a.trim())
+        .filter(|a| !a.is_empty())
+        .collect()
+}
 
-    log_info!("[bd_show] Issue {} found: {}", id, raw_issue.is_some());
-    Ok(raw_issue.map(transform_issue))
+/// Non-empty entries of `options.labels`.
+fn requested_labels(options: &ListOptions) -> Vec<&str> {
+    options.labels.iter().flatten()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty())
+        .collect()
 }
 
-#[tauri::command]
-async fn bd_create(payload: CreatePayload) -> Result<Option<Issue>, String> {
-    log_info!("[bd_create] Creating issue: {:?}", payload.title);
-    let mut args: Vec<String> = vec![payload.title.clone()];
+/// Filters (and tree shaping) the CLI can't express, applied after `transform_issue`.
+fn apply_list_post_filters(mut issues: Vec<Issue>, options: &ListOptions) -> Result<Vec<Issue>, String> {
+    if let Some(actor) = options.last_actor.as_deref().filter(|a| !a.is_empty()) {
+        log_info!("[bd_list] lastActor={}: last-actor tracking unavailable with the CLI backend, returning no issues", actor);
+        return Ok(Vec::new());
+    }
 
-    if let Some(ref desc) = payload.description {
-        args.push("--description".to_string());
-        args.push(desc.clone());
+    if options.mine_only.unwrap_or(false) {
+        retain_assigned_to_actor(&mut issues, resolve_actor(options.cwd.as_deref()))?;
     }
-    if let Some(ref t) = payload.issue_type {
-        args.push("--type".to_string());
-        args.push(t.clone());
+
+    let assignees = requested_assignees(options);
+    if assignees.len() > 1 {
+        issues.retain(|i| i.assignee.as_deref().is_some_and(|a| assignees.contains(&a)));
     }
-    if let Some(ref p) = payload.priority {
-        args.push("--priority".to_string());
-        args.push(priority_to_number(p));
+
+    let labels = requested_labels(options);
+    if !labels.is_empty() {
+        let match_all = match options.labels_match.as_deref().unwrap_or("any") {
+            "any" => false,
+            "all" => true,
+            other => return Err(format!("Invalid labelsMatch: {} (expected any or all)", other)),
+        };
+        issues.retain(|i| {
+            let has = |label: &&str| i.labels.iter().any(|l| l == label);
+            if match_all { labels.iter().all(has) } else { labels.iter().any(has) }
+        });
     }
-    if let Some(ref a) = payload.assignee {
-        args.push("--assignee".to_string());
-        args.push(a.clone());
+
+    if let Some(pattern) = options.id_pattern.as_deref().map(str::trim).filter(|p| !p.is_empty()) {
+        validate_id_pattern(pattern)?;
+        issues.retain(|i| id_matches_pattern(&i.id, pattern));
     }
-    if let Some(ref labels) = payload.labels {
-        if !labels.is_empty() {
-            args.push("--labels".to_string());
-            args.push(labels.join(","));
-        }
+
+    if options.top_level_only.unwrap_or(false) {
+        issues.retain(|i| i.parent.as_ref().map_or(true, |p| p.id.is_empty()));
     }
-    if let Some(ref ext) = payload.external_ref {
-        args.push("--external-ref".to_string());
-        args.push(ext.clone());
+
+    let after = parse_list_date_bound("createdAfter", options.created_after.as_deref(), "00:00:00")?;
+    let before = parse_list_date_bound("createdBefore", options.created_before.as_deref(), "23:59:59.999999999")?;
+    if after.is_some() || before.is_some() {
+        issues.retain(|i| match parse_timestamp_utc(&i.created_at) {
+            Some(created) => after.map_or(true, |a| created >= a) && before.map_or(true, |b| created <= b),
+            None => false,
+        });
     }
-    if let Some(est) = payload.estimate_minutes {
-        args.push("--estimate".to_string());
-        args.push(est.to_string());
+
+    if options.limit.is_some() || options.offset.is_some() {
+        issues = paginate_by_offset(issues, options.offset.unwrap_or(0), options.limit);
     }
-    if let Some(ref design) = payload.design_notes {
-        args.push("--design".to_string());
-        args.push(design.clone());
+
+    if let Some(max_chars) = options.description_excerpt {
+        for issue in issues.iter_mut() {
+            issue.description = excerpt(&issue.description, max_chars);
+        }
     }
-    if let Some(ref acc) = payload.acceptance_criteria {
-        args.push("--acceptance".to_string());
-        args.push(acc.clone());
+
+    if options.tree.unwrap_or(false) {
+        issues = order_as_tree(issues);
     }
-    if let Some(ref notes) = payload.working_notes {
-        args.push("--notes".to_string());
-        args.push(notes.clone());
+    Ok(issues)
+}
+
+/// `text` cut to at most `max_chars` characters at a word boundary, plus "…".
+/// A single word longer than the limit is cut mid-word.
+fn excerpt(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
     }
-    if let Some(ref parent) = payload.parent {
-        if !parent.is_empty() {
-            args.push("--parent".to_string());
-            args.push(parent.clone());
+    let cut: String = text.chars().take(max_chars).collect();
+    let at_boundary = text.chars().nth(max_chars).is_some_and(char::is_whitespace);
+    let kept = if at_boundary {
+        cut.as_str()
+    } else {
+        cut.rfind(char::is_whitespace).map_or(cut.as_str(), |pos| &cut[..pos])
+    };
+    format!("{}…", kept.trim_end())
+}
+
+/// Deepest nesting followed when ordering a tree — guards against parent cycles.
+const TREE_MAX_DEPTH: usize = 32;
+
+/// Order issues parent-before-children (depth-first, original order among siblings)
+/// and set `depth` and the child count. Issues whose parent isn't in the list are roots; anything left
+/// unreached (parent cycles, nesting beyond the cap) is appended as a root.
+fn order_as_tree(issues: Vec<Issue>) -> Vec<Issue> {
+    let ids: std::collections::HashSet<String> = issues.iter().map(|i| i.id.clone()).collect();
+    let parent_of = |issue: &Issue| issue.parent.as_ref().map(|p| p.id.clone()).filter(|p| ids.contains(p));
+
+    let mut children: HashMap<String, Vec<usize>> = HashMap::new();
+    let mut roots: Vec<usize> = Vec::new();
+    for (idx, issue) in issues.iter().enumerate() {
+        match parent_of(issue) {
+            Some(parent) => children.entry(parent).or_default().push(idx),
+            None => roots.push(idx),
         }
     }
-    if let Some(ref spec_id) = payload.spec_id {
-        if !spec_id.is_empty() {
-            args.push("--spec-id".to_string());
-            args.push(spec_id.clone());
+
+    let mut order: Vec<(usize, usize)> = Vec::new();
+    let mut placed = vec![false; issues.len()];
+    let mut stack: Vec<(usize, usize)> = roots.iter().rev().map(|&r| (r, 0)).collect();
+    while let Some((idx, depth)) = stack.pop() {
+        if placed[idx] {
+            continue;
+        }
+        placed[idx] = true;
+        order.push((idx, depth));
+        if depth < TREE_MAX_DEPTH {
+            if let Some(kids) = children.get(&issues[idx].id) {
+                stack.extend(kids.iter().rev().map(|&k| (k, depth + 1)));
+            }
+        }
+    }
+    for (idx, done) in placed.iter().enumerate() {
+        if !done {
+            order.push((idx, 0));
         }
     }
 
-    let output = execute_bd("create", &args, payload.cwd.as_deref())?;
+    let mut slots: Vec<Option<Issue>> = issues.into_iter().map(Some).collect();
+    order.into_iter()
+        .filter_map(|(idx, depth)| {
+            slots[idx].take().map(|mut issue| {
+                issue.depth = Some(depth);
+                let count = children.get(&issue.id).map_or(0, Vec::len);
+                set_child_count(&mut issue, count);
+                issue
+            })
+        })
+        .collect()
+}
 
-    let raw_issue: BdRawIssue = serde_json::from_str(&output)
-        .map_err(|e| format!("Failed to parse created issue: {}", e))?;
+/// Id patterns may only contain id characters plus the `*`/`?` wildcards.
+fn validate_id_pattern(pattern: &str) -> Result<(), String> {
+    if pattern.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '*' | '?')) {
+        Ok(())
+    } else {
+        Err(format!("Invalid idPattern '{}': only letters, digits, '-', '_', '.', '*' and '?' are allowed", pattern))
+    }
+}
+
+/// Match an id against a glob (`*` any run, `?` one char); without wildcards it's a prefix match.
+fn id_matches_pattern(id: &str, pattern: &str) -> bool {
+    if !pattern.contains(['*', '?']) {
+        return id.starts_with(pattern);
+    }
+    let (id, pat): (Vec<char>, Vec<char>) = (id.chars().collect(), pattern.chars().collect());
+    let (mut i, mut p) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while i < id.len() {
+        if p < pat.len() && (pat[p] == '?' || pat[p] == id[i]) {
+            i += 1;
+            p += 1;
+        } else if p < pat.len() && pat[p] == '*' {
+            backtrack = Some((p, i));
+            p += 1;
+        } else if let Some((star_p, star_i)) = backtrack {
+            p = star_p + 1;
+            i = star_i + 1;
+            backtrack = Some((star_p, star_i + 1));
+        } else {
+            return false;
+        }
+    }
+    pat[p..].iter().all(|&c| c == '*')
+}
+
+/// Parse an optional list date filter. Date-only values are expanded with `day_time` (UTC),
+/// so an inclusive upper bound can cover the whole day rather than stop at midnight.
+fn parse_list_date_bound(name: &str, value: Option<&str>, day_time: &str) -> Result<Option<(i64, u32)>, String> {
+    let value = match value.map(str::trim) {
+        Some(v) if !v.is_empty() => v,
+        _ => return Ok(None),
+    };
+    let parsed = if value.len() == 10 {
+        parse_timestamp_utc(&format!("{}T{}Z", value, day_time))
+    } else {
+        parse_timestamp_utc(value)
+    };
+    parsed
+        .map(Some)
+        .ok_or_else(|| format!("Invalid {} timestamp: {}", name, value))
+}
 
-    Ok(Some(transform_issue(raw_issue)))
+/// Run `bd list` with the filters the CLI supports natively.
+fn fetch_cli_list_issues(options: &ListOptions) -> Result<Vec<Issue>, String> {
+    fetch_cli_list_report(options).map(|(issues, _)| issues)
 }
 
-#[tauri::command]
-async fn bd_update(id: String, updates: UpdatePayload) -> Result<Option<Issue>, String> {
-    // Always log update calls for debugging (regardless of LOGGING_ENABLED)
-    log::info!("[bd_update] Updating issue: {} with cwd: {:?}", id, updates.cwd);
-    log::info!("[bd_update] Updates: status={:?}, title={:?}, type={:?}", updates.status, updates.title, updates.issue_type);
+fn fetch_cli_list_report(options: &ListOptions) -> Result<(Vec<Issue>, Vec<SkippedIssue>), String> {
+    log_info!("[bd_list] cwd: {:?}", options.cwd);
 
-    let mut args: Vec<String> = vec![id.clone()];
+    // Sync database before reading to ensure data is up-to-date
+    sync_bd_database(options.cwd.as_deref());
 
-    if let Some(ref title) = updates.title {
-        args.push("--title".to_string());
-        args.push(title.clone());
+    let mut args: Vec<String> = Vec::new();
+
+    // --all flag only works correctly on bd >= 0.55; for older versions, fallback to 2 calls
+    let use_all = options.include_all.unwrap_or(false);
+    if use_all && !supports_list_all_flag() {
+        // Fallback: fetch open + closed separately and merge
+        log_info!("[bd_list] --all requested but bd < 0.55 — falling back to 2 calls");
+        let mut fallback_args = args.clone();
+        fallback_args.push("--limit=0".to_string());
+
+        let open_output = execute_bd("list", &fallback_args, options.cwd.as_deref())?;
+        let open = parse_issues_report(&open_output, "bd_list_open")?;
+
+        fallback_args.push("--status=closed".to_string());
+        let closed_output = execute_bd("list", &fallback_args, options.cwd.as_deref())?;
+        let closed = parse_issues_report(&closed_output, "bd_list_closed")?;
+
+        let mut all_issues = open.issues;
+        all_issues.extend(closed.issues);
+        log_info!("[bd_list] Found {} issues (fallback)", all_issues.len());
+        let skipped = dedupe_skipped([open.skipped, closed.skipped].concat());
+        return Ok((all_issues.into_iter().map(transform_issue).collect(), skipped));
     }
-    if let Some(ref desc) = updates.description {
-        args.push("--description".to_string());
-        args.push(desc.clone());
+
+    if use_all {
+        args.push("--all".to_string());
     }
-    if let Some(ref t) = updates.issue_type {
-        args.push("--type".to_string());
-        args.push(t.clone());
+    if let Some(ref statuses) = options.status {
+        if !statuses.is_empty() {
+            args.push(format!("--status={}", statuses.join(",")));
+        }
     }
-    if let Some(ref s) = updates.status {
-        args.push("--status".to_string());
-        args.push(s.clone());
+    if let Some(ref types) = options.issue_type {
+        if !types.is_empty() {
+            args.push(format!("--type={}", types.join(",")));
+        }
     }
-    if let Some(ref p) = updates.priority {
-        args.push("--priority".to_string());
-        args.push(priority_to_number(p));
+    if let Some(ref priorities) = options.priority {
+        if !priorities.is_empty() {
+            let nums: Vec<String> = priorities.iter().map(|p| priority_to_number(p)).collect();
+            args.push(format!("--priority={}", nums.join(",")));
+        }
     }
-    if let Some(ref a) = updates.assignee {
-        args.push("--assignee".to_string());
-        args.push(a.clone());
+    // The CLI filters on a single assignee; several are matched in apply_list_post_filters
+    if let [assignee] = requested_assignees(options).as_slice() {
+        args.push(format!("--assignee={}", assignee));
     }
-    if let Some(ref labels) = updates.labels {
-        args.push("--set-labels".to_string());
-        args.push(labels.join(","));
+    // Same for labels: one narrows the CLI query, any/all over several is matched afterwards
+    if let [label] = requested_labels(options).as_slice() {
+        args.push(format!("--label={}", label));
     }
-    if let Some(ref ext) = updates.external_ref {
-        args.push("--external-ref".to_string());
-        args.push(ext.clone());
+
+    // Always disable limit to get all issues (bd defaults to 50)
+    args.push("--limit=0".to_string());
+
+    let output = execute_bd("list", &args, options.cwd.as_deref())?;
+
+    let parsed = parse_issues_report(&output, "bd_list")?;
+
+    log_info!("[bd_list] Found {} issues", parsed.issues.len());
+    Ok((parsed.issues.into_iter().map(transform_issue).collect(), parsed.skipped))
+}
+
+/// Offset pagination: `created_at DESC, id DESC` order, skip `offset`, keep at most `limit`.
+/// Timestamps are compared as instants (SQLite and RFC 3339 forms mix in one list);
+/// unparseable ones sort last. Prefer `paginate_by_cursor` when issues may be created between pages.
+fn paginate_by_offset(mut issues: Vec<Issue>, offset: usize, limit: Option<usize>) -> Vec<Issue> {
+    issues.sort_by_cached_key(|i| std::cmp::Reverse((parse_timestamp_utc(&i.created_at), i.id.clone())));
+    issues.into_iter().skip(offset).take(limit.unwrap_or(usize::MAX)).collect()
+}
+
+/// Keyset pagination over a list of issues.
+/// Orders by `created_at DESC, id DESC`, keeps only issues strictly after the cursor
+/// and returns at most `limit` of them (None = all) plus the cursor for the next page.
+/// Timestamps are compared as instants like `paginate_by_offset`; unparseable ones sort last.
+/// Unlike offset paging, issues created between two calls can't shift rows across pages.
+fn paginate_by_cursor(mut issues: Vec<Issue>, cursor: Option<&ListCursor>, limit: Option<usize>) -> IssuePage {
+    issues.sort_by_cached_key(|i| std::cmp::Reverse((parse_timestamp_utc(&i.created_at), i.id.clone())));
+
+    if let Some(c) = cursor {
+        let after = (parse_timestamp_utc(&c.after_created_at), c.after_id.as_str());
+        issues.retain(|i| (parse_timestamp_utc(&i.created_at), i.id.as_str()) < after);
     }
-    if let Some(est) = updates.estimate_minutes {
-        args.push("--estimate".to_string());
-        args.push(est.to_string());
-    }
-    if let Some(ref design) = updates.design_notes {
-        args.push("--design".to_string());
-        args.push(design.clone());
-    }
-    if let Some(ref acc) = updates.acceptance_criteria {
-        args.push("--acceptance".to_string());
-        args.push(acc.clone());
-    }
-    if let Some(ref notes) = updates.working_notes {
-        args.push("--notes".to_string());
-        args.push(notes.clone());
-    }
-    if let Some(ref metadata) = updates.metadata {
-        args.push("--metadata".to_string());
-        args.push(metadata.clone());
-    }
-    if let Some(ref spec_id) = updates.spec_id {
-        args.push("--spec-id".to_string());
-        args.push(spec_id.clone());
-    }
-    if let Some(ref parent) = updates.parent {
-        args.push("--parent".to_string());
-        args.push(parent.clone());
-    }
-
-    log::info!("[bd_update] Executing: bd update {}", args.join(" "));
-    let output = execute_bd("update", &args, updates.cwd.as_deref())?;
-
-    log::info!("[bd_update] Raw output: {}", output.chars().take(500).collect::<String>());
-
-    // Handle empty output from bd CLI (some updates return empty response)
-    let trimmed_output = output.trim();
-    if trimmed_output.is_empty() {
-        log::info!("[bd_update] Empty response from bd, fetching issue {} to get updated data", id);
-        // Fetch the updated issue directly
-        let show_output = execute_bd("show", std::slice::from_ref(&id), updates.cwd.as_deref())?;
-        let show_result: serde_json::Value = serde_json::from_str(&show_output)
-            .map_err(|e| {
-                log::error!("[bd_update] Failed to parse show JSON: {}", e);
-                format!("Failed to fetch updated issue: {}", e)
-            })?;
-
-        let raw_issue: Option<BdRawIssue> = if show_result.is_array() {
-            show_result.as_array()
-                .and_then(|arr| arr.first())
-                .and_then(|v| serde_json::from_value(v.clone()).ok())
-        } else {
-            serde_json::from_value(show_result).ok()
-        };
 
-        return Ok(raw_issue.map(transform_issue));
+    let has_more = limit.is_some_and(|limit| issues.len() > limit);
+    if let (true, Some(limit)) = (has_more, limit) {
+        issues.truncate(limit);
     }
 
-    // bd update can return either a single object or an array
-    let result: serde_json::Value = serde_json::from_str(trimmed_output)
-        .map_err(|e| {
-            log::error!("[bd_update] Failed to parse JSON: {}", e);
-            format!("Failed to parse updated issue: {}", e)
-        })?;
-
-    let raw_issue: Option<BdRawIssue> = if result.is_array() {
-        log::info!("[bd_update] Result is array");
-        result.as_array()
-            .and_then(|arr| arr.first())
-            .and_then(|v| serde_json::from_value(v.clone()).ok())
+    let next_cursor = if has_more {
+        issues.last().map(|last| ListCursor {
+            after_created_at: last.created_at.clone(),
+            after_id: last.id.clone(),
+        })
     } else {
-        log::info!("[bd_update] Result is object");
-        serde_json::from_value(result.clone()).map_err(|e| {
-            log::error!("[bd_update] Failed to parse issue from result: {}", e);
-            e
-        }).ok()
+        None
     };
 
-    if let Some(ref issue) = raw_issue {
-        log::info!("[bd_update] Updated issue {} - new status: {}", id, issue.status);
-    } else {
-        log::warn!("[bd_update] Could not parse updated issue from response");
-    }
-
-    Ok(raw_issue.map(transform_issue))
+    IssuePage { issues, next_cursor }
 }
 
-#[tauri::command]
-async fn bd_close(id: String, options: CwdOptions) -> Result<serde_json::Value, String> {
-    log_info!("[bd_close] Closing issue: {} with cwd: {:?}", id, options.cwd);
+/// Resolve the current actor the same way bd does:
+/// `BD_ACTOR`, then `git config user.name` in the project, then the OS user.
+fn resolve_actor(cwd: Option<&str>) -> Option<String> {
+    let git_name = || {
+        let mut cmd = new_command("git");
+        cmd.args(["config", "user.name"]);
+        if let Some(dir) = cwd {
+            cmd.current_dir(dir);
+        }
+        cmd.output().ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+    };
+    pick_actor(
+        env::var("BD_ACTOR").ok(),
+        git_name,
+        env::var("USER").or_else(|_| env::var("USERNAME")).ok(),
+    )
+}
 
-    let mut args = vec![id.clone()];
-    // br supports --suggest-next for showing newly unblocked issues
-    if matches!(get_cli_client_info(), Some((CliClient::Br, _, _, _))) {
-        args.push("--suggest-next".to_string());
-    }
+/// First non-blank candidate in actor resolution order. Git is only queried when needed.
+fn pick_actor(
+    bd_actor: Option<String>,
+    git_name: impl FnOnce() -> Option<String>,
+    os_user: Option<String>,
+) -> Option<String> {
+    let non_blank = |v: Option<String>| v.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+    non_blank(bd_actor)
+        .or_else(|| non_blank(git_name()))
+        .or_else(|| non_blank(os_user))
+}
+
+/// `mineOnly`: keep the issues assigned to the resolved `actor`.
+fn retain_assigned_to_actor(issues: &mut Vec<Issue>, actor: Option<String>) -> Result<(), String> {
+    let actor = actor
+        .ok_or_else(|| "Could not resolve the current actor (set BD_ACTOR or git user.name)".to_string())?;
+    issues.retain(|i| i.assignee.as_deref() == Some(actor.as_str()));
+    log_info!("[bd_list] mineOnly: {} issues assigned to {}", issues.len(), actor);
+    Ok(())
+}
 
-    let output = execute_bd("close", &args, options.cwd.as_deref())?;
+/// Current actor for the project, so the UI can label "my issues" views.
+#[tauri::command]
+async fn bd_current_actor(cwd: Option<String>) -> Option<String> {
+    resolve_actor(cwd.as_deref())
+}
 
-    log_info!("[bd_close] Raw output: {}", output.chars().take(500).collect::<String>());
+#[derive(Debug, PartialEq, Serialize)]
+pub struct TypeCount {
+    #[serde(rename = "type")]
+    pub issue_type: String,
+    pub count: usize,
+}
 
-    let result: serde_json::Value = serde_json::from_str(&output)
-        .map_err(|e| {
-            log_error!("[bd_close] Failed to parse JSON: {}", e);
-            format!("Failed to parse close result: {}", e)
-        })?;
+/// Issue types present in the project with counts, custom types included.
+#[tauri::command]
+async fn bd_types(cwd: Option<String>) -> Result<Vec<TypeCount>, String> {
+    let raw_issues = fetch_all_raw_issues(cwd.as_deref(), "bd_types")?;
+    Ok(count_issue_types(&raw_issues))
+}
 
-    log_info!("[bd_close] Issue {} closed successfully", id);
-    Ok(result)
+/// Most common first, ties by name. Tombstones aren't counted.
+fn count_issue_types(issues: &[BdRawIssue]) -> Vec<TypeCount> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for issue in issues.iter().filter(|i| i.status != "tombstone") {
+        *counts.entry(issue.issue_type.to_lowercase()).or_insert(0) += 1;
+    }
+    let mut types: Vec<TypeCount> = counts
+        .into_iter()
+        .map(|(issue_type, count)| TypeCount { issue_type, count })
+        .collect();
+    types.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.issue_type.cmp(&b.issue_type)));
+    types
 }
 
+/// Cursor-based variant of `bd_list` for infinite scroll.
+/// Pass the `nextCursor` of the previous page to get the following one.
 #[tauri::command]
-async fn bd_search(query: String, options: CwdOptions) -> Result<Vec<Issue>, String> {
-    log_info!("[bd_search] Searching for: {} with cwd: {:?}", query, options.cwd);
-
-    let args = vec![query];
-    let output = execute_bd("search", &args, options.cwd.as_deref())?;
+async fn bd_list_cursor(options: ListOptions, cursor: Option<ListCursor>, limit: Option<usize>) -> Result<IssuePage, String> {
+    let issues = fetch_list_issues(&options)?;
+    let page = paginate_by_cursor(issues, cursor.as_ref(), limit);
+    log_info!("[bd_list_cursor] Returning {} issues (more: {})", page.issues.len(), page.next_cursor.is_some());
+    Ok(page)
+}
 
-    log_info!("[bd_search] Raw output: {}", output.chars().take(500).collect::<String>());
+/// Every raw issue, closed included (synced first). Raw rather than transformed so
+/// callers see the CLI's own values, e.g. custom issue types.
+fn fetch_all_raw_issues(cwd: Option<&str>, context: &str) -> Result<Vec<BdRawIssue>, String> {
+    // Sync database before reading to ensure data is up-to-date
+    sync_bd_database(cwd);
 
-    let trimmed = output.trim();
-    if trimmed.is_empty() || trimmed == "[]" {
-        return Ok(vec![]);
+    // Single --all call for bd >= 0.55, fallback to 2 calls for older versions
+    if supports_list_all_flag() {
+        let all_output = execute_bd("list", &["--all".to_string(), "--limit=0".to_string()], cwd)?;
+        parse_issues_tolerant(&all_output, &format!("{}_all", context))
+    } else {
+        let open_output = execute_bd("list", &["--limit=0".to_string()], cwd)?;
+        let closed_output = execute_bd("list", &["--status=closed".to_string(), "--limit=0".to_string()], cwd)?;
+        let mut issues = parse_issues_tolerant(&open_output, &format!("{}_open", context))?;
+        issues.extend(parse_issues_tolerant(&closed_output, &format!("{}_closed", context))?);
+        Ok(issues)
     }
-
-    let raw: Vec<BdRawIssue> = serde_json::from_str(trimmed)
-        .map_err(|e| {
-            log_error!("[bd_search] Failed to parse JSON: {}", e);
-            format!("Failed to parse search results: {}", e)
-        })?;
-
-    Ok(raw.into_iter().map(transform_issue).collect())
 }
 
 #[tauri::command]
-async fn bd_label_add(id: String, label: String, options: CwdOptions) -> Result<(), String> {
-    log_info!("[bd_label_add] Adding label '{}' to issue {}", label, id);
-    let args = vec![id, label];
-    execute_bd("label add", &args, options.cwd.as_deref())?;
-    Ok(())
+async fn bd_count(options: CwdOptions) -> Result<CountResult, String> {
+    let raw_issues = fetch_all_raw_issues(options.cwd.as_deref(), "bd_count")?;
+    Ok(count_raw_issues(&raw_issues))
 }
 
-#[tauri::command]
-async fn bd_label_remove(id: String, label: String, options: CwdOptions) -> Result<(), String> {
-    log_info!("[bd_label_remove] Removing label '{}' from issue {}", label, id);
-    let args = vec![id, label];
-    execute_bd("label remove", &args, options.cwd.as_deref())?;
-    Ok(())
+#[derive(Debug, Serialize)]
+pub struct DashboardData {
+    pub counts: CountResult,
+    pub ready: Vec<Issue>,
 }
 
+/// Counts plus the ready queue for summary widgets. Cheaper than `bd_poll_data`: the
+/// full list is only counted, never transformed, and no JSON for it crosses IPC.
 #[tauri::command]
-async fn bd_delete(id: String, options: CwdOptions) -> Result<serde_json::Value, String> {
-    let mut args = vec![id.clone(), "--force".to_string()];
-    if supports_delete_hard_flag() {
-        args.push("--hard".to_string());
+async fn bd_dashboard(cwd: Option<String>) -> Result<DashboardData, String> {
+    let cwd = cwd.as_deref();
+    let raw_issues = fetch_all_raw_issues(cwd, "bd_dashboard")?;
+    let ready_output = execute_bd("ready", &[], cwd)?;
+    let raw_ready = parse_issues_tolerant(&ready_output, "bd_dashboard_ready")?;
+    let dashboard = dashboard_data(&raw_issues, raw_ready);
+    log_info!("[bd_dashboard] {} issues, {} ready", dashboard.counts.count, dashboard.ready.len());
+    Ok(dashboard)
+}
+
+fn dashboard_data(all_issues: &[BdRawIssue], ready: Vec<BdRawIssue>) -> DashboardData {
+    DashboardData {
+        counts: count_raw_issues(all_issues),
+        ready: ready.into_iter().map(transform_issue).collect(),
     }
-    log::info!("[bd_delete] Deleting issue: {} with args: {:?}", id, args);
-    execute_bd("delete", &args, options.cwd.as_deref())?;
+}
 
-    // Sync after delete to push deletion to remote and prevent resurrection
-    sync_bd_database(options.cwd.as_deref());
+/// Totals and breakdowns for `bd_count`. Tombstones are left out like in the lists
+/// (whether `list --all` returns them depends on the bd version) and counted apart.
+fn count_raw_issues(all_issues: &[BdRawIssue]) -> CountResult {
+    let (tombstones, raw_issues): (Vec<&BdRawIssue>, Vec<&BdRawIssue>) =
+        all_issues.iter().partition(|i| i.status == "tombstone");
 
-    // Clean up attachments folder for this issue
-    let project_path = options.cwd.as_deref().unwrap_or(".");
-    let abs_project_path = if project_path == "." || project_path.is_empty() {
-        env::current_dir().ok()
-    } else {
-        let p = PathBuf::from(project_path);
-        if p.is_relative() {
-            env::current_dir().ok().map(|cwd| cwd.join(&p))
-        } else {
-            Some(p)
+    let mut by_type: HashMap<String, usize> = HashMap::new();
+    by_type.insert("bug".to_string(), 0);
+    by_type.insert("task".to_string(), 0);
+    by_type.insert("feature".to_string(), 0);
+    by_type.insert("epic".to_string(), 0);
+    by_type.insert("chore".to_string(), 0);
+
+    let mut by_priority: HashMap<String, usize> = HashMap::new();
+    by_priority.insert("p0".to_string(), 0);
+    by_priority.insert("p1".to_string(), 0);
+    by_priority.insert("p2".to_string(), 0);
+    by_priority.insert("p3".to_string(), 0);
+    by_priority.insert("p4".to_string(), 0);
+
+    let mut by_assignee: HashMap<String, usize> = HashMap::new();
+
+    for issue in raw_issues.iter() {
+        let assignee = issue.assignee.as_deref().map(str::trim).unwrap_or_default();
+        *by_assignee.entry(assignee.to_string()).or_insert(0) += 1;
+
+        let issue_type = issue.issue_type.to_lowercase();
+        if by_type.contains_key(&issue_type) {
+            *by_type.get_mut(&issue_type).unwrap() += 1;
         }
-    };
 
-    if let Some(path) = abs_project_path {
-        if let Ok(abs_path) = path.canonicalize() {
-            let att_dir = abs_path.join(".beads").join("attachments").join(issue_short_id(&id));
-            if att_dir.exists() && att_dir.is_dir() {
-                if let Err(e) = fs::remove_dir_all(&att_dir) {
-                    log::warn!("[bd_delete] Failed to remove attachments folder: {}", e);
-                } else {
-                    log::info!("[bd_delete] Removed attachments folder: {:?}", att_dir);
-                }
-            }
+        let priority_key = format!("p{}", issue.priority);
+        if by_priority.contains_key(&priority_key) {
+            *by_priority.get_mut(&priority_key).unwrap() += 1;
         }
     }
 
-    Ok(serde_json::json!({ "success": true, "id": id }))
+    let last_updated = latest_timestamp(raw_issues.iter().map(|i| i.updated_at.as_str()));
+
+    CountResult {
+        count: raw_issues.len(),
+        by_type,
+        by_priority,
+        by_assignee,
+        last_updated,
+        tombstone_count: tombstones.len(),
+    }
 }
 
 #[tauri::command]
-async fn bd_comments_add(id: String, content: String, options: CwdOptions) -> Result<serde_json::Value, String> {
-    let args = vec![id, content];
+async fn bd_ready(options: CwdOptions) -> Result<Vec<Issue>, String> {
+    log_info!("[bd_ready] Called with cwd: {:?}", options.cwd);
 
-    execute_bd("comments add", &args, options.cwd.as_deref())?;
+    // Sync database before reading to ensure data is up-to-date
+    sync_bd_database(options.cwd.as_deref());
 
-    Ok(serde_json::json!({ "success": true }))
+    let output = execute_bd("ready", &[], options.cwd.as_deref())?;
+
+    let raw_issues = parse_issues_tolerant(&output, "bd_ready")?;
+
+    log_info!("[bd_ready] Found {} ready issues", raw_issues.len());
+    Ok(raw_issues.into_iter().map(transform_issue).collect())
 }
 
 #[tauri::command]
-async fn bd_dep_add(issue_id: String, blocker_id: String, options: CwdOptions) -> Result<serde_json::Value, String> {
-    let args = vec![issue_id, blocker_id];
-
-    execute_bd("dep add", &args, options.cwd.as_deref())?;
+async fn bd_status(options: CwdOptions) -> Result<serde_json::Value, String> {
+    let output = execute_bd("status", &[], options.cwd.as_deref())?;
 
-    Ok(serde_json::json!({ "success": true }))
+    serde_json::from_str(&output)
+        .map_err(|e| format!("Failed to parse status: {}", e))
 }
 
 #[tauri::command]
-async fn bd_dep_remove(issue_id: String, blocker_id: String, options: CwdOptions) -> Result<serde_json::Value, String> {
-    let args = vec![issue_id, blocker_id];
-
-    execute_bd("dep remove", &args, options.cwd.as_deref())?;
+async fn bd_show(
+    id: String,
+    options: CwdOptions,
+    include_blocking_count: Option<bool>,
+    include_subtree: Option<bool>,
+    include_dangling_refs: Option<bool>,
+) -> Result<Issue, BackendError> {
+    log_info!("[bd_show] Called for issue: {} with cwd: {:?}", id, options.cwd);
 
-    Ok(serde_json::json!({ "success": true }))
+    // Relation labels and the opt-ins below share one project list
+    let project = LazyProjectList::new();
+    let mut issue = found_or_not_found(&id, show_issue_cached(&id, &options, &project)?)?;
+
+    // All cost a full list, so only on request (and one list serves them all)
+    let want_blocking = include_blocking_count.unwrap_or(false);
+    let want_subtree = include_subtree.unwrap_or(false);
+    let want_dangling = include_dangling_refs.unwrap_or(false) && (issue.blocked_by.is_some() || issue.blocks.is_some());
+    if want_blocking || want_subtree || want_dangling {
+        match project_list(&project, options.cwd.as_deref()) {
+            Ok(all) => {
+                if want_dangling {
+                    let existing: std::collections::HashSet<String> = all.iter().map(|i| i.id.clone()).collect();
+                    let dangling = find_dangling_refs(&issue, &existing);
+                    if !dangling.is_empty() {
+                        log_warn!("[bd_show] Issue {} has dangling refs: {:?}", id, dangling);
+                        issue.dangling_refs = Some(dangling);
+                    }
+                }
+                if want_blocking {
+                    issue.transitive_blocking_count = Some(transitive_blocking_count(&id, all));
+                }
+                if want_subtree {
+                    let subtree = build_subtree(&id, all);
+                    if !subtree.is_empty() {
+                        issue.children = Some(subtree);
+                    }
+                }
+            }
+            Err(e) => log_warn!("[bd_show] Could not load the project list: {}", e),
+        }
+    }
+    Ok(issue)
 }
 
-#[tauri::command]
-async fn bd_dep_add_relation(id1: String, id2: String, relation_type: String, options: CwdOptions) -> Result<serde_json::Value, String> {
-    let args = vec![id1, id2, "--type".to_string(), relation_type];
+/// Every issue in the project (`list --all`), loaded on first use by `project_list`.
+type LazyProjectList = std::cell::OnceCell<Result<Vec<Issue>, String>>;
 
-    execute_bd("dep add", &args, options.cwd.as_deref())?;
+fn project_list<'a>(project: &'a LazyProjectList, cwd: Option<&str>) -> Result<&'a [Issue], &'a str> {
+    project
+        .get_or_init(|| {
+            let options = ListOptions { include_all: Some(true), cwd: cwd.map(String::from), ..Default::default() };
+            fetch_list_issues(&options)
+        })
+        .as_deref()
+        .map_err(String::as_str)
+}
 
-    Ok(serde_json::json!({ "success": true }))
+fn found_or_not_found(id: &str, issue: Option<Issue>) -> Result<Issue, BackendError> {
+    issue.ok_or_else(|| BackendError::NotFound(format!("Issue not found: {}", id)))
 }
 
-#[tauri::command]
-async fn bd_dep_remove_relation(id1: String, id2: String, options: CwdOptions) -> Result<serde_json::Value, String> {
-    let args = vec![id1, id2];
+/// Children of `root` from a project-wide list, each carrying its own children.
+/// Nesting stops at TREE_MAX_DEPTH and an issue is never placed twice (parent cycles).
+fn build_subtree(root: &str, issues: &[Issue]) -> Vec<ChildIssue> {
+    let mut children_of: HashMap<&str, Vec<&Issue>> = HashMap::new();
+    for issue in issues {
+        if let Some(parent) = issue.parent.as_ref().filter(|p| !p.id.is_empty()) {
+            children_of.entry(parent.id.as_str()).or_default().push(issue);
+        }
+    }
 
-    execute_bd("dep remove", &args, options.cwd.as_deref())?;
+    fn walk<'a>(
+        id: &str,
+        depth: usize,
+        children_of: &HashMap<&str, Vec<&'a Issue>>,
+        placed: &mut std::collections::HashSet<&'a str>,
+    ) -> Vec<ChildIssue> {
+        let mut out = Vec::new();
+        for child in children_of.get(id).into_iter().flatten() {
+            if !placed.insert(child.id.as_str()) {
+                continue;
+            }
+            let grandchildren = if depth < TREE_MAX_DEPTH {
+                walk(&child.id, depth + 1, children_of, placed)
+            } else {
+                Vec::new()
+            };
+            out.push(ChildIssue {
+                id: child.id.clone(),
+                title: child.title.clone(),
+                status: child.status.clone(),
+                priority: child.priority.clone(),
+                children: if grandchildren.is_empty() { None } else { Some(grandchildren) },
+            });
+        }
+        out
+    }
 
-    Ok(serde_json::json!({ "success": true }))
+    let mut placed = std::collections::HashSet::from([root]);
+    walk(root, 1, &children_of, &mut placed)
 }
 
-#[tauri::command]
-async fn bd_available_relation_types() -> Vec<serde_json::Value> {
-    let common: Vec<(&str, &str)> = vec![
-        ("relates-to", "Relates To"),
-        ("related", "Related"),
-        ("discovered-from", "Discovered From"),
-        ("duplicates", "Duplicates"),
-        ("supersedes", "Supersedes"),
-        ("caused-by", "Caused By"),
-        ("replies-to", "Replies To"),
-    ];
-    let bd_only: Vec<(&str, &str)> = vec![
-        ("tracks", "Tracks"),
-        ("until", "Until"),
-        ("validates", "Validates"),
-    ];
+/// `show` plus relation labels, served from the show cache while the data is unchanged.
+/// Relation labels come from `project`, so the caller can reuse that list.
+fn show_issue_cached(id: &str, options: &CwdOptions, project: &LazyProjectList) -> Result<Option<Issue>, String> {
+    // Sync first (cooldown-limited) so a cache hit can't hide remote changes;
+    // a successful sync clears this project's cache
+    sync_bd_database(options.cwd.as_deref());
 
-    let types = match get_cli_client_info() {
-        Some((CliClient::Br, _, _, _)) => common,
-        _ => {
-            let mut all = common;
-            all.extend(bd_only);
-            all
+    let working_dir = resolve_working_dir(options.cwd.as_deref());
+    let mtime = get_beads_mtime(&std::path::Path::new(&working_dir).join(".beads"));
+    if let Some(mtime) = mtime {
+        if let Some(cached) = show_cache_get(&working_dir, id, mtime) {
+            log_info!("[bd_show] Cache hit for {}", id);
+            return Ok(Some(cached));
         }
+    }
+
+    let mut issue = match fetch_show_issue(id, options.cwd.as_deref())? {
+        Some(issue) => issue,
+        None => return Ok(None),
     };
+    let child_count = issue.children.as_ref().map_or(0, Vec::len);
+    set_child_count(&mut issue, child_count);
+
+    // Relations returned as bare ids need a project-wide list for their titles
+    if issue.relations.iter().flatten().any(|r| r.title.is_empty()) {
+        match project_list(project, options.cwd.as_deref()) {
+            Ok(all) => enrich_relations(&mut issue, all),
+            Err(e) => log_warn!("[bd_show] Could not load relation labels: {}", e),
+        }
+    }
 
-    types.into_iter().map(|(v, l)| serde_json::json!({ "value": v, "label": l })).collect()
+    if let Some(mtime) = mtime {
+        show_cache_put(&working_dir, &issue, mtime);
+    }
+    Ok(Some(issue))
 }
 
-#[tauri::command]
-async fn fs_exists(path: String) -> Result<bool, String> {
-    Ok(std::path::Path::new(&path).exists())
+fn set_child_count(issue: &mut Issue, count: usize) {
+    issue.child_count = Some(count);
+    issue.has_children = Some(count > 0);
 }
 
-#[tauri::command]
-async fn fs_list(path: Option<String>) -> Result<FsListResult, String> {
-    use std::fs;
+/// Fill in title/status/priority of relations the CLI returned as bare ids.
+fn enrich_relations(issue: &mut Issue, all: &[Issue]) {
+    let by_id: HashMap<&str, &Issue> = all.iter().map(|i| (i.id.as_str(), i)).collect();
+    for rel in issue.relations.iter_mut().flatten() {
+        if !rel.title.is_empty() {
+            continue;
+        }
+        if let Some(other) = by_id.get(rel.id.as_str()) {
+            rel.title = other.title.clone();
+            rel.status = other.status.clone();
+            rel.priority = other.priority.clone();
+        }
+    }
+}
 
-    let target_path = match path {
-        Some(p) if p == "~" => dirs::home_dir().unwrap_or_else(|| PathBuf::from("/")),
-        Some(p) => PathBuf::from(p),
-        None => dirs::home_dir().unwrap_or_else(|| PathBuf::from("/")),
-    };
+/// Number of distinct open issues downstream of `root` along "blocks" edges
+/// (what it blocks, what those block, ...). Closed issues are neither counted nor
+/// followed; the visited set makes cycles harmless.
+fn transitive_blocking_count(root: &str, issues: &[Issue]) -> usize {
+    BlockingGraph::new(issues).downstream_count(root)
+}
 
-    let target_path = target_path.canonicalize()
-        .map_err(|e| format!("Cannot resolve path: {}", e))?;
+/// "blocks" adjacency of a project, built once for any number of
+/// `downstream_count` lookups.
+struct BlockingGraph<'a> {
+    blocks: HashMap<&'a str, Vec<&'a str>>,
+    closed: std::collections::HashSet<&'a str>,
+}
 
-    let entries = fs::read_dir(&target_path)
-        .map_err(|e| format!("Cannot read directory: {}", e))?;
+impl<'a> BlockingGraph<'a> {
+    fn new(issues: &'a [Issue]) -> Self {
+        let closed = issues.iter()
+            .filter(|i| i.status == "closed")
+            .map(|i| i.id.as_str())
+            .collect();
+        let mut blocks: HashMap<&str, Vec<&str>> = HashMap::new();
+        for issue in issues {
+            for blocker in issue.blocked_by.iter().flatten() {
+                blocks.entry(blocker.as_str()).or_default().push(issue.id.as_str());
+            }
+            for blocked in issue.blocks.iter().flatten() {
+                blocks.entry(issue.id.as_str()).or_default().push(blocked.as_str());
+            }
+        }
+        Self { blocks, closed }
+    }
+
+    /// See `transitive_blocking_count`.
+    fn downstream_count(&self, root: &str) -> usize {
+        let mut visited: std::collections::HashSet<&str> = std::collections::HashSet::new();
+        visited.insert(root);
+        let mut stack = vec![root];
+        while let Some(id) = stack.pop() {
+            for &next in self.blocks.get(id).into_iter().flatten() {
+                if !self.closed.contains(next) && visited.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+        visited.len() - 1
+    }
+}
 
-    let mut directories: Vec<DirectoryEntry> = Vec::new();
+fn show_cache_get(working_dir: &str, id: &str, mtime: std::time::SystemTime) -> Option<Issue> {
+    let cache = SHOW_CACHE.lock().unwrap();
+    cache.as_ref()?
+        .get(working_dir)?
+        .get(id)
+        .filter(|(cached_mtime, _)| *cached_mtime == mtime)
+        .map(|(_, issue)| issue.clone())
+}
 
-    for entry in entries {
-        let entry = match entry {
-            Ok(e) => e,
-            Err(_) => continue,
-        };
+fn show_cache_put(working_dir: &str, issue: &Issue, mtime: std::time::SystemTime) {
+    let mut cache = SHOW_CACHE.lock().unwrap();
+    cache.get_or_insert_with(HashMap::new)
+        .entry(working_dir.to_string())
+        .or_default()
+        .insert(issue.id.clone(), (mtime, issue.clone()));
+}
 
-        let name = entry.file_name().to_string_lossy().to_string();
+fn show_cache_invalidate(working_dir: &str) {
+    if let Some(cache) = SHOW_CACHE.lock().unwrap().as_mut() {
+        cache.remove(working_dir);
+    }
+}
 
-        // Skip hidden files
-        if name.starts_with('.') {
-            continue;
+/// Run `bd show` for a single issue and transform it. Returns `Ok(None)` when not found.
+fn fetch_show_issue(id: &str, cwd: Option<&str>) -> Result<Option<Issue>, String> {
+    let id = id.to_string();
+    let output = match execute_bd("show", std::slice::from_ref(&id), cwd) {
+        Ok(output) => output,
+        Err(e) => {
+            // Handle "not found" errors gracefully (future bd versions may use non-zero exit)
+            let err_lower = e.to_lowercase();
+            if err_lower.contains("no issue found") || err_lower.contains("not found") {
+                log_info!("[bd_show] Issue {} not found (error from bd): {}", id, e);
+                return Ok(None);
+            }
+            return Err(e);
         }
+    };
 
-        let metadata = match entry.metadata() {
-            Ok(m) => m,
-            Err(_) => continue,
-        };
-
-        if metadata.is_dir() {
-            let full_path = entry.path();
-            let beads_path = full_path.join(".beads");
-            let has_beads = beads_path.is_dir();
-            let uses_dolt = has_beads && project_uses_dolt(&beads_path);
-
-            directories.push(DirectoryEntry {
-                name,
-                path: full_path.to_string_lossy().to_string(),
-                is_directory: true,
-                has_beads,
-                uses_dolt,
-            });
-        }
+    // Handle empty output (current bd behavior for missing issues: exit 0, empty stdout)
+    let trimmed = output.trim();
+    if trimmed.is_empty() {
+        log_info!("[bd_show] Issue {} not found (empty output from bd)", id);
+        return Ok(None);
     }
 
-    // Sort: beads projects first, then alphabetically
-    directories.sort_by(|a, b| {
-        match (a.has_beads, b.has_beads) {
-            (true, false) => std::cmp::Ordering::Less,
-            (false, true) => std::cmp::Ordering::Greater,
-            _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
-        }
-    });
+    // bd show can return either a single object or an array
+    let result: serde_json::Value = serde_json::from_str(trimmed)
+        .map_err(|e| {
+            log_error!("[bd_show] Failed to parse JSON for {}: {}", id, e);
+            format!("Failed to parse issue: {}", e)
+        })?;
 
-    let current_beads_path = target_path.join(".beads");
-    let current_has_beads = current_beads_path.is_dir();
-    let current_uses_dolt = current_has_beads && project_uses_dolt(&current_beads_path);
+    let raw_issue: Option<BdRawIssue> = if result.is_array() {
+        result.as_array()
+            .and_then(|arr| arr.first())
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+    } else {
+        serde_json::from_value(result).ok()
+    };
 
-    Ok(FsListResult {
-        current_path: target_path.to_string_lossy().to_string(),
-        has_beads: current_has_beads,
-        uses_dolt: current_uses_dolt,
-        entries: directories,
-    })
+    log_info!("[bd_show] Issue {} found: {}", id, raw_issue.is_some());
+    Ok(raw_issue.map(transform_issue))
 }
 
-// File watcher commands removed - replaced by frontend polling for lower CPU usage
-
-// ============================================================================
-// Update Checker
-// ============================================================================
+/// Collect the ids of every issue in the project, closed ones included.
+fn fetch_existing_ids(cwd: Option<&str>) -> Result<std::collections::HashSet<String>, String> {
+    let options = ListOptions {
+        include_all: Some(true),
+        cwd: cwd.map(String::from),
+        ..Default::default()
+    };
+    Ok(fetch_list_issues(&options)?.into_iter().map(|i| i.id).collect())
+}
 
-const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
-const GITHUB_RELEASES_URL: &str = "https://api.github.com/repos/w3dev33/beads-task-issue-tracker/releases/latest";
+/// Dependency ids referenced by `issue` that are not in `existing`.
+fn find_dangling_refs(issue: &Issue, existing: &std::collections::HashSet<String>) -> Vec<String> {
+    let mut dangling: Vec<String> = Vec::new();
+    let refs = issue.blocked_by.iter().flatten().chain(issue.blocks.iter().flatten());
+    for dep_id in refs {
+        if !existing.contains(dep_id) && !dangling.contains(dep_id) {
+            dangling.push(dep_id.clone());
+        }
+    }
+    dangling
+}
 
-/// Get a GitHub token from `gh auth token` (if gh CLI is installed and authenticated).
-/// Raises the API rate limit from 60/hour (anonymous) to 5,000/hour (authenticated).
-fn get_github_token() -> Option<String> {
-    // Check GITHUB_TOKEN env var first
-    if let Ok(token) = env::var("GITHUB_TOKEN") {
-        if !token.is_empty() {
-            return Some(token);
+/// `dep remove` argument pairs (issue, blocker) needed to drop the dangling refs of `issue`.
+fn plan_dep_prune(issue: &Issue, existing: &std::collections::HashSet<String>) -> Vec<(String, String)> {
+    let mut pairs = Vec::new();
+    for blocker in issue.blocked_by.iter().flatten() {
+        if !existing.contains(blocker) {
+            pairs.push((issue.id.clone(), blocker.clone()));
         }
     }
-    // Fall back to gh CLI
-    let output = new_command("gh")
-        .args(&["auth", "token"])
-        .output()
-        .ok()?;
-    if output.status.success() {
-        let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        if !token.is_empty() {
-            return Some(token);
+    for blocked in issue.blocks.iter().flatten() {
+        if !existing.contains(blocked) {
+            pairs.push((blocked.clone(), issue.id.clone()));
         }
     }
-    None
+    pairs
 }
 
-/// Build a reqwest client with GitHub auth if available.
-fn github_client() -> Result<reqwest::Client, String> {
-    reqwest::Client::builder()
-        .user_agent("beads-task-issue-tracker")
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+#[tauri::command]
+async fn bd_create(payload: CreatePayload) -> Result<Option<Issue>, String> {
+    create_issue(payload)
 }
 
-/// Add GitHub auth header to a request if a token is available.
-fn with_github_auth(req: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
-    match get_github_token() {
-        Some(token) => req.bearer_auth(token),
-        None => req,
-    }
+#[derive(Debug, Serialize)]
+pub struct CreateBatchError {
+    /// Position of the failed title in the request
+    pub index: usize,
+    pub title: String,
+    pub error: String,
 }
 
-fn get_platform_string() -> &'static str {
-    if cfg!(target_os = "macos") {
-        "macos"
-    } else if cfg!(target_os = "windows") {
-        "windows"
-    } else {
-        "linux"
-    }
+/// A batch title whose issue was created but couldn't be loaded back (`CREATE_PARTIAL`).
+#[derive(Debug, Serialize)]
+pub struct CreateBatchUnloaded {
+    /// Position of the title in the request
+    pub index: usize,
+    pub id: String,
+    pub title: String,
 }
 
-fn find_platform_asset(assets: &[GitHubAsset]) -> Option<&GitHubAsset> {
-    let suffix = if cfg!(target_os = "macos") {
-        if cfg!(target_arch = "aarch64") {
-            "_macOS-ARM64.dmg"
-        } else {
-            "_macOS-Intel.dmg"
+#[derive(Debug, Serialize)]
+pub struct CreateBatchResult {
+    /// Created issues, in request order
+    pub created: Vec<Issue>,
+    /// Also created, but only the id is known; refresh the list rather than retrying them
+    pub unloaded: Vec<CreateBatchUnloaded>,
+    pub errors: Vec<CreateBatchError>,
+}
+
+/// Create one issue per non-blank title with the shared `defaults` (type, priority,
+/// labels, parent...), holding the project lock for the whole batch. The CLI can't
+/// roll back, so a failure stops the batch and is reported with its index alongside
+/// the issues already created; with `continue_on_error` the remaining titles are tried.
+/// An issue that was created but couldn't be loaded back counts as created.
+#[tauri::command]
+async fn bd_create_batch(
+    titles: Vec<String>,
+    defaults: CreatePayload,
+    cwd: Option<String>,
+    continue_on_error: Option<bool>,
+) -> Result<CreateBatchResult, String> {
+    let payloads = batch_payloads(&titles, &defaults, cwd.as_deref());
+    let continue_on_error = continue_on_error.unwrap_or(false);
+    with_project_lock(cwd.as_deref(), || {
+        let mut result = CreateBatchResult { created: Vec::new(), unloaded: Vec::new(), errors: Vec::new() };
+        for (index, payload) in payloads {
+            let title = payload.title.clone();
+            let error = match create_issue(payload) {
+                Ok(Some(issue)) => {
+                    result.created.push(issue);
+                    continue;
+                }
+                Ok(None) => "Issue was created but could not be loaded".to_string(),
+                Err(e) => match create_partial_id(&e) {
+                    Some(id) => {
+                        log_warn!("[bd_create_batch] Title {} created as {} but not loaded: {}", index, id, e);
+                        result.unloaded.push(CreateBatchUnloaded { index, id: id.to_string(), title });
+                        continue;
+                    }
+                    None => e,
+                },
+            };
+            log_warn!("[bd_create_batch] Title {} ({:?}) failed: {}", index, title, error);
+            result.errors.push(CreateBatchError { index, title, error });
+            if !continue_on_error {
+                break;
+            }
         }
-    } else if cfg!(target_os = "windows") {
-        "_Windows.msi"
-    } else {
-        "_Linux-amd64.AppImage"
-    };
+        log_info!(
+            "[bd_create_batch] Created {} issues ({} not loaded), {} failed",
+            result.created.len() + result.unloaded.len(), result.unloaded.len(), result.errors.len()
+        );
+        Ok(result)
+    })
+}
 
-    assets.iter().find(|a| a.name.ends_with(suffix))
+/// One create payload per non-blank title (trimmed), keyed by its index in `titles`.
+fn batch_payloads(titles: &[String], defaults: &CreatePayload, cwd: Option<&str>) -> Vec<(usize, CreatePayload)> {
+    titles.iter().enumerate()
+        .filter(|(_, t)| !t.trim().is_empty())
+        .map(|(index, t)| {
+            let mut payload = defaults.clone();
+            payload.title = t.trim().to_string();
+            payload.cwd = cwd.map(str::to_string);
+            (index, payload)
+        })
+        .collect()
 }
 
-fn compare_versions(current: &str, latest: &str) -> bool {
-    // Remove 'v' prefix if present
-    let current = current.trim_start_matches('v');
-    let latest = latest.trim_start_matches('v');
+fn create_issue(mut payload: CreatePayload) -> Result<Option<Issue>, String> {
+    log_info!("[bd_create] Creating issue: {:?}", payload.title);
+    let project_config = load_project_config(payload.cwd.as_deref());
+    apply_project_defaults(&mut payload, &project_config);
+    if let Some(ref labels) = payload.labels {
+        payload.labels = Some(normalize_labels(labels, project_config.lowercase_labels));
+    }
+    let discovered_from = payload.discovered_from.as_deref().map(str::trim).filter(|s| !s.is_empty());
+    if let Some(source) = discovered_from {
+        if fetch_show_issue(source, payload.cwd.as_deref())?.is_none() {
+            return Err(format!("Source issue not found: {}", source));
+        }
+    }
+    let status = initial_status(payload.status.as_deref())?;
+    let has_due = payload.due.as_deref().is_some_and(|d| !d.trim().is_empty());
+    if has_due && !supports_due_flag(get_cli_client_info()) {
+        return Err("Due dates require bd 0.40 or newer".to_string());
+    }
+    let args = create_args(&payload)?;
 
-    let parse_version = |v: &str| -> Vec<u32> {
-        v.split('.')
-            .filter_map(|s| s.parse::<u32>().ok())
-            .collect()
-    };
+    let output = execute_bd("create", &args, payload.cwd.as_deref())?;
 
-    let current_parts = parse_version(current);
-    let latest_parts = parse_version(latest);
+    let mut created = match serde_json::from_str::<BdRawIssue>(&output) {
+        Ok(raw_issue) => Some(transform_issue(raw_issue)),
+        // The issue exists at this point — never make the UI re-submit and duplicate it
+        Err(e) => recover_created_issue(&output, &e.to_string(), |id| {
+            fetch_show_issue(id, payload.cwd.as_deref())
+        })?,
+    };
 
-    for i in 0..3 {
-        let c = current_parts.get(i).copied().unwrap_or(0);
-        let l = latest_parts.get(i).copied().unwrap_or(0);
-        if l > c {
-            return true;
+    if let (Some(issue), Some(status)) = (created.as_mut(), status) {
+        let update_args = vec![issue.id.clone(), "--status".to_string(), status.to_string()];
+        // The issue exists either way — report it as created, just still open
+        match execute_bd("update", &update_args, payload.cwd.as_deref()) {
+            Ok(_) => issue.status = status.to_string(),
+            Err(e) => log_warn!("[bd_create] Created {} but could not set status {}: {}", issue.id, status, e),
         }
-        if c > l {
-            return false;
+    }
+
+    // `create` echoes the issue without its dependencies — reload so the relation shows
+    match created {
+        Some(issue) if discovered_from.is_some() => {
+            match fetch_show_issue(&issue.id, payload.cwd.as_deref()) {
+                Ok(Some(full)) => Ok(Some(full)),
+                Ok(None) => Ok(Some(issue)),
+                Err(e) => {
+                    log_warn!("[bd_create] Could not reload {} with its relations: {}", issue.id, e);
+                    Ok(Some(issue))
+                }
+            }
         }
+        other => Ok(other),
     }
-    false
 }
 
-#[tauri::command]
-async fn check_for_updates() -> Result<UpdateInfo, String> {
-    let client = github_client()?;
+/// `create` arguments for a (defaults-applied, labels-normalized) payload.
+/// The status isn't passed here, see `initial_status`.
+fn create_args(payload: &CreatePayload) -> Result<Vec<String>, String> {
+    let mut args: Vec<String> = vec![payload.title.clone()];
 
-    let response = with_github_auth(client.get(GITHUB_RELEASES_URL))
-        .send()
-        .await
+    if let Some(ref desc) = payload.description {
+        args.push("--description".to_string());
+        args.push(desc.clone());
+    }
+    if let Some(ref t) = payload.issue_type {
+        args.push("--type".to_string());
+        args.push(t.clone());
+    }
+    if let Some(ref p) = payload.priority {
+        args.push("--priority".to_string());
+        args.push(priority_to_number(p));
+    }
+    if let Some(ref a) = payload.assignee {
+        args.push("--assignee".to_string());
+        args.push(a.clone());
+    }
+    if let Some(ref labels) = payload.labels {
+        if !labels.is_empty() {
+            args.push("--labels".to_string());
+            args.push(labels.join(","));
+        }
+    }
+    if let Some(ref ext) = payload.external_ref {
+        args.push("--external-ref".to_string());
+        args.push(ext.clone());
+    }
+    if let Some(est) = payload.estimate_minutes {
+        args.push("--estimate".to_string());
+        args.push(est.to_string());
+    }
+    if let Some(ref design) = payload.design_notes {
+        args.push("--design".to_string());
+        args.push(design.clone());
+    }
+    if let Some(ref acc) = payload.acceptance_criteria {
+        args.push("--acceptance".to_string());
+        args.push(acc.clone());
+    }
+    if let Some(ref notes) = payload.working_notes {
+        args.push("--notes".to_string());
+        args.push(notes.clone());
+    }
+    if let Some(ref parent) = payload.parent {
+        if !parent.is_empty() {
+            args.push("--parent".to_string());
+            args.push(parent.clone());
+        }
+    }
+    if let Some(ref spec_id) = payload.spec_id {
+        if !spec_id.is_empty() {
+            args.push("--spec-id".to_string());
+            args.push(spec_id.clone());
+        }
+    }
+    if let Some(ref due) = payload.due {
+        if !due.trim().is_empty() {
+            args.push("--due".to_string());
+            args.push(resolve_due_date(due)?);
+        }
+    }
+    // Linked by the create itself, so there is never an unlinked follow-up
+    if let Some(source) = payload.discovered_from.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+        args.push("--deps".to_string());
+        args.push(format!("discovered-from:{}", source));
+    }
+    Ok(args)
+}
+
+/// Recover from unparseable `create` output: the issue was created, so find its id
+/// and re-fetch it. If that fails too, the error carries the id
+/// (`CREATE_PARTIAL:<id>: ...`) so the UI can open it instead of creating a duplicate.
+fn recover_created_issue(
+    output: &str,
+    parse_error: &str,
+    fetch: impl FnOnce(&str) -> Result<Option<Issue>, String>,
+) -> Result<Option<Issue>, String> {
+    log_warn!("[bd_create] Failed to parse created issue: {}", parse_error);
+    let id = extract_created_id(output)
+        .ok_or_else(|| format!("Failed to parse created issue: {}", parse_error))?;
+
+    match fetch(&id) {
+        Ok(Some(issue)) => {
+            log_info!("[bd_create] Recovered created issue {} via show", id);
+            Ok(Some(issue))
+        }
+        Ok(None) => Err(format!("CREATE_PARTIAL:{}: Issue was created but could not be loaded", id)),
+        Err(e) => Err(format!("CREATE_PARTIAL:{}: Issue was created but could not be loaded: {}", id, e)),
+    }
+}
+
+/// The id in a `CREATE_PARTIAL:<id>: ...` error from `recover_created_issue`.
+fn create_partial_id(error: &str) -> Option<&str> {
+    error.strip_prefix("CREATE_PARTIAL:")?.split_once(':').map(|(id, _)| id)
+}
+
+/// Best-effort id extraction from `create` output: a JSON object/array with an
+/// `id` field, or the text form "Created issue: <id>".
+fn extract_created_id(output: &str) -> Option<String> {
+    if let Ok(value) = serde_json::from_str::<serde_json::Value>(output.trim()) {
+        let obj = if value.is_array() { value.get(0).cloned()? } else { value };
+        if let Some(id) = obj.get("id").and_then(|v| v.as_str()) {
+            return Some(id.to_string());
+        }
+    }
+    output.lines()
+        .find_map(|line| line.split_once("Created issue:").map(|(_, rest)| rest))
+        .and_then(|rest| rest.split_whitespace().next())
+        .map(|id| id.trim_end_matches([':', ',']).to_string())
+        .filter(|id| !id.is_empty())
+}
+
+#[tauri::command]
+async fn bd_update(id: String, updates: UpdatePayload) -> Result<Option<Issue>, BackendError> {
+    // Snapshot for the changed-field diff; an update must not fail just because this did
+    let before = match fetch_show_issue(&id, updates.cwd.as_deref()) {
+        Ok(None) => return Err(BackendError::NotFound(format!("Issue not found: {}", id))),
+        Ok(issue) => issue,
+        Err(e) => {
+            log::warn!("[bd_update] Could not load {} before update: {}", id, e);
+            None
+        }
+    };
+
+    let mut updated = run_issue_update(id, &updates, before.as_ref())?;
+    if let (Some(before), Some(after)) = (before.as_ref(), updated.as_mut()) {
+        after.changed_fields = Some(changed_issue_fields(before, after));
+    }
+    Ok(updated)
+}
+
+/// Types that are used as containers for child issues.
+const PARENT_ISSUE_TYPES: [&str; 2] = ["epic", "feature"];
+
+/// Refuse to turn an issue that has children into a non-parent type (e.g. epic → bug),
+/// which would leave its children under a leaf issue. Promoting to a parent type is free.
+fn check_type_change(before: &Issue, children: &[ChildIssue], new_type: Option<&str>) -> Result<(), String> {
+    let Some(new_type) = new_type.filter(|t| *t != before.issue_type) else { return Ok(()) };
+    if children.is_empty() || PARENT_ISSUE_TYPES.contains(&new_type) {
+        return Ok(());
+    }
+    Err(format!(
+        "{} has {} child issue(s) and a {} can't have children. Detach them first or pick {}.",
+        before.id, children.len(), new_type, PARENT_ISSUE_TYPES.join(" or ")
+    ))
+}
+
+/// Names (as serialized) of the editable fields that differ between two versions of an issue.
+fn changed_issue_fields(before: &Issue, after: &Issue) -> Vec<String> {
+    let parent_id = |issue: &Issue| issue.parent.as_ref().map(|p| p.id.clone());
+    let checks = [
+        ("title", before.title != after.title),
+        ("description", before.description != after.description),
+        ("type", before.issue_type != after.issue_type),
+        ("status", before.status != after.status),
+        ("priority", before.priority != after.priority),
+        ("assignee", before.assignee != after.assignee),
+        ("labels", before.labels != after.labels),
+        ("externalRef", before.external_ref != after.external_ref),
+        ("estimateMinutes", before.estimate_minutes != after.estimate_minutes),
+        ("designNotes", before.design_notes != after.design_notes),
+        ("acceptanceCriteria", before.acceptance_criteria != after.acceptance_criteria),
+        ("workingNotes", before.working_notes != after.working_notes),
+        ("metadata", before.metadata != after.metadata),
+        ("specId", before.spec_id != after.spec_id),
+        ("parent", parent_id(before) != parent_id(after)),
+    ];
+    checks.iter().filter(|(_, changed)| *changed).map(|(name, _)| name.to_string()).collect()
+}
+
+/// Shared update path. `current` is the issue as already loaded by the caller, if any;
+/// a type change needs it for the children guard and fails when it can't be loaded.
+fn run_issue_update(id: String, updates: &UpdatePayload, current: Option<&Issue>) -> Result<Option<Issue>, String> {
+    // Always log update calls for debugging (regardless of LOGGING_ENABLED)
+    log::info!("[bd_update] Updating issue: {} with cwd: {:?}", id, updates.cwd);
+    log::info!("[bd_update] Updates: status={:?}, title={:?}, type={:?}", updates.status, updates.title, updates.issue_type);
+
+    // Only a change to a non-parent type can strand children
+    if let Some(new_type) = updates.issue_type.as_deref().filter(|t| !PARENT_ISSUE_TYPES.contains(t)) {
+        let loaded;
+        let current = match current {
+            Some(current) => current,
+            None => {
+                loaded = fetch_show_issue(&id, updates.cwd.as_deref())
+                    .map_err(|e| format!("Could not load {} to check the type change: {}", id, e))?
+                    .ok_or_else(|| format!("Issue not found: {}", id))?;
+                &loaded
+            }
+        };
+        if current.issue_type != new_type {
+            // `show` has no children on bd >= 0.50, so they come from the project list
+            let list_options = ListOptions { include_all: Some(true), cwd: updates.cwd.clone(), ..Default::default() };
+            let all = fetch_list_issues(&list_options)
+                .map_err(|e| format!("Could not load the children of {} to check the type change: {}", id, e))?;
+            check_type_change(current, &children_in_list(&id, &all), Some(new_type))?;
+        }
+    }
+
+    let mut args: Vec<String> = vec![id.clone()];
+
+    if let Some(ref title) = updates.title {
+        args.push("--title".to_string());
+        args.push(title.clone());
+    }
+    if let Some(ref desc) = updates.description {
+        args.push("--description".to_string());
+        args.push(desc.clone());
+    }
+    if let Some(ref t) = updates.issue_type {
+        args.push("--type".to_string());
+        args.push(t.clone());
+    }
+    if let Some(ref s) = updates.status {
+        args.push("--status".to_string());
+        args.push(s.clone());
+    }
+    if let Some(ref p) = updates.priority {
+        args.push("--priority".to_string());
+        args.push(priority_to_number(p));
+    }
+    if let Some(ref a) = updates.assignee {
+        args.push("--assignee".to_string());
+        args.push(a.clone());
+    }
+    if let Some(ref labels) = updates.labels {
+        let lowercase = project_lowercase_labels(updates.cwd.as_deref());
+        args.extend(set_labels_args(labels, lowercase));
+    }
+    if let Some(ref ext) = updates.external_ref {
+        args.push("--external-ref".to_string());
+        args.push(ext.clone());
+    }
+    if let Some(est) = updates.estimate_minutes {
+        args.push("--estimate".to_string());
+        args.push(est.to_string());
+    }
+    if let Some(ref design) = updates.design_notes {
+        args.push("--design".to_string());
+        args.push(design.clone());
+    }
+    if let Some(ref acc) = updates.acceptance_criteria {
+        args.push("--acceptance".to_string());
+        args.push(acc.clone());
+    }
+    if let Some(ref notes) = updates.working_notes {
+        args.push("--notes".to_string());
+        args.push(notes.clone());
+    }
+    if let Some(ref metadata) = updates.metadata {
+        args.push("--metadata".to_string());
+        args.push(metadata.clone());
+    }
+    if let Some(ref spec_id) = updates.spec_id {
+        args.push("--spec-id".to_string());
+        args.push(spec_id.clone());
+    }
+    if let Some(ref parent) = updates.parent {
+        args.push("--parent".to_string());
+        args.push(parent.clone());
+    }
+    if let Some(ref due) = updates.due {
+        if !supports_due_flag(get_cli_client_info()) {
+            return Err("Due dates require bd 0.40 or newer".to_string());
+        }
+        // Empty clears the due date
+        let due = if due.trim().is_empty() { String::new() } else { resolve_due_date(due)? };
+        args.push("--due".to_string());
+        args.push(due);
+    }
+
+    log::info!("[bd_update] Executing: bd update {}", args.join(" "));
+    let output = execute_bd("update", &args, updates.cwd.as_deref())?;
+
+    log::info!("[bd_update] Raw output: {}", output.chars().take(500).collect::<String>());
+
+    // Handle empty output from bd CLI (some updates return empty response)
+    let trimmed_output = output.trim();
+    if trimmed_output.is_empty() {
+        log::info!("[bd_update] Empty response from bd, fetching issue {} to get updated data", id);
+        // Fetch the updated issue directly
+        let show_output = execute_bd("show", std::slice::from_ref(&id), updates.cwd.as_deref())?;
+        let show_result: serde_json::Value = serde_json::from_str(&show_output)
+            .map_err(|e| {
+                log::error!("[bd_update] Failed to parse show JSON: {}", e);
+                format!("Failed to fetch updated issue: {}", e)
+            })?;
+
+        let raw_issue: Option<BdRawIssue> = if show_result.is_array() {
+            show_result.as_array()
+                .and_then(|arr| arr.first())
+                .and_then(|v| serde_json::from_value(v.clone()).ok())
+        } else {
+            serde_json::from_value(show_result).ok()
+        };
+
+        return Ok(raw_issue.map(transform_issue));
+    }
+
+    // bd update can return either a single object or an array
+    let result: serde_json::Value = serde_json::from_str(trimmed_output)
+        .map_err(|e| {
+            log::error!("[bd_update] Failed to parse JSON: {}", e);
+            format!("Failed to parse updated issue: {}", e)
+        })?;
+
+    let raw_issue: Option<BdRawIssue> = if result.is_array() {
+        log::info!("[bd_update] Result is array");
+        result.as_array()
+            .and_then(|arr| arr.first())
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+    } else {
+        log::info!("[bd_update] Result is object");
+        serde_json::from_value(result.clone()).map_err(|e| {
+            log::error!("[bd_update] Failed to parse issue from result: {}", e);
+            e
+        }).ok()
+    };
+
+    if let Some(ref issue) = raw_issue {
+        log::info!("[bd_update] Updated issue {} - new status: {}", id, issue.status);
+    } else {
+        log::warn!("[bd_update] Could not parse updated issue from response");
+    }
+
+    Ok(raw_issue.map(transform_issue))
+}
+
+/// Apply the same update to several issues under one hold of the project lock.
+/// Stops at the first failure unless `continue_on_error` (default true).
+#[tauri::command]
+async fn bd_bulk_update(
+    ids: Vec<String>,
+    updates: UpdatePayload,
+    continue_on_error: Option<bool>,
+) -> Result<Vec<BulkItemResult>, String> {
+    log_info!("[bd_bulk_update] Updating {} issues", ids.len());
+    let cwd = updates.cwd.clone();
+    let results = with_project_lock(cwd.as_deref(), || {
+        bulk_update_with(&ids, continue_on_error.unwrap_or(true), |id| {
+            run_issue_update(id.to_string(), &updates, None).map(|_| ())
+        })
+    });
+
+    let failed = results.iter().filter(|r| !r.success).count();
+    log_info!("[bd_bulk_update] Done: {} updated, {} failed", results.len() - failed, failed);
+    Ok(results)
+}
+
+/// Results for the ids attempted; after a failure without `continue_on_error`,
+/// the remaining ids are not attempted and not listed.
+fn bulk_update_with(
+    ids: &[String],
+    continue_on_error: bool,
+    mut update: impl FnMut(&str) -> Result<(), String>,
+) -> Vec<BulkItemResult> {
+    let mut results = Vec::new();
+    for id in ids {
+        let result = update(id);
+        let failed = result.is_err();
+        results.push(BulkItemResult { id: id.clone(), success: !failed, error: result.err() });
+        if failed && !continue_on_error {
+            break;
+        }
+    }
+    results
+}
+
+/// Reopen a closed issue, returning the updated issue. Uses the CLI's `reopen`,
+/// which also clears the stored close date and reason; CLIs without it get
+/// `update --status open`.
+#[tauri::command]
+async fn bd_reopen(id: String, options: CwdOptions) -> Result<Option<Issue>, String> {
+    log_info!("[bd_reopen] Reopening issue: {} with cwd: {:?}", id, options.cwd);
+    let cwd = options.cwd.as_deref();
+    match execute_bd("reopen", std::slice::from_ref(&id), cwd) {
+        Ok(_) => fetch_show_issue(&id, cwd),
+        Err(e) if is_unknown_command(&e) => {
+            log_info!("[bd_reopen] No reopen command, falling back to update --status open");
+            let updates = UpdatePayload {
+                status: Some("open".to_string()),
+                cwd: options.cwd.clone(),
+                ..Default::default()
+            };
+            run_issue_update(id, &updates, None)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Whether a CLI error means the subcommand doesn't exist in this version
+/// (cobra's "unknown command" for bd, clap's "unrecognized subcommand" for br).
+fn is_unknown_command(error: &str) -> bool {
+    let lower = error.to_lowercase();
+    lower.contains("unknown command") || lower.contains("unrecognized subcommand")
+}
+
+#[tauri::command]
+async fn bd_close(id: String, options: CwdOptions) -> Result<serde_json::Value, String> {
+    log_info!("[bd_close] Closing issue: {} with cwd: {:?}", id, options.cwd);
+
+    let mut args = vec![id.clone()];
+    // br supports --suggest-next for showing newly unblocked issues
+    if matches!(get_cli_client_info(), Some((CliClient::Br, _, _, _))) {
+        args.push("--suggest-next".to_string());
+    }
+
+    let output = execute_bd("close", &args, options.cwd.as_deref())?;
+
+    log_info!("[bd_close] Raw output: {}", output.chars().take(500).collect::<String>());
+
+    let result: serde_json::Value = serde_json::from_str(&output)
+        .map_err(|e| {
+            log_error!("[bd_close] Failed to parse JSON: {}", e);
+            format!("Failed to parse close result: {}", e)
+        })?;
+
+    log_info!("[bd_close] Issue {} closed successfully", id);
+    Ok(result)
+}
+
+#[tauri::command]
+async fn bd_search(query: String, options: CwdOptions) -> Result<Vec<Issue>, String> {
+    log_info!("[bd_search] Searching for: {} with cwd: {:?}", query, options.cwd);
+
+    let query = match normalize_search_query(&query) {
+        Some(q) => q,
+        None => return Ok(vec![]),
+    };
+    record_recent_search(options.cwd.as_deref(), &query);
+    let args = vec![query];
+    let output = execute_bd("search", &args, options.cwd.as_deref())?;
+
+    log_info!("[bd_search] Raw output: {}", output.chars().take(500).collect::<String>());
+
+    let trimmed = output.trim();
+    if trimmed.is_empty() || trimmed == "[]" {
+        return Ok(vec![]);
+    }
+
+    let raw: Vec<BdRawIssue> = serde_json::from_str(trimmed)
+        .map_err(|e| {
+            log_error!("[bd_search] Failed to parse JSON: {}", e);
+            format!("Failed to parse search results: {}", e)
+        })?;
+
+    Ok(raw.into_iter().map(transform_issue).collect())
+}
+
+/// Trim a search query; `None` for blank input so callers return no results
+/// instead of running the CLI with an empty term. The query goes to the CLI as a
+/// single argv entry, so quotes and operators need no shell escaping.
+fn normalize_search_query(query: &str) -> Option<String> {
+    let trimmed = query.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+/// Recent searches kept per project
+const RECENT_SEARCHES_MAX: usize = 20;
+
+fn record_recent_search(cwd: Option<&str>, query: &str) {
+    let key = resolve_working_dir(cwd);
+    let saved = update_config(|config| {
+        push_recent_search(config.recent_searches.entry(key).or_default(), query, RECENT_SEARCHES_MAX);
+    });
+    if let Err(e) = saved {
+        log_warn!("[bd_search] Could not save recent searches: {}", e);
+    }
+}
+
+/// Move `query` to the front (dropping an earlier copy) and cap the list at `max`.
+fn push_recent_search(recent: &mut Vec<String>, query: &str, max: usize) {
+    recent.retain(|q| q != query);
+    recent.insert(0, query.to_string());
+    recent.truncate(max);
+}
+
+#[tauri::command]
+async fn get_recent_searches(cwd: Option<String>) -> Vec<String> {
+    load_config().recent_searches.remove(&resolve_working_dir(cwd.as_deref())).unwrap_or_default()
+}
+
+#[tauri::command]
+async fn clear_recent_searches(cwd: Option<String>) -> Result<(), String> {
+    let key = resolve_working_dir(cwd.as_deref());
+    if load_config().recent_searches.contains_key(&key) {
+        update_config(|config| config.recent_searches.remove(&key))?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+async fn bd_label_add(id: String, label: String, options: CwdOptions) -> Result<(), String> {
+    log_info!("[bd_label_add] Adding label '{}' to issue {}", label, id);
+    let lowercase = project_lowercase_labels(options.cwd.as_deref());
+    let label = normalize_labels(&[label], lowercase)
+        .pop()
+        .ok_or_else(|| "Label cannot be empty".to_string())?;
+    let args = vec![id, label];
+    execute_bd("label add", &args, options.cwd.as_deref())?;
+    Ok(())
+}
+
+#[tauri::command]
+async fn bd_label_remove(id: String, label: String, options: CwdOptions) -> Result<(), String> {
+    log_info!("[bd_label_remove] Removing label '{}' from issue {}", label, id);
+    let args = vec![id, label];
+    execute_bd("label remove", &args, options.cwd.as_deref())?;
+    Ok(())
+}
+
+/// `update` args replacing the whole label set (empty clears every label).
+fn set_labels_args(labels: &[String], lowercase: bool) -> Vec<String> {
+    vec!["--set-labels".to_string(), normalize_labels(labels, lowercase).join(",")]
+}
+
+/// Replace an issue's labels with exactly `labels` (normalized) in one update.
+/// Returns the labels the issue ends up with.
+#[tauri::command]
+async fn bd_set_labels(id: String, labels: Vec<String>, options: CwdOptions) -> Result<Vec<String>, String> {
+    let lowercase = project_lowercase_labels(options.cwd.as_deref());
+    let labels = normalize_labels(&labels, lowercase);
+    log_info!("[bd_set_labels] Setting labels of {} to {:?}", id, labels);
+    let updates = UpdatePayload { labels: Some(labels.clone()), cwd: options.cwd, ..Default::default() };
+    let updated = run_issue_update(id, &updates, None)?;
+    Ok(updated.map_or(labels, |issue| issue.labels))
+}
+
+/// Delete an issue. One with open children is refused unless `force` (delete it and
+/// leave the children parentless) or `cascade` (delete its whole subtree) is set.
+#[tauri::command]
+async fn bd_delete(
+    id: String,
+    options: CwdOptions,
+    force: Option<bool>,
+    cascade: Option<bool>,
+) -> Result<serde_json::Value, BackendError> {
+    let cwd = options.cwd.as_deref();
+    // `show` has no children on bd >= 0.50, so they come from one project-wide list
+    let all = fetch_list_issues(&ListOptions { include_all: Some(true), cwd: options.cwd.clone(), ..Default::default() })?;
+    let deleted = delete_with_children(
+        &id,
+        force.unwrap_or(false),
+        cascade.unwrap_or(false),
+        |issue_id| Ok(children_in_list(issue_id, &all)),
+        |issue_id| delete_single_issue(issue_id, cwd),
+    )?;
+
+    Ok(serde_json::json!({ "success": true, "id": id, "deleted": deleted }))
+}
+
+fn delete_single_issue(id: &str, cwd: Option<&str>) -> Result<(), String> {
+    let mut args = vec![id.to_string(), "--force".to_string()];
+    if supports_delete_hard_flag() {
+        args.push("--hard".to_string());
+    }
+    log::info!("[bd_delete] Deleting issue: {} with args: {:?}", id, args);
+
+    delete_then_cleanup_attachments(
+        id,
+        cwd,
+        || {
+            execute_bd("delete", &args, cwd)?;
+            // Sync after delete to push deletion to remote and prevent resurrection
+            sync_bd_database(cwd);
+            Ok(())
+        },
+        || fetch_show_issue(id, cwd).map(|issue| issue.is_some()),
+    )
+}
+
+/// Direct children of `parent_id` in a project-wide list: issues naming it as their
+/// parent or, as bd >= 0.50 leaves `parent` out, with a dotted `<parent_id>.<n>` id.
+fn children_in_list(parent_id: &str, all: &[Issue]) -> Vec<ChildIssue> {
+    all.iter()
+        .filter(|issue| match issue.parent.as_ref().filter(|p| !p.id.is_empty()) {
+            Some(parent) => parent.id == parent_id,
+            None => issue.id.strip_prefix(parent_id)
+                .and_then(|rest| rest.strip_prefix('.'))
+                .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit())),
+        })
+        .map(|issue| ChildIssue {
+            id: issue.id.clone(),
+            title: issue.title.clone(),
+            status: issue.status.clone(),
+            priority: issue.priority.clone(),
+            children: None,
+        })
+        .collect()
+}
+
+/// Apply the open-children guard, then delete. With `cascade`, descendants are
+/// deleted first (deepest first) so no issue is ever left pointing at a missing
+/// parent. Returns the deleted ids in deletion order.
+fn delete_with_children(
+    id: &str,
+    force: bool,
+    cascade: bool,
+    mut children_of: impl FnMut(&str) -> Result<Vec<ChildIssue>, String>,
+    mut delete: impl FnMut(&str) -> Result<(), String>,
+) -> Result<Vec<String>, String> {
+    let children = children_of(id)?;
+    let open: Vec<&str> = children.iter()
+        .filter(|c| c.status != "closed")
+        .map(|c| c.id.as_str())
+        .collect();
+    if !open.is_empty() && !force && !cascade {
+        return Err(format!(
+            "HAS_OPEN_CHILDREN:{}: {} has open children ({}). Use force to delete it anyway or cascade to delete them too.",
+            open.join(","), id, open.join(", ")
+        ));
+    }
+
+    let mut order: Vec<String> = Vec::new();
+    if cascade {
+        // Post-order walk; `seen` guards against parent cycles
+        let mut seen: std::collections::HashSet<String> = std::collections::HashSet::new();
+        seen.insert(id.to_string());
+        let mut stack: Vec<(String, bool)> = children.into_iter().rev().map(|c| (c.id, false)).collect();
+        while let Some((child, expanded)) = stack.pop() {
+            if expanded {
+                order.push(child);
+                continue;
+            }
+            if !seen.insert(child.clone()) {
+                continue;
+            }
+            let grandchildren = children_of(&child)?;
+            stack.push((child, true));
+            stack.extend(grandchildren.into_iter().rev().map(|c| (c.id, false)));
+        }
+    }
+    order.push(id.to_string());
+
+    for issue_id in &order {
+        delete(issue_id)?;
+    }
+    if order.len() > 1 {
+        log_info!("[bd_delete] Cascade-deleted {} with {} descendants", id, order.len() - 1);
+    }
+    Ok(order)
+}
+
+/// Run `delete`, then remove the issue's attachments folder only once the issue is
+/// confirmed gone. A failed delete, a soft delete (issue still visible) or a failed
+/// re-check all leave the attachments in place so nothing gets orphaned.
+fn delete_then_cleanup_attachments(
+    id: &str,
+    cwd: Option<&str>,
+    delete: impl FnOnce() -> Result<(), String>,
+    still_exists: impl FnOnce() -> Result<bool, String>,
+) -> Result<(), String> {
+    delete()?;
+
+    match still_exists() {
+        Ok(false) => {}
+        Ok(true) => {
+            log::info!("[bd_delete] {} still present after delete (soft delete), keeping attachments", id);
+            return Ok(());
+        }
+        Err(e) => {
+            log::warn!("[bd_delete] Could not confirm deletion of {}, keeping attachments: {}", id, e);
+            return Ok(());
+        }
+    }
+
+    remove_issue_attachments(id, cwd);
+    Ok(())
+}
+
+/// Delete the `.beads/attachments/<short-id>` folder of an issue, if any.
+fn remove_issue_attachments(id: &str, cwd: Option<&str>) {
+    let project_path = cwd.unwrap_or(".");
+    let abs_project_path = if project_path == "." || project_path.is_empty() {
+        env::current_dir().ok()
+    } else {
+        let p = PathBuf::from(project_path);
+        if p.is_relative() {
+            env::current_dir().ok().map(|cwd| cwd.join(&p))
+        } else {
+            Some(p)
+        }
+    };
+
+    if let Some(path) = abs_project_path {
+        if let Ok(abs_path) = path.canonicalize() {
+            let att_dir = abs_path.join(".beads").join("attachments").join(issue_short_id(id));
+            if att_dir.exists() && att_dir.is_dir() {
+                if let Err(e) = fs::remove_dir_all(&att_dir) {
+                    log::warn!("[bd_delete] Failed to remove attachments folder: {}", e);
+                } else {
+                    log::info!("[bd_delete] Removed attachments folder: {:?}", att_dir);
+                }
+            }
+        }
+    }
+}
+
+/// Per-item outcome of a bulk operation
+#[derive(Debug, Serialize, PartialEq)]
+pub struct BulkItemResult {
+    pub id: String,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Delete several issues under one sync. Hard deletes remove each issue's attachments
+/// once it's confirmed gone; soft deletes (tombstones) keep them.
+#[tauri::command]
+async fn bd_bulk_delete(ids: Vec<String>, hard: bool, options: CwdOptions) -> Result<Vec<BulkItemResult>, String> {
+    log_info!("[bd_bulk_delete] Deleting {} issues (hard={})", ids.len(), hard);
+    let cwd = options.cwd.as_deref();
+
+    // One hold of the project lock for all deletes and the sync
+    let results = with_project_lock(cwd, || {
+        let results = bulk_delete_with(
+            &ids,
+            hard,
+            cwd,
+            |id| {
+                let mut args = vec![id.to_string(), "--force".to_string()];
+                if hard && supports_delete_hard_flag() {
+                    args.push("--hard".to_string());
+                }
+                execute_bd("delete", &args, cwd).map(|_| ())
+            },
+            |id| fetch_show_issue(id, cwd).map(|issue| issue.is_some()),
+        );
+
+        // Single sync for the whole batch
+        sync_bd_database(cwd);
+        results
+    });
+
+    let failed = results.iter().filter(|r| !r.success).count();
+    log_info!("[bd_bulk_delete] Done: {} deleted, {} failed", results.len() - failed, failed);
+    Ok(results)
+}
+
+fn bulk_delete_with(
+    ids: &[String],
+    hard: bool,
+    cwd: Option<&str>,
+    mut delete: impl FnMut(&str) -> Result<(), String>,
+    mut still_exists: impl FnMut(&str) -> Result<bool, String>,
+) -> Vec<BulkItemResult> {
+    ids.iter()
+        .map(|id| {
+            let result = if hard {
+                delete_then_cleanup_attachments(id, cwd, || delete(id), || still_exists(id))
+            } else {
+                delete(id)
+            };
+            BulkItemResult { id: id.clone(), success: result.is_ok(), error: result.err() }
+        })
+        .collect()
+}
+
+#[tauri::command]
+async fn bd_comments_add(id: String, content: String, options: CwdOptions) -> Result<serde_json::Value, String> {
+    let args = vec![id, content];
+
+    execute_bd("comments add", &args, options.cwd.as_deref())?;
+
+    Ok(serde_json::json!({ "success": true }))
+}
+
+#[tauri::command]
+async fn bd_comment_delete(issue_id: String, comment_id: String, cwd: Option<String>) -> Result<serde_json::Value, String> {
+    if !supports_comment_delete(get_cli_client_info()) {
+        return Err("Deleting comments requires bd 0.56 or newer".to_string());
+    }
+    log_info!("[bd_comment_delete] Deleting comment {} from issue {}", comment_id, issue_id);
+    let args = vec![issue_id, comment_id];
+
+    execute_bd("comments delete", &args, cwd.as_deref())?;
+
+    Ok(serde_json::json!({ "success": true }))
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommentWithAttachment {
+    /// The created comment, when the CLI echoes it back
+    pub comment: Option<Comment>,
+    pub filename: String,
+}
+
+/// Copy a file into the issue's attachments and add a comment that embeds it.
+/// If the comment can't be added, a freshly copied file is removed again.
+#[tauri::command]
+async fn bd_comment_with_attachment(
+    issue_id: String,
+    content: String,
+    source_path: String,
+    options: CwdOptions,
+) -> Result<CommentWithAttachment, String> {
+    let project_path = resolve_working_dir(options.cwd.as_deref());
+    let (dest_dir, copied) = attach_file(&project_path, &source_path, &issue_id)?;
+
+    let body = comment_with_image(&content, &copied.filename);
+    let output = match execute_bd("comments add", &[issue_id.clone(), body], options.cwd.as_deref()) {
+        Ok(output) => output,
+        Err(e) => {
+            if !copied.deduplicated {
+                fs::remove_file(dest_dir.join(&copied.filename)).ok();
+            }
+            return Err(e);
+        }
+    };
+    log_info!("[bd_comment_with_attachment] Commented on {} with {}", issue_id, copied.filename);
+
+    let comment = serde_json::from_str::<BdRawComment>(output.trim()).ok().map(transform_comment);
+    Ok(CommentWithAttachment { comment, filename: copied.filename })
+}
+
+/// Comment body with a markdown image reference to an attachment appended.
+fn comment_with_image(content: &str, filename: &str) -> String {
+    let image = format!("![{}]({})", filename, filename);
+    if content.trim().is_empty() {
+        image
+    } else {
+        format!("{}\n\n{}", content.trim_end(), image)
+    }
+}
+
+/// `blocker_id` blocks `issue_id` (`issue_id` depends on `blocker_id`).
+#[tauri::command]
+async fn bd_dep_add(issue_id: String, blocker_id: String, options: CwdOptions) -> Result<serde_json::Value, String> {
+    add_blocking_dep(&issue_id, &blocker_id, options.cwd.as_deref())
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct BlockerResult {
+    #[serde(rename = "blockerId")]
+    pub blocker_id: String,
+    /// False with no error for skipped duplicates (already a blocker, or repeated in the batch)
+    pub added: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Add several blockers to `issue_id` under one hold of the project lock. Each is
+/// checked for existence, self-edges, duplicates and cycles (counting the edges
+/// accepted earlier in the batch) before anything runs.
+#[tauri::command]
+async fn bd_dep_add_many(
+    issue_id: String,
+    blocker_ids: Vec<String>,
+    options: CwdOptions,
+) -> Result<Vec<BlockerResult>, String> {
+    let cwd = options.cwd.as_deref();
+    with_project_lock(cwd, || {
+        let list_options = ListOptions { include_all: Some(true), cwd: options.cwd.clone(), ..Default::default() };
+        let issues = fetch_list_issues(&list_options)?;
+        let mut results = plan_blocker_batch(&issue_id, &blocker_ids, &issues);
+        for result in results.iter_mut().filter(|r| r.added) {
+            let args = vec![issue_id.clone(), result.blocker_id.clone()];
+            if let Err(e) = execute_bd("dep add", &args, cwd) {
+                result.added = false;
+                result.error = Some(e.trim().to_string());
+            }
+        }
+        let added = results.iter().filter(|r| r.added).count();
+        log_info!("[bd_dep_add_many] {}: {} of {} blockers added", issue_id, added, results.len());
+        Ok(results)
+    })
+}
+
+/// Decide which blockers of `issue_id` may be added (`added: true`) given the current
+/// issues. A blocker is rejected when `issue_id` already blocks it, directly or
+/// transitively, since the new edge would close a cycle.
+fn plan_blocker_batch(issue_id: &str, blocker_ids: &[String], issues: &[Issue]) -> Vec<BlockerResult> {
+    let existing: std::collections::HashSet<&str> = issues.iter().map(|i| i.id.as_str()).collect();
+    let mut blocks: HashMap<String, Vec<String>> = HashMap::new();
+    for issue in issues {
+        for blocker in issue.blocked_by.iter().flatten() {
+            blocks.entry(blocker.clone()).or_default().push(issue.id.clone());
+        }
+        for blocked in issue.blocks.iter().flatten() {
+            blocks.entry(issue.id.clone()).or_default().push(blocked.clone());
+        }
+    }
+    let reaches = |blocks: &HashMap<String, Vec<String>>, from: &str, to: &str| {
+        let mut seen = std::collections::HashSet::new();
+        let mut stack = vec![from.to_string()];
+        while let Some(id) = stack.pop() {
+            if id == to {
+                return true;
+            }
+            if seen.insert(id.clone()) {
+                stack.extend(blocks.get(&id).into_iter().flatten().cloned());
+            }
+        }
+        false
+    };
+
+    let mut results: Vec<BlockerResult> = Vec::new();
+    for raw in blocker_ids {
+        let blocker = raw.trim().to_string();
+        let reject = |error: String| BlockerResult { blocker_id: blocker.clone(), added: false, error: Some(error) };
+        let already = blocks.get(&blocker).is_some_and(|b| b.iter().any(|id| id == issue_id));
+        let result = if blocker == issue_id {
+            reject("An issue cannot block itself".to_string())
+        } else if !existing.contains(blocker.as_str()) {
+            reject(format!("Issue not found: {}", blocker))
+        } else if already || results.iter().any(|r| r.blocker_id == blocker) {
+            BlockerResult { blocker_id: blocker.clone(), added: false, error: None }
+        } else if reaches(&blocks, issue_id, &blocker) {
+            reject(format!("Adding {} as a blocker would create a dependency cycle", blocker))
+        } else {
+            blocks.entry(blocker.clone()).or_default().push(issue_id.to_string());
+            BlockerResult { blocker_id: blocker.clone(), added: true, error: None }
+        };
+        results.push(result);
+    }
+    results
+}
+
+/// Which side of a "blocks" edge the other issue is on, relative to `issue_id`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BlockDirection {
+    /// The other issue blocks `issue_id`
+    BlockedBy,
+    /// `issue_id` blocks the other issue
+    Blocks,
+}
+
+/// (blocked, blocker) for a "blocks" edge — the order `dep add`/`dep remove` take.
+fn blocking_edge(issue_id: &str, other_id: &str, direction: BlockDirection) -> (String, String) {
+    match direction {
+        BlockDirection::BlockedBy => (issue_id.to_string(), other_id.to_string()),
+        BlockDirection::Blocks => (other_id.to_string(), issue_id.to_string()),
+    }
+}
+
+/// `blocker_id` blocks `issue_id`. Same as `bd_dep_add`, named for the direction.
+#[tauri::command]
+async fn bd_add_blocker(issue_id: String, blocker_id: String, options: CwdOptions) -> Result<serde_json::Value, String> {
+    let (blocked, blocker) = blocking_edge(&issue_id, &blocker_id, BlockDirection::BlockedBy);
+    add_blocking_dep(&blocked, &blocker, options.cwd.as_deref())
+}
+
+/// `issue_id` blocks `blocked_id` (`blocked_id` depends on `issue_id`).
+#[tauri::command]
+async fn bd_add_blocks(issue_id: String, blocked_id: String, options: CwdOptions) -> Result<serde_json::Value, String> {
+    let (blocked, blocker) = blocking_edge(&issue_id, &blocked_id, BlockDirection::Blocks);
+    add_blocking_dep(&blocked, &blocker, options.cwd.as_deref())
+}
+
+fn add_blocking_dep(blocked_id: &str, blocker_id: &str, cwd: Option<&str>) -> Result<serde_json::Value, String> {
+    // Best-effort status check: a closed blocker doesn't block anything, which is
+    // usually a mistake — warn so the UI can confirm, but still add the dep.
+    let warning = match fetch_show_issue(blocker_id, cwd) {
+        Ok(Some(blocker)) => closed_blocker_warning(&blocker),
+        Ok(None) => None,
+        Err(e) => {
+            log_warn!("[bd_dep_add] Could not check blocker {} status: {}", blocker_id, e);
+            None
+        }
+    };
+
+    let args = vec![blocked_id.to_string(), blocker_id.to_string()];
+
+    execute_bd("dep add", &args, cwd)?;
+
+    let mut result = serde_json::json!({ "success": true, "added": true });
+    if let Some(warning) = warning {
+        result["warning"] = serde_json::Value::String(warning);
+    }
+    Ok(result)
+}
+
+/// Warning for `bd_dep_add` when the blocker is already closed or tombstoned.
+fn closed_blocker_warning(blocker: &Issue) -> Option<String> {
+    match blocker.status.as_str() {
+        "closed" | "tombstone" => Some(format!("blocker is already {}", blocker.status)),
+        _ => None,
+    }
+}
+
+#[tauri::command]
+async fn bd_dep_remove(issue_id: String, blocker_id: String, options: CwdOptions) -> Result<serde_json::Value, String> {
+    let args = vec![issue_id, blocker_id];
+
+    execute_bd("dep remove", &args, options.cwd.as_deref())?;
+
+    Ok(serde_json::json!({ "success": true }))
+}
+
+/// Remove dependencies of `id` that point to issues which no longer exist.
+#[tauri::command]
+async fn bd_dep_prune(id: String, options: CwdOptions) -> Result<serde_json::Value, String> {
+    log_info!("[bd_dep_prune] Pruning dangling deps of {}", id);
+    let cwd = options.cwd.as_deref();
+
+    let issue = fetch_show_issue(&id, cwd)?
+        .ok_or_else(|| format!("Issue {} not found", id))?;
+    let existing = fetch_existing_ids(cwd)?;
+
+    let mut removed: Vec<String> = Vec::new();
+    for (issue_id, blocker_id) in plan_dep_prune(&issue, &existing) {
+        let dangling = if issue_id == id { blocker_id.clone() } else { issue_id.clone() };
+        match execute_bd("dep remove", &[issue_id, blocker_id], cwd) {
+            Ok(_) => removed.push(dangling),
+            Err(e) => log_warn!("[bd_dep_prune] Failed to remove dep on {}: {}", dangling, e),
+        }
+    }
+
+    log_info!("[bd_dep_prune] Removed {} dangling deps from {}", removed.len(), id);
+    Ok(serde_json::json!({ "success": true, "removed": removed }))
+}
+
+/// Upper bound on subgraph nodes, to keep the rendered graph readable.
+const DEP_SUBGRAPH_MAX_NODES: usize = 150;
+
+/// Dependency subgraph around `id`, up to `depth` hops along blocks/relation edges,
+/// built from one project-wide list.
+#[tauri::command]
+async fn bd_dep_subgraph(id: String, depth: usize, options: CwdOptions) -> Result<DepSubgraph, BackendError> {
+    log_info!("[bd_dep_subgraph] {} depth={}", id, depth);
+    sync_bd_database(options.cwd.as_deref());
+
+    let all = fetch_list_issues(&ListOptions { include_all: Some(true), cwd: options.cwd.clone(), ..Default::default() })?;
+    let graph = build_dep_subgraph(&id, depth, DEP_SUBGRAPH_MAX_NODES, &all)
+        .ok_or_else(|| BackendError::NotFound(format!("Issue not found: {}", id)))?;
+    log_info!("[bd_dep_subgraph] {} nodes, {} edges, truncated={}", graph.nodes.len(), graph.edges.len(), graph.truncated);
+    Ok(graph)
+}
+
+/// Directed edges touching `issue` (parent/child links are not dependencies).
+fn issue_dep_edges(issue: &Issue) -> Vec<DepEdge> {
+    let edge = |from: &str, to: &str, edge_type: &str| DepEdge {
+        from: from.to_string(),
+        to: to.to_string(),
+        edge_type: edge_type.to_string(),
+    };
+    let mut edges = Vec::new();
+    for blocker in issue.blocked_by.iter().flatten() {
+        edges.push(edge(blocker, &issue.id, "blocks"));
+    }
+    for blocked in issue.blocks.iter().flatten() {
+        edges.push(edge(&issue.id, blocked, "blocks"));
+    }
+    for rel in issue.relations.iter().flatten() {
+        if rel.direction == "dependent" {
+            edges.push(edge(&rel.id, &issue.id, &rel.relation_type));
+        } else {
+            edges.push(edge(&issue.id, &rel.id, &rel.relation_type));
+        }
+    }
+    edges
+}
+
+/// Bounded BFS from `root` over the edges of every issue in `all`, followed in both
+/// directions; only edges whose ends are both in the result are returned. Ids that
+/// aren't in `all` (deleted issues) are skipped. None when `root` itself is missing.
+fn build_dep_subgraph(root: &str, depth: usize, max_nodes: usize, all: &[Issue]) -> Option<DepSubgraph> {
+    let by_id: HashMap<&str, &Issue> = all.iter().map(|i| (i.id.as_str(), i)).collect();
+    by_id.get(root)?;
+
+    // Each edge once, whichever end(s) reported it
+    let mut all_edges: Vec<DepEdge> = Vec::new();
+    let mut seen_edges: std::collections::HashSet<(String, String, String)> = std::collections::HashSet::new();
+    for issue in all {
+        for e in issue_dep_edges(issue) {
+            if seen_edges.insert((e.from.clone(), e.to.clone(), e.edge_type.clone())) {
+                all_edges.push(e);
+            }
+        }
+    }
+    let mut neighbors: HashMap<&str, Vec<&str>> = HashMap::new();
+    for e in &all_edges {
+        neighbors.entry(e.from.as_str()).or_default().push(e.to.as_str());
+        neighbors.entry(e.to.as_str()).or_default().push(e.from.as_str());
+    }
+
+    let mut nodes: Vec<Issue> = Vec::new();
+    let mut visited: std::collections::HashSet<&str> = std::collections::HashSet::new();
+    let mut queue: std::collections::VecDeque<(&str, usize)> = std::collections::VecDeque::new();
+    let mut truncated = false;
+
+    visited.insert(root);
+    queue.push_back((root, 0));
+
+    while let Some((id, dist)) = queue.pop_front() {
+        let Some(issue) = by_id.get(id) else { continue };
+        if nodes.len() >= max_nodes {
+            truncated = true;
+            break;
+        }
+        if dist < depth {
+            for &other in neighbors.get(id).into_iter().flatten() {
+                if visited.insert(other) {
+                    queue.push_back((other, dist + 1));
+                }
+            }
+        }
+        nodes.push((*issue).clone());
+    }
+
+    let in_graph: std::collections::HashSet<&str> = nodes.iter().map(|n| n.id.as_str()).collect();
+    let edges: Vec<DepEdge> = all_edges.iter()
+        .filter(|e| in_graph.contains(e.from.as_str()) && in_graph.contains(e.to.as_str()))
+        .cloned()
+        .collect();
+
+    Some(DepSubgraph { nodes, edges, truncated })
+}
+
+/// All `from → to` pairs of one relation type across the project (closed issues included),
+/// e.g. every "duplicates" link for a duplicates report.
+#[tauri::command]
+async fn bd_relations_of_type(dep_type: String, cwd: Option<String>) -> Result<Vec<RelationPair>, String> {
+    let options = ListOptions { include_all: Some(true), cwd, ..Default::default() };
+    let issues = fetch_list_issues(&options)?;
+    let pairs = relation_pairs_of_type(&issues, &dep_type);
+    log_info!("[bd_relations_of_type] {} pairs of type {}", pairs.len(), dep_type);
+    Ok(pairs)
+}
+
+/// Collect pairs of `dep_type` from transformed issues. "blocks" comes from `blocked_by`
+/// (blocker → blocked); other types from outgoing relations (issue → target).
+fn relation_pairs_of_type(issues: &[Issue], dep_type: &str) -> Vec<RelationPair> {
+    let by_id: HashMap<&str, &Issue> = issues.iter().map(|i| (i.id.as_str(), i)).collect();
+    let end = |id: &str| RelationEnd {
+        id: id.to_string(),
+        title: by_id.get(id).map(|i| i.title.clone()).unwrap_or_default(),
+        status: by_id.get(id).map(|i| i.status.clone()).unwrap_or_default(),
+    };
+
+    let mut pairs: Vec<RelationPair> = Vec::new();
+    for issue in issues {
+        if dep_type == "blocks" {
+            for blocker in issue.blocked_by.iter().flatten() {
+                pairs.push(RelationPair { from: end(blocker), to: end(&issue.id) });
+            }
+        } else {
+            for rel in issue.relations.iter().flatten() {
+                if rel.relation_type == dep_type && rel.direction == "dependency" {
+                    pairs.push(RelationPair { from: end(&issue.id), to: end(&rel.id) });
+                }
+            }
+        }
+    }
+    pairs
+}
+
+/// Dependency problems found by `bd_validate_dependencies`. Edges are outgoing:
+/// `from` is the issue holding the dependency, `to` the issue it points at.
+#[derive(Debug, Default, Serialize)]
+pub struct DepValidationReport {
+    #[serde(rename = "checkedIssues")]
+    pub checked_issues: usize,
+    /// Dependencies on issues that don't exist
+    pub dangling: Vec<DepEdge>,
+    /// Issues depending on themselves
+    #[serde(rename = "selfDeps")]
+    pub self_deps: Vec<DepEdge>,
+    /// Extra copies of an edge (one entry per surplus copy)
+    pub duplicates: Vec<DepEdge>,
+    /// Ids of every issue with at least one problem
+    #[serde(rename = "affectedIds")]
+    pub affected_ids: Vec<String>,
+    /// Edges cleaned up when run with `fix`
+    pub fixed: usize,
+    /// With `fix`: duplicated edges whose every copy was removed and that could not be
+    /// added back (re-create them by hand)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub lost: Vec<DepEdge>,
+}
+
+/// Outgoing dependency edges of a raw issue as (target, type), one per stored copy;
+/// parent/child links aren't dependencies. Raw because `transform_issue` collapses
+/// repeated edges, which would hide duplicates.
+fn raw_dep_edges(issue: &BdRawIssue) -> Vec<(&str, &str)> {
+    match &issue.dependencies {
+        Some(deps) => deps.iter()
+            .filter_map(|dep| {
+                let dep_type = dep.dependency_type.as_deref()?;
+                let target = dep.depends_on_id.as_deref().or(dep.id.as_deref())?;
+                (dep_type != "parent-child").then_some((target, dep_type))
+            })
+            .collect(),
+        None => issue.blocked_by.iter().flatten().map(|b| (b.as_str(), "blocks")).collect(),
+    }
+}
+
+/// Check the outgoing dependencies of every issue. CLI projects only store one side of
+/// each edge, so (unlike the built-in backend would) there is no asymmetry check.
+fn validate_dependencies(issues: &[BdRawIssue]) -> DepValidationReport {
+    let existing: std::collections::HashSet<&str> = issues.iter().map(|i| i.id.as_str()).collect();
+    let mut report = DepValidationReport { checked_issues: issues.len(), ..Default::default() };
+
+    for issue in issues {
+        let mut seen: Vec<(&str, &str)> = Vec::new();
+        for (target, dep_type) in raw_dep_edges(issue) {
+            let edge = DepEdge {
+                from: issue.id.clone(),
+                to: target.to_string(),
+                edge_type: dep_type.to_string(),
+            };
+            if target == issue.id {
+                report.self_deps.push(edge);
+            } else if !existing.contains(target) {
+                report.dangling.push(edge);
+            } else if seen.contains(&(target, dep_type)) {
+                report.duplicates.push(edge);
+            } else {
+                seen.push((target, dep_type));
+                continue;
+            }
+            if !report.affected_ids.contains(&issue.id) {
+                report.affected_ids.push(issue.id.clone());
+            }
+        }
+    }
+    report
+}
+
+/// Whether `issue` (as `show` returns it) still has the outgoing edge.
+fn issue_has_dep(issue: &Issue, edge: &DepEdge) -> bool {
+    if edge.edge_type == "blocks" {
+        return issue.blocked_by.iter().flatten().any(|b| *b == edge.to);
+    }
+    issue.relations.iter().flatten()
+        .any(|r| r.id == edge.to && r.relation_type == edge.edge_type && r.direction == "dependency")
+}
+
+/// Report dangling, self and duplicate dependencies across the project.
+/// With `fix`, dangling and self edges are removed, and each surplus copy of a
+/// duplicated edge gets one `dep remove`. A CLI whose remove drops every copy leaves
+/// the edge gone; it is then added back once, and listed in `lost` if that fails.
+#[tauri::command]
+async fn bd_validate_dependencies(cwd: Option<String>, fix: Option<bool>) -> Result<DepValidationReport, String> {
+    let issues = fetch_all_raw_issues(cwd.as_deref(), "bd_validate_dependencies")?;
+    let mut report = validate_dependencies(&issues);
+    log_info!(
+        "[bd_validate_dependencies] {} issues: {} dangling, {} self, {} duplicate",
+        report.checked_issues, report.dangling.len(), report.self_deps.len(), report.duplicates.len()
+    );
+
+    if fix.unwrap_or(false) {
+        let cwd = cwd.as_deref();
+        let remove = |edge: &DepEdge| execute_bd("dep remove", &[edge.from.clone(), edge.to.clone()], cwd);
+        for edge in report.dangling.iter().chain(&report.self_deps) {
+            match remove(edge) {
+                Ok(_) => report.fixed += 1,
+                Err(e) => log_warn!("[bd_validate_dependencies] Failed to remove {} -> {}: {}", edge.from, edge.to, e),
+            }
+        }
+
+        let mut lost: Vec<DepEdge> = Vec::new();
+        let mut handled: Vec<&DepEdge> = Vec::new();
+        for edge in &report.duplicates {
+            if handled.contains(&edge) {
+                continue;
+            }
+            handled.push(edge);
+            let surplus = report.duplicates.iter().filter(|d| *d == edge).count();
+            for _ in 0..surplus {
+                match remove(edge) {
+                    Ok(_) => report.fixed += 1,
+                    Err(e) => {
+                        log_warn!("[bd_validate_dependencies] Failed to remove a copy of {} -> {}: {}", edge.from, edge.to, e);
+                        break;
+                    }
+                }
+            }
+            let still_there = fetch_show_issue(&edge.from, cwd)?.is_some_and(|issue| issue_has_dep(&issue, edge));
+            if still_there {
+                continue;
+            }
+            let args = [edge.from.clone(), edge.to.clone(), "--type".to_string(), edge.edge_type.clone()];
+            if let Err(e) = execute_bd("dep add", &args, cwd) {
+                log_error!("[bd_validate_dependencies] {} -> {} was removed and could not be added back: {}", edge.from, edge.to, e);
+                lost.push(edge.clone());
+            }
+        }
+        report.lost = lost;
+        log_info!("[bd_validate_dependencies] Fixed {} edges, lost {}", report.fixed, report.lost.len());
+    }
+    Ok(report)
+}
+
+#[tauri::command]
+async fn bd_dep_add_relation(id1: String, id2: String, relation_type: String, options: CwdOptions) -> Result<serde_json::Value, String> {
+    let relation_type = relation_type.trim().to_string();
+    validate_relation_type(&relation_type, get_cli_client_info().map(|(client, ..)| client))?;
+    let args = vec![id1, id2, "--type".to_string(), relation_type];
+
+    execute_bd("dep add", &args, options.cwd.as_deref())?;
+
+    Ok(serde_json::json!({ "success": true }))
+}
+
+#[tauri::command]
+async fn bd_dep_remove_relation(id1: String, id2: String, options: CwdOptions) -> Result<serde_json::Value, String> {
+    let args = vec![id1, id2];
+
+    execute_bd("dep remove", &args, options.cwd.as_deref())?;
+
+    Ok(serde_json::json!({ "success": true }))
+}
+
+#[tauri::command]
+async fn bd_available_relation_types() -> Vec<serde_json::Value> {
+    relation_types(get_cli_client_info().map(|(client, ..)| client))
+        .into_iter()
+        .map(|(v, l)| serde_json::json!({ "value": v, "label": l }))
+        .collect()
+}
+
+/// Relation types (value, label) the client supports; br lacks the bd-only ones.
+fn relation_types(client: Option<CliClient>) -> Vec<(&'static str, &'static str)> {
+    let common: Vec<(&str, &str)> = vec![
+        ("relates-to", "Relates To"),
+        ("related", "Related"),
+        ("discovered-from", "Discovered From"),
+        ("duplicates", "Duplicates"),
+        ("supersedes", "Supersedes"),
+        ("caused-by", "Caused By"),
+        ("replies-to", "Replies To"),
+    ];
+    let bd_only: Vec<(&str, &str)> = vec![
+        ("tracks", "Tracks"),
+        ("until", "Until"),
+        ("validates", "Validates"),
+    ];
+
+    match client {
+        Some(CliClient::Br) => common,
+        _ => {
+            let mut all = common;
+            all.extend(bd_only);
+            all
+        }
+    }
+}
+
+/// Reject relation types the client doesn't know before `dep add` turns a typo into
+/// a junk relation or an opaque CLI error.
+fn validate_relation_type(relation_type: &str, client: Option<CliClient>) -> Result<(), String> {
+    let valid: Vec<&str> = relation_types(client).into_iter().map(|(value, _)| value).collect();
+    if valid.contains(&relation_type) {
+        Ok(())
+    } else {
+        Err(format!("Unknown relation type '{}'; valid: {}", relation_type, valid.join(", ")))
+    }
+}
+
+#[tauri::command]
+async fn fs_exists(path: String) -> Result<bool, String> {
+    Ok(std::path::Path::new(&path).exists())
+}
+
+#[tauri::command]
+async fn fs_list(path: Option<String>) -> Result<FsListResult, String> {
+    use std::fs;
+
+    let target_path = match path {
+        Some(p) if p == "~" => dirs::home_dir().unwrap_or_else(|| PathBuf::from("/")),
+        Some(p) => PathBuf::from(p),
+        None => dirs::home_dir().unwrap_or_else(|| PathBuf::from("/")),
+    };
+
+    let target_path = target_path.canonicalize()
+        .map_err(|e| format!("Cannot resolve path: {}", e))?;
+
+    let entries = fs::read_dir(&target_path)
+        .map_err(|e| format!("Cannot read directory: {}", e))?;
+
+    let mut directories: Vec<DirectoryEntry> = Vec::new();
+
+    for entry in entries {
+        let entry = match entry {
+            Ok(e) => e,
+            Err(_) => continue,
+        };
+
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        // Skip hidden files
+        if name.starts_with('.') {
+            continue;
+        }
+
+        let metadata = match entry.metadata() {
+            Ok(m) => m,
+            Err(_) => continue,
+        };
+
+        if metadata.is_dir() {
+            let full_path = entry.path();
+            let beads_path = full_path.join(".beads");
+            let has_beads = beads_path.is_dir();
+            let uses_dolt = has_beads && project_uses_dolt(&beads_path);
+
+            directories.push(DirectoryEntry {
+                name,
+                path: full_path.to_string_lossy().to_string(),
+                is_directory: true,
+                has_beads,
+                uses_dolt,
+            });
+        }
+    }
+
+    // Sort: beads projects first, then alphabetically
+    directories.sort_by(|a, b| {
+        match (a.has_beads, b.has_beads) {
+            (true, false) => std::cmp::Ordering::Less,
+            (false, true) => std::cmp::Ordering::Greater,
+            _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+        }
+    });
+
+    let current_beads_path = target_path.join(".beads");
+    let current_has_beads = current_beads_path.is_dir();
+    let current_uses_dolt = current_has_beads && project_uses_dolt(&current_beads_path);
+
+    Ok(FsListResult {
+        current_path: target_path.to_string_lossy().to_string(),
+        has_beads: current_has_beads,
+        uses_dolt: current_uses_dolt,
+        entries: directories,
+    })
+}
+
+// File watcher commands removed - replaced by frontend polling for lower CPU usage
+
+// ============================================================================
+// Update Checker
+// ============================================================================
+
+const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+const GITHUB_RELEASES_URL: &str = "https://api.github.com/repos/w3dev33/beads-task-issue-tracker/releases/latest";
+
+/// Get a GitHub token from `gh auth token` (if gh CLI is installed and authenticated).
+/// Raises the API rate limit from 60/hour (anonymous) to 5,000/hour (authenticated).
+fn get_github_token() -> Option<String> {
+    // Check GITHUB_TOKEN env var first
+    if let Ok(token) = env::var("GITHUB_TOKEN") {
+        if !token.is_empty() {
+            return Some(token);
+        }
+    }
+    // Fall back to gh CLI
+    let output = new_command("gh")
+        .args(&["auth", "token"])
+        .output()
+        .ok()?;
+    if output.status.success() {
+        let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if !token.is_empty() {
+            return Some(token);
+        }
+    }
+    None
+}
+
+/// Build a reqwest client with GitHub auth if available.
+fn github_client() -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .user_agent("beads-task-issue-tracker")
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+/// Add GitHub auth header to a request if a token is available.
+fn with_github_auth(req: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    match get_github_token() {
+        Some(token) => req.bearer_auth(token),
+        None => req,
+    }
+}
+
+fn get_platform_string() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "macos"
+    } else if cfg!(target_os = "windows") {
+        "windows"
+    } else {
+        "linux"
+    }
+}
+
+fn find_platform_asset(assets: &[GitHubAsset]) -> Option<&GitHubAsset> {
+    let suffix = if cfg!(target_os = "macos") {
+        if cfg!(target_arch = "aarch64") {
+            "_macOS-ARM64.dmg"
+        } else {
+            "_macOS-Intel.dmg"
+        }
+    } else if cfg!(target_os = "windows") {
+        "_Windows.msi"
+    } else {
+        "_Linux-amd64.AppImage"
+    };
+
+    assets.iter().find(|a| a.name.ends_with(suffix))
+}
+
+fn compare_versions(current: &str, latest: &str) -> bool {
+    // Remove 'v' prefix if present
+    let current = current.trim_start_matches('v');
+    let latest = latest.trim_start_matches('v');
+
+    let parse_version = |v: &str| -> Vec<u32> {
+        v.split('.')
+            .filter_map(|s| s.parse::<u32>().ok())
+            .collect()
+    };
+
+    let current_parts = parse_version(current);
+    let latest_parts = parse_version(latest);
+
+    for i in 0..3 {
+        let c = current_parts.get(i).copied().unwrap_or(0);
+        let l = latest_parts.get(i).copied().unwrap_or(0);
+        if l > c {
+            return true;
+        }
+        if c > l {
+            return false;
+        }
+    }
+    false
+}
+
+#[tauri::command]
+async fn check_for_updates() -> Result<UpdateInfo, String> {
+    let client = github_client()?;
+
+    let response = with_github_auth(client.get(GITHUB_RELEASES_URL))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch releases: {}", e))?;
+
+    // Handle 404 (no published releases yet)
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(UpdateInfo {
+            current_version: CURRENT_VERSION.to_string(),
+            latest_version: CURRENT_VERSION.to_string(),
+            has_update: false,
+            release_url: "https://github.com/w3dev33/beads-task-issue-tracker/releases".to_string(),
+            download_url: None,
+            platform: get_platform_string().to_string(),
+            release_notes: None,
+        });
+    }
+
+    if !response.status().is_success() {
+        return Err(format!("GitHub API returned status: {}", response.status()));
+    }
+
+    let release: GitHubRelease = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse release info: {}", e))?;
+
+    let latest_version = release.tag_name.trim_start_matches('v').to_string();
+    let has_update = compare_versions(CURRENT_VERSION, &latest_version);
+
+    let download_url = find_platform_asset(&release.assets)
+        .map(|a| a.browser_download_url.clone());
+
+    // Fetch CHANGELOG.md via GitHub API (raw.githubusercontent CDN ignores query params for caching)
+    let changelog = with_github_auth(
+        client
+            .get("https://api.github.com/repos/w3dev33/beads-task-issue-tracker/contents/CHANGELOG.md")
+            .header("Accept", "application/vnd.github.raw+json")
+    )
+        .send()
+        .await
+        .ok()
+        .and_then(|r| if r.status().is_success() { Some(r) } else { None });
+    let changelog_text = match changelog {
+        Some(r) => r.text().await.ok(),
+        None => None,
+    };
+
+    Ok(UpdateInfo {
+        current_version: CURRENT_VERSION.to_string(),
+        latest_version,
+        has_update,
+        release_url: release.html_url,
+        download_url,
+        platform: get_platform_string().to_string(),
+        release_notes: changelog_text.or(release.body),
+    })
+}
+
+#[tauri::command]
+async fn check_for_updates_demo() -> Result<UpdateInfo, String> {
+    let client = github_client()?;
+
+    let response = with_github_auth(client.get(GITHUB_RELEASES_URL))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch releases: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("GitHub API returned status: {}", response.status()));
+    }
+
+    let release: GitHubRelease = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse release info: {}", e))?;
+
+    let latest_version = release.tag_name.trim_start_matches('v').to_string();
+
+    let download_url = find_platform_asset(&release.assets)
+        .map(|a| a.browser_download_url.clone());
+
+    // Fetch CHANGELOG.md via GitHub API (raw.githubusercontent CDN ignores query params for caching)
+    let changelog = with_github_auth(
+        client
+            .get("https://api.github.com/repos/w3dev33/beads-task-issue-tracker/contents/CHANGELOG.md")
+            .header("Accept", "application/vnd.github.raw+json")
+    )
+        .send()
+        .await
+        .ok()
+        .and_then(|r| if r.status().is_success() { Some(r) } else { None });
+    let changelog_text = match changelog {
+        Some(r) => r.text().await.ok(),
+        None => None,
+    };
+
+    // Demo mode: force has_update = true, fake current version as 0.0.0
+    Ok(UpdateInfo {
+        current_version: "0.0.0".to_string(),
+        latest_version,
+        has_update: true,
+        release_url: release.html_url,
+        download_url,
+        platform: get_platform_string().to_string(),
+        release_notes: changelog_text.or(release.body),
+    })
+}
+
+#[tauri::command]
+async fn check_bd_cli_update() -> Result<BdCliUpdateInfo, String> {
+    // Get current bd version
+    let version_str = get_bd_version().await;
+    if version_str.contains("not found") {
+        return Err("bd CLI not found".to_string());
+    }
+
+    // Parse semver from version string
+    let current_tuple = parse_bd_version(&version_str)
+        .ok_or_else(|| format!("Could not parse version from: {}", version_str))?;
+    let current_version = format!("{}.{}.{}", current_tuple.0, current_tuple.1, current_tuple.2);
+
+    // Determine the correct GitHub repo based on client type (bd vs br)
+    let client_type = detect_cli_client(&version_str);
+    let api_url = match client_type {
+        CliClient::Br => "https://api.github.com/repos/Dicklesworthstone/beads_rust/releases/latest",
+        _ => "https://api.github.com/repos/steveyegge/beads/releases/latest",
+    };
+    let releases_url = match client_type {
+        CliClient::Br => "https://github.com/Dicklesworthstone/beads_rust/releases",
+        _ => "https://github.com/steveyegge/beads/releases",
+    };
+
+    let client = github_client()?;
+
+    let response = with_github_auth(client.get(api_url))
+        .send()
+        .await
         .map_err(|e| format!("Failed to fetch releases: {}", e))?;
 
-    // Handle 404 (no published releases yet)
-    if response.status() == reqwest::StatusCode::NOT_FOUND {
-        return Ok(UpdateInfo {
-            current_version: CURRENT_VERSION.to_string(),
-            latest_version: CURRENT_VERSION.to_string(),
-            has_update: false,
-            release_url: "https://github.com/w3dev33/beads-task-issue-tracker/releases".to_string(),
-            download_url: None,
-            platform: get_platform_string().to_string(),
-            release_notes: None,
+    if !response.status().is_success() {
+        return Err(format!("GitHub API returned status: {}", response.status()));
+    }
+
+    let release: GitHubRelease = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse release info: {}", e))?;
+
+    let latest_version = release.tag_name.trim_start_matches('v').to_string();
+    let has_update = compare_versions(&current_version, &latest_version);
+
+    Ok(BdCliUpdateInfo {
+        current_version,
+        latest_version,
+        has_update,
+        release_url: releases_url.to_string(),
+    })
+}
+
+#[tauri::command]
+async fn download_and_install_update(download_url: String) -> Result<String, String> {
+    log::info!("[download_update] Starting download from: {}", download_url);
+
+    // Extract filename from URL
+    let filename = download_url
+        .rsplit('/')
+        .next()
+        .unwrap_or("update-download")
+        .to_string();
+    log::info!("[download_update] Target filename: {}", filename);
+
+    // Download the file
+    let client = reqwest::Client::builder()
+        .user_agent("beads-task-issue-tracker")
+        .build()
+        .map_err(|e| {
+            log::error!("[download_update] Failed to create HTTP client: {}", e);
+            format!("Failed to create HTTP client: {}", e)
+        })?;
+
+    log::info!("[download_update] Sending GET request...");
+    let response = client
+        .get(&download_url)
+        .send()
+        .await
+        .map_err(|e| {
+            log::error!("[download_update] HTTP request failed: {} (url: {})", e, download_url);
+            format!("Failed to download update: {}", e)
+        })?;
+
+    let status = response.status();
+    let final_url = response.url().to_string();
+    log::info!("[download_update] Response status: {} (final URL: {})", status, final_url);
+
+    if !status.is_success() {
+        log::error!("[download_update] Download failed with status: {} (url: {})", status, final_url);
+        return Err(format!("Download failed with status: {}", status));
+    }
+
+    log::info!("[download_update] Reading response bytes...");
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| {
+            log::error!("[download_update] Failed to read response bytes: {}", e);
+            format!("Failed to read download bytes: {}", e)
+        })?;
+    log::info!("[download_update] Downloaded {} bytes", bytes.len());
+
+    // Save to ~/Downloads
+    let download_dir = dirs::download_dir()
+        .ok_or_else(|| {
+            log::error!("[download_update] Could not find Downloads directory");
+            "Could not find Downloads directory".to_string()
+        })?;
+
+    let dest_path = download_dir.join(&filename);
+    log::info!("[download_update] Saving to: {}", dest_path.display());
+    fs::write(&dest_path, &bytes)
+        .map_err(|e| {
+            log::error!("[download_update] Failed to save file to {}: {}", dest_path.display(), e);
+            format!("Failed to save file: {}", e)
+        })?;
+
+    let dest_str = dest_path.to_string_lossy().to_string();
+    log::info!("[download_update] Saved successfully: {} ({} bytes)", dest_str, bytes.len());
+
+    // On macOS, mount the DMG
+    #[cfg(target_os = "macos")]
+    {
+        if filename.ends_with(".dmg") {
+            log::info!("[download_update] Mounting DMG: {}", dest_str);
+            Command::new("open")
+                .arg(&dest_path)
+                .spawn()
+                .map_err(|e| {
+                    log::error!("[download_update] Failed to open DMG: {}", e);
+                    format!("Failed to open DMG: {}", e)
+                })?;
+        }
+    }
+
+    Ok(dest_str)
+}
+
+// ============================================================================
+// Debug / Logging Commands
+// ============================================================================
+
+fn get_log_path() -> PathBuf {
+    // Match tauri-plugin-log's LogDir resolution per platform:
+    //   macOS:   ~/Library/Logs/com.beads.manager/
+    //   Linux:   ~/.local/share/com.beads.manager/logs/  (XDG_DATA_HOME)
+    //   Windows: %APPDATA%/com.beads.manager/logs/
+    #[cfg(target_os = "macos")]
+    {
+        let home = env::var("HOME").unwrap_or_default();
+        PathBuf::from(home)
+            .join("Library/Logs/com.beads.manager/beads.log")
+    }
+    #[cfg(target_os = "linux")]
+    {
+        dirs::data_local_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("com.beads.manager")
+            .join("logs")
+            .join("beads.log")
+    }
+    #[cfg(target_os = "windows")]
+    {
+        dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("com.beads.manager")
+            .join("logs")
+            .join("beads.log")
+    }
+}
+
+#[tauri::command]
+async fn get_logging_enabled() -> bool {
+    LOGGING_ENABLED.load(Ordering::Relaxed)
+}
+
+#[tauri::command]
+async fn set_logging_enabled(enabled: bool) {
+    LOGGING_ENABLED.store(enabled, Ordering::Relaxed);
+    if enabled {
+        log_info!("[debug] Logging enabled");
+    }
+    persist_config_change(|config| config.logging_enabled = enabled);
+}
+
+#[tauri::command]
+async fn get_verbose_logging() -> bool {
+    VERBOSE_LOGGING.load(Ordering::Relaxed)
+}
+
+#[tauri::command]
+async fn set_verbose_logging(enabled: bool) {
+    VERBOSE_LOGGING_GENERATION.fetch_add(1, Ordering::Relaxed);
+    VERBOSE_LOGGING.store(enabled, Ordering::Relaxed);
+    log_info!("[debug] Verbose logging: {}", if enabled { "ON" } else { "OFF" });
+    persist_config_change(|config| config.verbose_logging = enabled);
+}
+
+/// Save one settings.json change made by a per-setting command. The runtime value is
+/// already applied, so a failed write is only logged.
+fn persist_config_change(change: impl FnOnce(&mut AppConfig)) {
+    if let Err(e) = update_config(change) {
+        log_warn!("[config] {}", e);
+    }
+}
+
+/// Enable verbose logging for a limited time (default 30 minutes), then switch it off.
+#[tauri::command]
+async fn set_verbose_logging_timed(minutes: Option<u64>) {
+    let minutes = minutes.unwrap_or(VERBOSE_LOGGING_DEFAULT_MINUTES).max(1);
+    log_info!("[debug] Verbose logging: ON for {} min", minutes);
+    enable_verbose_logging_for(Duration::from_secs(minutes * 60));
+}
+
+/// Turn verbose logging on and spawn a timer that turns it off after `duration`,
+/// unless it was toggled again in the meantime.
+fn enable_verbose_logging_for(duration: Duration) {
+    let generation = VERBOSE_LOGGING_GENERATION.fetch_add(1, Ordering::Relaxed) + 1;
+    VERBOSE_LOGGING.store(true, Ordering::Relaxed);
+    let deadline = Instant::now() + duration;
+
+    std::thread::spawn(move || {
+        std::thread::sleep(duration);
+        let current = VERBOSE_LOGGING_GENERATION.load(Ordering::Relaxed);
+        if verbose_logging_expired(deadline, Instant::now(), generation, current) {
+            VERBOSE_LOGGING.store(false, Ordering::Relaxed);
+            log_info!("[debug] Verbose logging auto-disabled after {:?}", duration);
+        }
+    });
+}
+
+/// Whether a timed verbose session armed at `generation` should switch off now:
+/// its deadline has passed and nobody toggled verbose logging since.
+fn verbose_logging_expired(deadline: Instant, now: Instant, generation: u64, current_generation: u64) -> bool {
+    now >= deadline && generation == current_generation
+}
+
+#[tauri::command]
+async fn clear_logs() -> Result<(), String> {
+    let log_path = get_log_path();
+    if log_path.exists() {
+        fs::write(&log_path, "").map_err(|e| format!("Failed to clear logs: {}", e))?;
+        log_info!("[debug] Logs cleared");
+    }
+    Ok(())
+}
+
+#[tauri::command]
+async fn export_logs() -> Result<String, String> {
+    let log_path = get_log_path();
+    if !log_path.exists() {
+        return Err("No logs to export".to_string());
+    }
+
+    // Get export folder: Downloads > Documents > Home
+    let export_dir = dirs::download_dir()
+        .or_else(dirs::document_dir)
+        .or_else(dirs::home_dir)
+        .ok_or_else(|| "Could not find a folder to export logs".to_string())?;
+
+    // Generate filename with timestamp
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let export_filename = format!("beads-logs-{}.log", now);
+    let export_path = export_dir.join(&export_filename);
+
+    // Copy log file
+    fs::copy(&log_path, &export_path)
+        .map_err(|e| format!("Failed to export logs: {}", e))?;
+
+    Ok(export_path.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+async fn read_logs(tail_lines: Option<usize>) -> Result<String, String> {
+    read_log_tail(tail_lines)
+}
+
+fn read_log_tail(tail_lines: Option<usize>) -> Result<String, String> {
+    let log_path = get_log_path();
+    if !log_path.exists() {
+        return Ok(String::new());
+    }
+
+    let content = fs::read_to_string(&log_path)
+        .map_err(|e| format!("Failed to read logs: {}", e))?;
+
+    // If tail_lines is specified, return only the last N lines
+    if let Some(n) = tail_lines {
+        let lines: Vec<&str> = content.lines().collect();
+        let start = if lines.len() > n { lines.len() - n } else { 0 };
+        Ok(lines[start..].join("\n"))
+    } else {
+        Ok(content)
+    }
+}
+
+/// One parsed log line. Lines that don't match the log format come back with only
+/// `message` set (the raw line), e.g. continuation lines of multi-line messages.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct LogEntry {
+    pub timestamp: Option<String>,
+    pub level: Option<String>,
+    /// The `[category]` prefix used by the log macros, e.g. "sync"
+    pub category: Option<String>,
+    pub message: String,
+}
+
+/// Like `read_logs`, but parsed into entries for filtering by level/category.
+#[tauri::command]
+async fn read_logs_structured(tail_lines: Option<usize>) -> Result<Vec<LogEntry>, String> {
+    let content = read_log_tail(tail_lines)?;
+    Ok(content.lines().filter(|l| !l.trim().is_empty()).map(parse_log_line).collect())
+}
+
+/// Split a leading `[...]` group off `line`.
+fn take_bracketed(line: &str) -> Option<(&str, &str)> {
+    let rest = line.strip_prefix('[')?;
+    let end = rest.find(']')?;
+    Some((&rest[..end], &rest[end + 1..]))
+}
+
+/// Parse a plugin log line: `[date][time][target][LEVEL] [category] message`.
+fn parse_log_line(line: &str) -> LogEntry {
+    let parsed = (|| {
+        let (date, rest) = take_bracketed(line)?;
+        let (time, rest) = take_bracketed(rest)?;
+        let (_target, rest) = take_bracketed(rest)?;
+        let (level, rest) = take_bracketed(rest)?;
+        let rest = rest.strip_prefix(' ').unwrap_or(rest);
+        let (category, message) = match take_bracketed(rest) {
+            Some((category, message)) if !category.contains(' ') => {
+                (Some(category.to_string()), message.strip_prefix(' ').unwrap_or(message))
+            }
+            _ => (None, rest),
+        };
+        Some(LogEntry {
+            timestamp: Some(format!("{} {}", date, time)),
+            level: Some(level.to_string()),
+            category,
+            message: message.to_string(),
+        })
+    })();
+    parsed.unwrap_or_else(|| LogEntry {
+        timestamp: None,
+        level: None,
+        category: None,
+        message: line.to_string(),
+    })
+}
+
+#[tauri::command]
+async fn get_log_path_string() -> String {
+    get_log_path().to_string_lossy().to_string()
+}
+
+#[tauri::command]
+async fn log_frontend(level: String, message: String) {
+    match level.as_str() {
+        "error" => log::error!("[frontend] {}", message),
+        "warn" => log::warn!("[frontend] {}", message),
+        _ => log::info!("[frontend] {}", message),
+    }
+}
+
+#[tauri::command]
+async fn get_bd_version() -> String {
+    let binary = get_cli_binary();
+    match new_command(&binary)
+        .arg("--version")
+        .env("PATH", get_extended_path())
+        .output()
+    {
+        Ok(output) if output.status.success() => {
+            let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            if binary != "bd" {
+                format!("{} ({})", version, binary)
+            } else {
+                version
+            }
+        }
+        _ => format!("{} not found", binary),
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct CompatibilityInfo {
+    version: String,
+    /// "bd", "br", or "unknown"
+    #[serde(rename = "clientType")]
+    client_type: String,
+    #[serde(rename = "versionTuple")]
+    version_tuple: Option<Vec<u32>>,
+    #[serde(rename = "supportsDaemonFlag")]
+    supports_daemon_flag: bool,
+    #[serde(rename = "usesJsonlFiles")]
+    uses_jsonl_files: bool,
+    #[serde(rename = "usesDoltBackend")]
+    uses_dolt_backend: bool,
+    #[serde(rename = "supportsListAllFlag")]
+    supports_list_all_flag: bool,
+    /// Embedded Dolt crashes when two processes open the same project at once
+    #[serde(rename = "hasDoltConcurrencyBug")]
+    has_dolt_concurrency_bug: bool,
+    warnings: Vec<String>,
+}
+
+/// First bd release seen crashing (SIGSEGV) on concurrent embedded-Dolt access.
+/// No fixed release is known yet, so everything from here on is flagged.
+const DOLT_CONCURRENCY_BUG_SINCE: (u32, u32, u32) = (0, 55, 0);
+
+const DOLT_CONCURRENCY_WARNING: &str = "This bd version can crash (SIGSEGV) when two processes use the same project at once — \
+    avoid running agents or other bd commands against this project while the app is open";
+
+fn client_type_name(client: CliClient) -> &'static str {
+    match client {
+        CliClient::Bd => "bd",
+        CliClient::Br => "br",
+        CliClient::Unknown => "unknown",
+    }
+}
+
+fn has_dolt_concurrency_bug(client: CliClient, version: (u32, u32, u32)) -> bool {
+    client == CliClient::Bd && version >= DOLT_CONCURRENCY_BUG_SINCE
+}
+
+#[tauri::command]
+async fn check_bd_compatibility() -> CompatibilityInfo {
+    let version_string = get_bd_version().await;
+    let info = get_cli_client_info();
+
+    let mut warnings = Vec::new();
+
+    let (client, tuple) = match info {
+        Some((client, major, minor, patch)) => (client, Some((major, minor, patch))),
+        None => {
+            warnings.push(format!("Could not detect CLI client from: {}", version_string));
+            (CliClient::Unknown, None)
+        }
+    };
+
+    let client_type_str = client_type_name(client);
+
+    if client == CliClient::Br {
+        warnings.push("br (beads_rust) detected: frozen on classic SQLite+JSONL architecture, no daemon support".to_string());
+    }
+
+    if let Some((major, minor, _)) = tuple {
+        if client == CliClient::Bd && major == 0 && minor >= 50 {
+            warnings.push("bd >= 0.50.0 detected: daemon and JSONL systems have been removed".to_string());
+        }
+    }
+
+    let dolt_concurrency_bug = tuple.is_some_and(|t| has_dolt_concurrency_bug(client, t));
+    if dolt_concurrency_bug {
+        warnings.push(DOLT_CONCURRENCY_WARNING.to_string());
+    }
+
+    CompatibilityInfo {
+        version: version_string,
+        client_type: client_type_str.to_string(),
+        version_tuple: tuple.map(|(a, b, c)| vec![a, b, c]),
+        supports_daemon_flag: supports_daemon_flag(),
+        uses_jsonl_files: uses_jsonl_files(),
+        uses_dolt_backend: uses_dolt_backend(),
+        supports_list_all_flag: supports_list_all_flag(),
+        has_dolt_concurrency_bug: dolt_concurrency_bug,
+        warnings,
+    }
+}
+
+// ============================================================================
+// Diagnostics
+// ============================================================================
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct DiagnosticCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub message: String,
+}
+
+fn diagnostic(name: &str, status: CheckStatus, message: impl Into<String>) -> DiagnosticCheck {
+    DiagnosticCheck { name: name.to_string(), status, message: message.into() }
+}
+
+/// Environment self-test for bug reports: CLI binary and version, project `.beads`
+/// dir, backend, Dolt, config dir writability, `sqlite3` (migration comment restore)
+/// and, for an initialized project, dependency integrity.
+#[tauri::command]
+async fn run_diagnostics(cwd: Option<String>) -> Vec<DiagnosticCheck> {
+    let path_var = get_extended_path();
+    let binary = get_cli_binary();
+    let project = PathBuf::from(resolve_working_dir(cwd.as_deref()));
+    let beads_dir = project.join(".beads");
+    let client = get_cli_client_info();
+    let config_dir = get_config_path().parent().map(PathBuf::from).unwrap_or_else(|| PathBuf::from("."));
+
+    let mut checks = vec![check_cli_binary(&binary, &path_var)];
+    if let Some(resolved) = find_in_path(&binary, &path_var) {
+        checks.push(check_cli_version(&resolved));
+    }
+    checks.push(check_beads_dir(&project));
+    checks.push(check_backend(client, &beads_dir));
+    checks.push(check_dolt(client, &beads_dir));
+    checks.push(check_config_dir_writable(&config_dir));
+    checks.push(check_sqlite3(&path_var));
+    if beads_dir.is_dir() {
+        checks.push(check_dependencies(cwd.as_deref()));
+    }
+
+    let failed = checks.iter().filter(|c| c.status == CheckStatus::Fail).count();
+    log_info!("[diagnostics] {} checks, {} failed", checks.len(), failed);
+    checks
+}
+
+/// `validate_dependencies` over the project; problems are a warning (fixable from the
+/// dependency validator), a failed list too.
+fn check_dependencies(cwd: Option<&str>) -> DiagnosticCheck {
+    let issues = match fetch_all_raw_issues(cwd, "diagnostics") {
+        Ok(issues) => issues,
+        Err(e) => return diagnostic("dependencies", CheckStatus::Warn, format!("Could not list issues: {}", e)),
+    };
+    dependency_check(&validate_dependencies(&issues))
+}
+
+fn dependency_check(report: &DepValidationReport) -> DiagnosticCheck {
+    let (dangling, self_deps, duplicates) = (report.dangling.len(), report.self_deps.len(), report.duplicates.len());
+    if dangling + self_deps + duplicates == 0 {
+        return diagnostic("dependencies", CheckStatus::Pass, format!("{} issues, no dependency problems", report.checked_issues));
+    }
+    diagnostic(
+        "dependencies",
+        CheckStatus::Warn,
+        format!(
+            "{} dangling, {} self, {} duplicate dependencies in {}",
+            dangling, self_deps, duplicates, report.affected_ids.join(", ")
+        ),
+    )
+}
+
+fn check_cli_binary(binary: &str, path_var: &str) -> DiagnosticCheck {
+    match find_in_path(binary, path_var) {
+        Some(path) => diagnostic("cliBinary", CheckStatus::Pass, format!("{} → {}", binary, path.display())),
+        None => diagnostic("cliBinary", CheckStatus::Fail, format!("{} not found on PATH", binary)),
+    }
+}
+
+/// Runs `--version` and reports the detected client; an unrecognized client is a warning.
+fn check_cli_version(binary: &std::path::Path) -> DiagnosticCheck {
+    let output = match version_output(&mut new_command(&binary.to_string_lossy())) {
+        Ok(output) => output,
+        Err(e) => return diagnostic("cliVersion", CheckStatus::Fail, format!("Could not run {}: {}", binary.display(), e)),
+    };
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return diagnostic("cliVersion", CheckStatus::Fail, format!("--version failed: {}", stderr));
+    }
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let client = detect_cli_client(&version);
+    match (client, parse_bd_version(&version)) {
+        (CliClient::Unknown, _) | (_, None) => {
+            diagnostic("cliVersion", CheckStatus::Warn, format!("Unrecognized CLI: {}", version))
+        }
+        (client, Some(v)) if has_dolt_concurrency_bug(client, v) => {
+            diagnostic("cliVersion", CheckStatus::Warn, format!("{} ({}): {}", version, client_type_name(client), DOLT_CONCURRENCY_WARNING))
+        }
+        (client, Some(_)) => diagnostic("cliVersion", CheckStatus::Pass, format!("{} ({})", version, client_type_name(client))),
+    }
+}
+
+fn check_beads_dir(project: &std::path::Path) -> DiagnosticCheck {
+    if !project.is_dir() {
+        return diagnostic("beadsDir", CheckStatus::Fail, format!("Project folder not found: {}", project.display()));
+    }
+    let beads_dir = project.join(".beads");
+    if beads_dir.is_dir() {
+        diagnostic("beadsDir", CheckStatus::Pass, beads_dir.display().to_string())
+    } else {
+        diagnostic("beadsDir", CheckStatus::Fail, format!("No .beads folder in {} (run init)", project.display()))
+    }
+}
+
+fn check_backend(client: Option<(CliClient, u32, u32, u32)>, beads_dir: &std::path::Path) -> DiagnosticCheck {
+    match resolve_project_backend(client, beads_dir) {
+        "unknown" => diagnostic("backend", CheckStatus::Warn, "Could not determine the backend (CLI not detected)"),
+        backend => diagnostic("backend", CheckStatus::Pass, backend),
+    }
+}
+
+/// A Dolt database the CLI can't open (bd < 0.50 or br) is a failure; otherwise informational.
+fn check_dolt(client: Option<(CliClient, u32, u32, u32)>, beads_dir: &std::path::Path) -> DiagnosticCheck {
+    let has_dolt = beads_dir_has_dolt(beads_dir);
+    let cli_has_dolt = client_uses_dolt(client);
+    match (has_dolt, cli_has_dolt) {
+        (true, false) if client.is_some() => {
+            diagnostic("dolt", CheckStatus::Fail, "Project has a Dolt database but this CLI doesn't support Dolt")
+        }
+        (true, _) => diagnostic("dolt", CheckStatus::Pass, "Dolt database found"),
+        (false, _) => diagnostic("dolt", CheckStatus::Pass, "No Dolt database (SQLite/JSONL storage)"),
+    }
+}
+
+/// Creates the dir if needed and writes/removes a probe file.
+fn check_config_dir_writable(dir: &std::path::Path) -> DiagnosticCheck {
+    let probe = dir.join(format!(".write-test-{}", std::process::id()));
+    let result = fs::create_dir_all(dir)
+        .and_then(|_| fs::write(&probe, b"ok"))
+        .and_then(|_| fs::remove_file(&probe));
+    match result {
+        Ok(()) => diagnostic("configDir", CheckStatus::Pass, format!("{} is writable", dir.display())),
+        Err(e) => diagnostic("configDir", CheckStatus::Fail, format!("{} is not writable: {}", dir.display(), e)),
+    }
+}
+
+fn check_sqlite3(path_var: &str) -> DiagnosticCheck {
+    match find_in_path("sqlite3", path_var) {
+        Some(path) => diagnostic("sqlite3", CheckStatus::Pass, path.display().to_string()),
+        None => diagnostic("sqlite3", CheckStatus::Warn, "sqlite3 not found: comments can't be restored when migrating to Dolt"),
+    }
+}
+
+// ============================================================================
+// CLI Binary Configuration Commands
+// ============================================================================
+
+#[tauri::command]
+async fn get_cli_binary_path() -> String {
+    get_cli_binary()
+}
+
+/// Absolute path of the binary that actually runs: the configured name looked up on
+/// the same extended PATH the CLI is spawned with (first match wins, like `which`).
+#[tauri::command]
+async fn resolve_cli_binary_path() -> Result<String, String> {
+    let binary = get_cli_binary();
+    find_in_path(&binary, &get_extended_path())
+        .map(|p| p.to_string_lossy().to_string())
+        .ok_or_else(|| format!("{} not found on PATH", binary))
+}
+
+/// `which`: an explicit path is checked as-is, a bare name against each PATH entry.
+fn find_in_path(binary: &str, path_var: &str) -> Option<PathBuf> {
+    let as_path = std::path::Path::new(binary);
+    if as_path.components().count() > 1 {
+        return is_executable_file(as_path).then(|| as_path.canonicalize().ok()).flatten();
+    }
+    let names: Vec<String> = if cfg!(target_os = "windows") && as_path.extension().is_none() {
+        ["exe", "cmd", "bat"].iter().map(|ext| format!("{}.{}", binary, ext)).collect()
+    } else {
+        vec![binary.to_string()]
+    };
+    env::split_paths(path_var)
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .flat_map(|dir| names.iter().map(move |name| dir.join(name)))
+        .find(|candidate| is_executable_file(candidate))
+        .map(|found| found.canonicalize().unwrap_or(found))
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct AvailableCli {
+    /// Binary name searched for ("bd" or "br")
+    pub name: String,
+    pub path: String,
+    /// `--version` output, e.g. "bd version 0.49.6 (Homebrew)"
+    pub version: String,
+    #[serde(rename = "clientType")]
+    pub client_type: String,
+}
+
+/// Every working bd/br on the extended PATH plus the usual `go install` / `cargo install`
+/// locations, so settings can offer a picker instead of a typed path.
+#[tauri::command]
+async fn detect_available_clis() -> Vec<AvailableCli> {
+    let mut dirs: Vec<PathBuf> = env::split_paths(&get_extended_path()).collect();
+    if let Some(home) = user_home_dir() {
+        dirs.push(home.join(".cargo").join("bin"));
+        dirs.push(home.join("go").join("bin"));
+    }
+    let path_var = env::join_paths(&dirs).map(|p| p.to_string_lossy().to_string()).unwrap_or_default();
+    let found = detect_clis_in(&path_var);
+    log_info!("[cli_detect] Found {} CLI binaries", found.len());
+    found
+}
+
+fn user_home_dir() -> Option<PathBuf> {
+    let var = if cfg!(target_os = "windows") { "USERPROFILE" } else { "HOME" };
+    env::var_os(var).filter(|h| !h.is_empty()).map(PathBuf::from)
+}
+
+/// bd/br binaries in the directories of `path_var`, each resolved path listed once.
+/// Binaries whose `--version` fails are skipped.
+fn detect_clis_in(path_var: &str) -> Vec<AvailableCli> {
+    let mut seen = std::collections::HashSet::new();
+    let mut found = Vec::new();
+    for name in ["bd", "br"] {
+        for dir in env::split_paths(path_var).filter(|d| !d.as_os_str().is_empty()) {
+            let Some(path) = find_in_path(name, &dir.to_string_lossy()) else { continue };
+            if !seen.insert(path.clone()) {
+                continue;
+            }
+            let output = match version_output(&mut new_command(&path.to_string_lossy())) {
+                Ok(output) if output.status.success() => output,
+                Ok(output) => {
+                    log_warn!("[cli_detect] {} --version exited with {}", path.display(), output.status);
+                    continue;
+                }
+                Err(e) => {
+                    log_warn!("[cli_detect] Could not run {}: {}", path.display(), e);
+                    continue;
+                }
+            };
+            let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            found.push(AvailableCli {
+                name: name.to_string(),
+                path: path.to_string_lossy().to_string(),
+                client_type: client_type_name(detect_cli_client(&version)).to_string(),
+                version,
+            });
+        }
+    }
+    found
+}
+
+/// A wrapper script or wedged binary can hang on `--version`; probing gives up after this.
+const VERSION_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// `--version` run from the temp dir (so bd doesn't auto-migrate a project in the
+/// current dir), killed after `VERSION_PROBE_TIMEOUT`.
+fn version_output(cmd: &mut Command) -> std::io::Result<std::process::Output> {
+    cmd.arg("--version").current_dir(std::env::temp_dir());
+    output_with_timeout(cmd, Some(VERSION_PROBE_TIMEOUT))
+}
+
+fn is_executable_file(path: &std::path::Path) -> bool {
+    let meta = match fs::metadata(path) {
+        Ok(meta) if meta.is_file() => meta,
+        _ => return false,
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        meta.permissions().mode() & 0o111 != 0
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = meta;
+        true
+    }
+}
+
+#[tauri::command]
+async fn get_config() -> AppConfig {
+    load_config()
+}
+
+/// Persist the full settings at once and apply them. A changed CLI binary is validated first.
+#[tauri::command]
+async fn set_config(config: AppConfig) -> Result<(), String> {
+    if config.cli_binary != get_cli_binary() {
+        validate_cli_binary_internal(&config.cli_binary)?;
+    }
+    {
+        let _guard = CONFIG_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        save_config(&config)?;
+    }
+    apply_config(&config);
+    log_info!("[config] Settings saved");
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_env_passthrough() -> Option<Vec<String>> {
+    ENV_PASSTHROUGH.lock().unwrap().clone()
+}
+
+/// Set the opt-in allow-list; `None` goes back to plain inheritance.
+#[tauri::command]
+async fn set_env_passthrough(patterns: Option<Vec<String>>) -> Result<(), String> {
+    let patterns: Option<Vec<String>> = patterns.map(|patterns| {
+        patterns.iter()
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+            .collect()
+    });
+    update_config(|config| config.env_passthrough = patterns.clone())?;
+    log_info!("[config] Env passthrough set to: {:?}", patterns);
+    *ENV_PASSTHROUGH.lock().unwrap() = patterns;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_project_config(cwd: Option<String>) -> ProjectConfig {
+    load_project_config(cwd.as_deref())
+}
+
+#[tauri::command]
+async fn set_project_config(cwd: Option<String>, config: ProjectConfig) -> Result<(), String> {
+    let key = resolve_working_dir(cwd.as_deref());
+    update_config(|app_config| {
+        if config == ProjectConfig::default() {
+            app_config.projects.remove(&key);
+        } else {
+            app_config.projects.insert(key.clone(), config);
+        }
+    })?;
+    log_info!("[config] Project config saved for {}", key);
+    Ok(())
+}
+
+#[tauri::command]
+async fn set_cli_binary_path(path: String) -> Result<String, String> {
+    let binary = if path.trim().is_empty() { "bd".to_string() } else { path.trim().to_string() };
+
+    // Validate the binary first
+    let version = validate_cli_binary_internal(&binary)?;
+
+    // Update global state and reset version cache (new binary may be different version)
+    *CLI_BINARY.lock().unwrap() = binary.clone();
+    reset_bd_version_cache();
+
+    // Persist to config file
+    update_config(|config| config.cli_binary = binary.clone())?;
+
+    log_info!("[config] CLI binary set to: {} ({})", binary, version);
+    Ok(version)
+}
+
+#[tauri::command]
+async fn validate_cli_binary(path: String) -> Result<String, String> {
+    let binary = if path.trim().is_empty() { "bd".to_string() } else { path.trim().to_string() };
+    validate_cli_binary_internal(&binary)
+}
+
+fn validate_cli_binary_internal(binary: &str) -> Result<String, String> {
+    // Security: reject shell metacharacters — Command::new() doesn't use a shell,
+    // but defense-in-depth prevents any future misuse
+    let forbidden = [';', '|', '&', '$', '`', '>', '<', '(', ')', '{', '}', '!', '\n', '\r'];
+    if binary.chars().any(|c| forbidden.contains(&c)) {
+        return Err("Invalid binary path: contains shell metacharacters".to_string());
+    }
+    if binary.contains("..") {
+        return Err("Invalid binary path: directory traversal not allowed".to_string());
+    }
+
+    match new_command(binary)
+        .arg("--version")
+        .env("PATH", get_extended_path())
+        .output()
+    {
+        Ok(output) if output.status.success() => {
+            let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            if version.is_empty() {
+                Err(format!("'{}' returned empty version output", binary))
+            } else {
+                let affected = parse_bd_version(&version)
+                    .is_some_and(|v| has_dolt_concurrency_bug(detect_cli_client(&version), v));
+                if affected {
+                    log_warn!("[cli] {}: {}", version, DOLT_CONCURRENCY_WARNING);
+                }
+                Ok(version)
+            }
+        }
+        Ok(output) => {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            Err(format!("'{}' failed: {}", binary, if stderr.is_empty() { "unknown error".to_string() } else { stderr }))
+        }
+        Err(e) => {
+            Err(format!("'{}' not found or not executable: {}", binary, e))
+        }
+    }
+}
+
+#[tauri::command]
+async fn open_image_file(path: String) -> Result<(), String> {
+    log_info!("[open_image_file] Opening: {}", path);
+
+    // Security: Only allow image file extensions
+    let allowed_extensions = ["png", "jpg", "jpeg", "gif", "webp", "bmp", "svg", "ico", "tiff", "tif"];
+    let path_lower = path.to_lowercase();
+    let is_image = allowed_extensions.iter().any(|ext| path_lower.ends_with(&format!(".{}", ext)));
+
+    if !is_image {
+        return Err("Only image files are allowed".to_string());
+    }
+
+    // Verify file exists
+    if !std::path::Path::new(&path).exists() {
+        return Err(format!("File not found: {}", path));
+    }
+
+    // Security: Canonicalize to resolve symlinks/.. and verify inside .beads/attachments/
+    let canonical = std::path::Path::new(&path).canonicalize()
+        .map_err(|e| format!("Failed to resolve path: {}", e))?;
+    let canonical_str = canonical.to_string_lossy();
+    if !canonical_str.contains("/.beads/attachments/") {
+        log_warn!("[open_image_file] Refusing to open file outside attachments: {} (resolved: {})", path, canonical_str);
+        return Err("Can only open files inside .beads/attachments/".to_string());
+    }
+
+    // Use platform-specific command to open file with default application
+    #[cfg(target_os = "macos")]
+    {
+        Command::new("open")
+            .arg(&path)
+            .spawn()
+            .map_err(|e| format!("Failed to open file: {}", e))?;
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        new_command("cmd")
+            .args(["/C", "start", "", &path])
+            .spawn()
+            .map_err(|e| format!("Failed to open file: {}", e))?;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        Command::new("xdg-open")
+            .arg(&path)
+            .spawn()
+            .map_err(|e| format!("Failed to open file: {}", e))?;
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImageData {
+    pub base64: String,
+    pub mime_type: String,
+}
+
+#[tauri::command]
+async fn read_image_file(path: String) -> Result<ImageData, String> {
+    log_info!("[read_image_file] Reading: {}", path);
+
+    // Security: Only allow image file extensions
+    let allowed_extensions: &[(&str, &str)] = &[
+        ("png", "image/png"),
+        ("jpg", "image/jpeg"),
+        ("jpeg", "image/jpeg"),
+        ("gif", "image/gif"),
+        ("webp", "image/webp"),
+        ("bmp", "image/bmp"),
+        ("svg", "image/svg+xml"),
+        ("ico", "image/x-icon"),
+        ("tiff", "image/tiff"),
+        ("tif", "image/tiff"),
+    ];
+
+    let path_lower = path.to_lowercase();
+    let mime_type = allowed_extensions
+        .iter()
+        .find(|(ext, _)| path_lower.ends_with(&format!(".{}", ext)))
+        .map(|(_, mime)| *mime);
+
+    let mime_type = match mime_type {
+        Some(m) => m.to_string(),
+        None => return Err("Only image files are allowed".to_string()),
+    };
+
+    // Verify file exists
+    if !std::path::Path::new(&path).exists() {
+        return Err(format!("File not found: {}", path));
+    }
+
+    // Security: Canonicalize to resolve symlinks/.. and verify inside .beads/attachments/
+    let canonical = std::path::Path::new(&path).canonicalize()
+        .map_err(|e| format!("Failed to resolve path: {}", e))?;
+    let canonical_str = canonical.to_string_lossy();
+    if !canonical_str.contains("/.beads/attachments/") {
+        log_warn!("[read_image_file] Refusing to read file outside attachments: {} (resolved: {})", path, canonical_str);
+        return Err("Can only read files inside .beads/attachments/".to_string());
+    }
+
+    // Read file and encode as base64
+    let data = fs::read(&path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let base64 = base64_encode(&data);
+
+    Ok(ImageData { base64, mime_type })
+}
+
+fn base64_encode(data: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut result = String::with_capacity(data.len().div_ceil(3) * 4);
+
+    for chunk in data.chunks(3) {
+        let mut buf = [0u8; 3];
+        buf[..chunk.len()].copy_from_slice(chunk);
+
+        let n = ((buf[0] as u32) << 16) | ((buf[1] as u32) << 8) | (buf[2] as u32);
+
+        result.push(ALPHABET[(n >> 18) as usize & 0x3F] as char);
+        result.push(ALPHABET[(n >> 12) as usize & 0x3F] as char);
+
+        if chunk.len() > 1 {
+            result.push(ALPHABET[(n >> 6) as usize & 0x3F] as char);
+        } else {
+            result.push('=');
+        }
+
+        if chunk.len() > 2 {
+            result.push(ALPHABET[n as usize & 0x3F] as char);
+        } else {
+            result.push('=');
+        }
+    }
+
+    result
+}
+
+/// Standard base64 (RFC 4648, `+/`), the inverse of `base64_encode`. Whitespace
+/// (wrapped clipboard text) is ignored and the `=` padding may be left off; anything
+/// else that isn't valid base64 is an error rather than silently dropped.
+fn base64_decode(input: &str) -> Result<Vec<u8>, String> {
+    fn value(c: u8) -> Option<u32> {
+        match c {
+            b'A'..=b'Z' => Some((c - b'A') as u32),
+            b'a'..=b'z' => Some((c - b'a') as u32 + 26),
+            b'0'..=b'9' => Some((c - b'0') as u32 + 52),
+            b'+' => Some(62),
+            b'/' => Some(63),
+            _ => None,
+        }
+    }
+
+    let bytes: Vec<u8> = input.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    let padding = bytes.iter().rev().take_while(|&&b| b == b'=').count();
+    let data = &bytes[..bytes.len() - padding];
+    if padding > 2 || (padding > 0 && bytes.len() % 4 != 0) || data.len() % 4 == 1 {
+        return Err("Invalid base64: bad length or padding".to_string());
+    }
+
+    let mut result = Vec::with_capacity(data.len() * 3 / 4);
+    for chunk in data.chunks(4) {
+        let mut n = 0u32;
+        for (i, &c) in chunk.iter().enumerate() {
+            let v = value(c).ok_or_else(|| format!("Invalid base64 character: {:?}", c as char))?;
+            n |= v << (18 - 6 * i);
+        }
+        result.push((n >> 16) as u8);
+        if chunk.len() > 2 {
+            result.push((n >> 8) as u8);
+        }
+        if chunk.len() > 3 {
+            result.push(n as u8);
+        }
+    }
+    Ok(result)
+}
+
+#[tauri::command]
+async fn purge_orphan_attachments(project_path: String) -> Result<PurgeResult, String> {
+    log::info!("[purge_orphan_attachments] project: {}", project_path);
+
+    // Calculate absolute project path (reusing pattern from bd_delete)
+    let abs_project_path = if project_path == "." || project_path.is_empty() {
+        env::current_dir().map_err(|e| format!("Failed to get current directory: {}", e))?
+    } else {
+        let p = PathBuf::from(&project_path);
+        if p.is_relative() {
+            let cwd = env::current_dir()
+                .map_err(|e| format!("Failed to get current directory: {}", e))?;
+            cwd.join(&p)
+        } else {
+            p
+        }
+    };
+
+    let abs_project_path = abs_project_path
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve project path: {}", e))?;
+
+    // Build attachments directory path
+    let attachments_dir = abs_project_path.join(".beads").join("attachments");
+
+    // If attachments directory doesn't exist, nothing to purge
+    if !attachments_dir.exists() || !attachments_dir.is_dir() {
+        log::info!("[purge_orphan_attachments] No attachments directory found");
+        return Ok(PurgeResult {
+            deleted_count: 0,
+            deleted_folders: vec![],
+        });
+    }
+
+    // Get list of all existing issue IDs via bd list --all
+    let existing_ids: std::collections::HashSet<String> = {
+        let output = execute_bd("list", &["--all".to_string(), "--limit=0".to_string()], Some(&abs_project_path.to_string_lossy()))?;
+        let issues = parse_issues_tolerant(&output, "purge_orphan_attachments")?;
+        issues.into_iter().map(|i| i.id).collect()
+    };
+
+    log::info!("[purge_orphan_attachments] Found {} existing issues", existing_ids.len());
+
+    // List all subdirectories in attachments folder
+    let entries = fs::read_dir(&attachments_dir)
+        .map_err(|e| format!("Failed to read attachments directory: {}", e))?;
+
+    let mut deleted_folders: Vec<String> = Vec::new();
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+
+        let folder_name = match path.file_name() {
+            Some(name) => name.to_string_lossy().to_string(),
+            None => continue,
+        };
+
+        // Check if this folder corresponds to an existing issue (folders use short IDs)
+        let is_owned = existing_ids.iter().any(|id| issue_short_id(id) == folder_name);
+        if !is_owned {
+            log::info!("[purge_orphan_attachments] Deleting orphan folder: {}", folder_name);
+            if let Err(e) = fs::remove_dir_all(&path) {
+                log::warn!("[purge_orphan_attachments] Failed to delete {}: {}", folder_name, e);
+            } else {
+                deleted_folders.push(folder_name);
+            }
+        }
+    }
+
+    let deleted_count = deleted_folders.len();
+    log::info!("[purge_orphan_attachments] Purged {} orphan folders", deleted_count);
+
+    Ok(PurgeResult {
+        deleted_count,
+        deleted_folders,
+    })
+}
+
+/// Sanitize a filename for safe storage and br JSONL compatibility.
+/// Converts to kebab-case, strips diacritics, removes unsafe chars.
+/// Example: "Screenshot 2026-02-24 à 10.30.png" → "screenshot-2026-02-24-a-10-30.png"
+fn sanitize_filename(filename: &str) -> String {
+    // Split into stem and extension
+    let (stem, ext) = match filename.rfind('.') {
+        Some(pos) => (&filename[..pos], &filename[pos..]),
+        None => (filename, ""),
+    };
+
+    // Strip diacritics by replacing common accented chars, then lowercase + kebab-case
+    let mut sanitized = String::with_capacity(stem.len());
+    for c in stem.chars() {
+        let replacement = match c {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' => "a",
+            'è' | 'é' | 'ê' | 'ë' | 'È' | 'É' | 'Ê' | 'Ë' => "e",
+            'ì' | 'í' | 'î' | 'ï' | 'Ì' | 'Í' | 'Î' | 'Ï' => "i",
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' => "o",
+            'ù' | 'ú' | 'û' | 'ü' | 'Ù' | 'Ú' | 'Û' | 'Ü' => "u",
+            'ñ' | 'Ñ' => "n",
+            'ç' | 'Ç' => "c",
+            'ß' => "ss",
+            'æ' | 'Æ' => "ae",
+            'œ' | 'Œ' => "oe",
+            'ý' | 'ÿ' | 'Ý' => "y",
+            'A'..='Z' => { sanitized.push((c as u8 + 32) as char); continue; },
+            'a'..='z' | '0'..='9' => { sanitized.push(c); continue; },
+            '-' => { sanitized.push('-'); continue; },
+            ' ' | '_' | '.' => { sanitized.push('-'); continue; },
+            _ => "-",
+        };
+        sanitized.push_str(replacement);
+    }
+
+    // Collapse multiple consecutive dashes and trim
+    let mut result = String::with_capacity(sanitized.len());
+    let mut prev_dash = false;
+    for c in sanitized.chars() {
+        if c == '-' {
+            if !prev_dash {
+                result.push('-');
+            }
+            prev_dash = true;
+        } else {
+            result.push(c);
+            prev_dash = false;
+        }
+    }
+    let result = result.trim_matches('-');
+
+    let ext_lower = ext.to_lowercase();
+    if result.is_empty() {
+        format!("file{}", ext_lower)
+    } else {
+        format!("{}{}", result, ext_lower)
+    }
+}
+
+// ============================================================================
+// Attachment helpers
+// ============================================================================
+
+/// Image file extensions supported for attachment preview
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp", "bmp", "svg", "ico", "tiff", "tif"];
+
+/// Markdown file extensions supported for attachment preview
+const MARKDOWN_EXTENSIONS: &[&str] = &["md", "markdown"];
+
+/// Extract the short ID from a full issue ID by stripping the project prefix.
+/// e.g. "beads-manager-2qk" → "2qk", "kybio-1pxe" → "1pxe",
+///      "kybio-front-nuxt-4-466d" → "466d", "beads-manager-02e.1" → "02e.1"
+/// Falls back to the full ID if no prefix separator is found.
+fn issue_short_id(full_id: &str) -> &str {
+    // The short ID is after the last '-' that isn't followed by another segment
+    // containing only digits (which would be part of the prefix like "nuxt-4").
+    // Strategy: find the last '-' where everything after it matches [a-z0-9.]+ (the short ID).
+    // But "kybio-front-nuxt-4-466d": after last '-' is "466d" ✓
+    // "kybio-front-nuxt-4": after last '-' is "4" which could be a short ID or prefix part.
+    // Since we only call this with real issue IDs (not project names), the last segment is always the short ID.
+    match full_id.rfind('-') {
+        Some(pos) => &full_id[pos + 1..],
+        None => full_id,
+    }
+}
+
+/// Resolve the attachment directory for an issue.
+/// Always uses short ID: .beads/attachments/{short_id}/
+/// Rejects ids whose short id could leave `attachments_dir` (`a-../x`, `a/b`): issue
+/// ids reach here from the webview.
+fn resolve_attachment_dir(attachments_dir: &std::path::Path, issue_id: &str) -> Result<PathBuf, String> {
+    let short = issue_short_id(issue_id);
+    if short.is_empty() || short.contains("..") || short.contains('/') || short.contains('\\') {
+        return Err(format!("Invalid issue id for attachments: {}", issue_id));
+    }
+    Ok(attachments_dir.join(short))
+}
+
+/// Classify a filename as "image", "markdown", or "other"
+fn classify_attachment(filename: &str) -> &'static str {
+    let lower = filename.to_lowercase();
+    if IMAGE_EXTENSIONS.iter().any(|ext| lower.ends_with(&format!(".{}", ext))) {
+        "image"
+    } else if MARKDOWN_EXTENSIONS.iter().any(|ext| lower.ends_with(&format!(".{}", ext))) {
+        "markdown"
+    } else {
+        "other"
+    }
+}
+
+/// Resolve a duplicate filename: image.png → image-1.png → image-2.png
+fn resolve_duplicate_filename(dir: &std::path::Path, name: &str) -> String {
+    if !dir.join(name).exists() {
+        return name.to_string();
+    }
+    let (stem, ext) = match name.rfind('.') {
+        Some(pos) => (&name[..pos], &name[pos..]),
+        None => (name, ""),
+    };
+    for i in 1..1000 {
+        let candidate = format!("{}-{}{}", stem, i, ext);
+        if !dir.join(&candidate).exists() {
+            return candidate;
+        }
+    }
+    // Fallback with timestamp
+    let ts = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    format!("{}-{}{}", stem, ts, ext)
+}
+
+/// Rename the attachment folder `from` to `to` inside `attachments_dir`. An existing
+/// `to` is left alone (an error, so the caller can report it) rather than merged into.
+/// Returns false when there was nothing to move.
+fn rename_attachment_folder(attachments_dir: &std::path::Path, from: &str, to: &str) -> Result<bool, String> {
+    let source = attachments_dir.join(from);
+    if from == to || !source.is_dir() {
+        return Ok(false);
+    }
+    let target = attachments_dir.join(to);
+    if target.exists() {
+        return Err(format!("Cannot rename '{}' → '{}': target already exists", from, to));
+    }
+    std::fs::rename(&source, &target)
+        .map_err(|e| format!("Failed to rename '{}' → '{}': {}", from, to, e))?;
+    Ok(true)
+}
+
+// ============================================================================
+// Attachment Refs Migration v3 — filesystem-only
+// ============================================================================
+
+/// Check if a ref is a "real" external reference (Redmine, GitHub, or other URL/ID).
+/// Returns false for att: refs, local file paths, cleared: sentinels.
+fn is_real_external_ref(r: &str) -> bool {
+    let trimmed = r.trim();
+    if trimmed.is_empty() { return false; }
+    if trimmed.starts_with("cleared:") { return false; }
+    if trimmed.starts_with("att:") { return false; }
+    // Local file paths (absolute or relative .beads/)
+    if trimmed.starts_with('/') { return false; }
+    if trimmed.starts_with(".beads/") { return false; }
+    // Anything with path separators inside .beads or attachments is local
+    if trimmed.contains("/attachments/") || trimmed.contains("/.beads/") { return false; }
+    true
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RefsMigrationStatus {
+    needs_migration: bool,
+    ref_count: u32,
+    just_migrated: bool,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MigrateRefsResult {
+    success: bool,
+    refs_updated: u32,
+}
+
+/// Check if a project needs attachment refs migration v3.
+/// v3 strips all attachment refs (att:, local paths) from external_ref,
+/// keeping only real external refs (Redmine, GitHub, URLs).
+/// Returns quickly if the .migrated-attachments marker file exists.
+#[tauri::command]
+async fn check_refs_migration(cwd: Option<String>) -> Result<RefsMigrationStatus, String> {
+    let working_dir = cwd
+        .or_else(|| env::var("BEADS_PATH").ok())
+        .unwrap_or_else(|| {
+            env::current_dir()
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_else(|_| ".".to_string())
         });
+
+    let beads_dir = PathBuf::from(&working_dir).join(".beads");
+    if !beads_dir.exists() {
+        return Ok(RefsMigrationStatus { needs_migration: false, ref_count: 0, just_migrated: false });
     }
 
-    if !response.status().is_success() {
-        return Err(format!("GitHub API returned status: {}", response.status()));
+    // Already migrated to v3?
+    if beads_dir.join(".migrated-attachments").exists() {
+        // Check if auto-migration just ran (notify signal)
+        let notify_path = beads_dir.join(".migrated-attachments-notify");
+        let just_migrated = notify_path.exists();
+        if just_migrated {
+            let _ = std::fs::remove_file(&notify_path);
+        }
+        return Ok(RefsMigrationStatus { needs_migration: false, ref_count: 0, just_migrated });
     }
 
-    let release: GitHubRelease = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse release info: {}", e))?;
+    let jsonl_path = beads_dir.join("issues.jsonl");
+    if !jsonl_path.exists() {
+        let _ = std::fs::write(beads_dir.join(".migrated-attachments"), "");
+        return Ok(RefsMigrationStatus { needs_migration: false, ref_count: 0, just_migrated: false });
+    }
 
-    let latest_version = release.tag_name.trim_start_matches('v').to_string();
-    let has_update = compare_versions(CURRENT_VERSION, &latest_version);
+    // Scan JSONL for refs that need cleanup (non-real external refs)
+    let content = std::fs::read_to_string(&jsonl_path)
+        .map_err(|e| format!("Failed to read issues.jsonl: {}", e))?;
 
-    let download_url = find_platform_asset(&release.assets)
-        .map(|a| a.browser_download_url.clone());
+    let mut ref_count: u32 = 0;
 
-    // Fetch CHANGELOG.md via GitHub API (raw.githubusercontent CDN ignores query params for caching)
-    let changelog = with_github_auth(
-        client
-            .get("https://api.github.com/repos/w3dev33/beads-task-issue-tracker/contents/CHANGELOG.md")
-            .header("Accept", "application/vnd.github.raw+json")
-    )
-        .send()
-        .await
-        .ok()
-        .and_then(|r| if r.status().is_success() { Some(r) } else { None });
-    let changelog_text = match changelog {
-        Some(r) => r.text().await.ok(),
-        None => None,
+    for line in content.lines() {
+        if line.trim().is_empty() { continue; }
+        let v: serde_json::Value = match serde_json::from_str(line) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+        if let Some(ext_ref) = v.get("external_ref").and_then(|r| r.as_str()) {
+            if ext_ref.is_empty() { continue; }
+            let refs: Vec<&str> = ext_ref.split(|c: char| c == '\n' || c == '|').collect();
+            for r in &refs {
+                let trimmed = r.trim();
+                if !trimmed.is_empty() && !is_real_external_ref(trimmed) {
+                    ref_count += 1;
+                    break; // One bad ref per issue is enough to flag it
+                }
+            }
+        }
+    }
+
+    // Also check if attachment folders need renaming (full-id → short-id)
+    let mut folder_work_count: u32 = 0;
+    let attachments_dir = beads_dir.join("attachments");
+    if attachments_dir.exists() {
+        if let Ok(entries) = std::fs::read_dir(&attachments_dir) {
+            for entry in entries.flatten() {
+                if !entry.path().is_dir() { continue; }
+                let name = entry.file_name().to_string_lossy().to_string();
+                if issue_short_id(&name) != name {
+                    folder_work_count += 1;
+                }
+            }
+        }
+    }
+
+    let total = ref_count + folder_work_count;
+    if total == 0 {
+        let _ = std::fs::write(beads_dir.join(".migrated-attachments"), "");
+        return Ok(RefsMigrationStatus { needs_migration: false, ref_count: 0, just_migrated: false });
+    }
+
+    log_info!("[refs_migration_v3] Project needs migration: {} ref(s) to clean, {} folder(s) to update", ref_count, folder_work_count);
+    Ok(RefsMigrationStatus { needs_migration: true, ref_count: total, just_migrated: false })
+}
+
+/// Perform the attachment refs migration v3 (filesystem-only).
+/// Delegates to ensure_refs_migrated_v3 which handles backup, cleanup, dedup, and marker.
+/// The br sync is NOT called here — it will happen naturally after via sync_bd_database.
+#[tauri::command]
+async fn migrate_attachment_refs(cwd: Option<String>, confirm: Option<bool>) -> Result<MigrateRefsResult, String> {
+    require_safe_mode_confirmation("Attachment refs migration", confirm)?;
+    let working_dir = cwd
+        .or_else(|| env::var("BEADS_PATH").ok())
+        .unwrap_or_else(|| {
+            env::current_dir()
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_else(|_| ".".to_string())
+        });
+
+    let beads_dir = PathBuf::from(&working_dir).join(".beads");
+    ensure_refs_migrated_v3(&beads_dir, &working_dir);
+    show_cache_invalidate(&working_dir);
+    Ok(MigrateRefsResult { success: true, refs_updated: 0 })
+}
+
+/// Largest pasted image `write_image_file` accepts (decoded size)
+const MAX_PASTED_IMAGE_BYTES: usize = 20 * 1024 * 1024;
+
+/// Save a pasted image (base64, optionally as a `data:` URL) into the issue's
+/// attachment dir under the sanitized `filename`. Returns the stored filename,
+/// suffixed with -N when that name is taken.
+#[tauri::command]
+async fn write_image_file(
+    project_path: String,
+    issue_id: String,
+    filename: String,
+    base64: String,
+) -> Result<String, String> {
+    log::info!("[write_image_file] project: {}, issue: {}, file: {}", project_path, issue_id, filename);
+
+    let sanitized = sanitize_filename(&filename);
+    if classify_attachment(&sanitized) != "image" {
+        return Err("Only image files are allowed".to_string());
+    }
+    let encoded = match base64.split_once(";base64,") {
+        Some((prefix, data)) if prefix.starts_with("data:") => data,
+        _ => base64.as_str(),
     };
+    let data = base64_decode(encoded)?;
+    if data.is_empty() {
+        return Err("Image data is empty".to_string());
+    }
+    if data.len() > MAX_PASTED_IMAGE_BYTES {
+        return Err(format!("Image is larger than {} MB", MAX_PASTED_IMAGE_BYTES / (1024 * 1024)));
+    }
 
-    Ok(UpdateInfo {
-        current_version: CURRENT_VERSION.to_string(),
-        latest_version,
-        has_update,
-        release_url: release.html_url,
-        download_url,
-        platform: get_platform_string().to_string(),
-        release_notes: changelog_text.or(release.body),
-    })
+    let attachments_dir = absolute_project_path(&project_path)?.join(".beads").join("attachments");
+    let dest_dir = resolve_attachment_dir(&attachments_dir, &issue_id)?;
+    fs::create_dir_all(&dest_dir)
+        .map_err(|e| format!("Failed to create attachments directory: {}", e))?;
+
+    let dest_filename = resolve_duplicate_filename(&dest_dir, &sanitized);
+    let dest_path = dest_dir.join(&dest_filename);
+    fs::write(&dest_path, &data).map_err(|e| format!("Failed to write image: {}", e))?;
+    log::info!("[write_image_file] Wrote {} bytes to: {}", data.len(), dest_path.display());
+    Ok(dest_filename)
+}
+
+/// Absolute, canonical project path ("." or empty = current directory).
+fn absolute_project_path(project_path: &str) -> Result<PathBuf, String> {
+    let abs_project_path = if project_path == "." || project_path.is_empty() {
+        env::current_dir().map_err(|e| format!("Failed to get current directory: {}", e))?
+    } else {
+        let p = PathBuf::from(project_path);
+        if p.is_relative() {
+            let cwd = env::current_dir()
+                .map_err(|e| format!("Failed to get current directory: {}", e))?;
+            cwd.join(&p)
+        } else {
+            p
+        }
+    };
+
+    abs_project_path
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve project path: {}", e))
+}
+
+#[derive(Debug, Serialize)]
+pub struct CopiedAttachment {
+    pub filename: String,
+    /// True when an identical file was already attached and no copy was made
+    pub deduplicated: bool,
+}
+
+#[tauri::command]
+async fn copy_file_to_attachments(
+    project_path: String,
+    source_path: String,
+    issue_id: String,
+) -> Result<CopiedAttachment, String> {
+    log::info!(
+        "[copy_file_to_attachments] project: {}, source: {}, issue: {}",
+        project_path,
+        source_path,
+        issue_id
+    );
+    attach_file(&project_path, &source_path, &issue_id).map(|(_, copied)| copied)
 }
 
-#[tauri::command]
-async fn check_for_updates_demo() -> Result<UpdateInfo, String> {
-    let client = github_client()?;
+/// Validate and copy `source_path` into the issue's attachment dir.
+/// Returns the attachment dir along with the stored file.
+fn attach_file(
+    project_path: &str,
+    source_path: &str,
+    issue_id: &str,
+) -> Result<(PathBuf, CopiedAttachment), String> {
 
-    let response = with_github_auth(client.get(GITHUB_RELEASES_URL))
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch releases: {}", e))?;
+    // Validate file extension (images + markdown)
+    let source_lower = source_path.to_lowercase();
+    let is_allowed = IMAGE_EXTENSIONS.iter().chain(MARKDOWN_EXTENSIONS.iter())
+        .any(|ext| source_lower.ends_with(&format!(".{}", ext)));
 
-    if !response.status().is_success() {
-        return Err(format!("GitHub API returned status: {}", response.status()));
+    if !is_allowed {
+        return Err("Only image and markdown files are allowed".to_string());
     }
 
-    let release: GitHubRelease = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse release info: {}", e))?;
+    // Verify source file exists
+    let source = PathBuf::from(source_path);
+    if !source.exists() {
+        return Err(format!("Source file not found: {}", source_path));
+    }
 
-    let latest_version = release.tag_name.trim_start_matches('v').to_string();
+    let abs_project_path = absolute_project_path(project_path)?;
 
-    let download_url = find_platform_asset(&release.assets)
-        .map(|a| a.browser_download_url.clone());
+    // Build destination directory: {project}/.beads/attachments/{short_id}/
+    let attachments_dir = abs_project_path.join(".beads").join("attachments");
+    let dest_dir = resolve_attachment_dir(&attachments_dir, issue_id)?;
 
-    // Fetch CHANGELOG.md via GitHub API (raw.githubusercontent CDN ignores query params for caching)
-    let changelog = with_github_auth(
-        client
-            .get("https://api.github.com/repos/w3dev33/beads-task-issue-tracker/contents/CHANGELOG.md")
-            .header("Accept", "application/vnd.github.raw+json")
-    )
-        .send()
-        .await
-        .ok()
-        .and_then(|r| if r.status().is_success() { Some(r) } else { None });
-    let changelog_text = match changelog {
-        Some(r) => r.text().await.ok(),
-        None => None,
-    };
+    // Create directory if needed
+    fs::create_dir_all(&dest_dir)
+        .map_err(|e| format!("Failed to create attachments directory: {}", e))?;
 
-    // Demo mode: force has_update = true, fake current version as 0.0.0
-    Ok(UpdateInfo {
-        current_version: "0.0.0".to_string(),
-        latest_version,
-        has_update: true,
-        release_url: release.html_url,
-        download_url,
-        platform: get_platform_string().to_string(),
-        release_notes: changelog_text.or(release.body),
-    })
+    // Return just the filename (frontend doesn't need to store it in external_ref)
+    let copied = copy_into_attachment_dir(&source, &dest_dir)?;
+    Ok((dest_dir, copied))
 }
 
-#[tauri::command]
-async fn check_bd_cli_update() -> Result<BdCliUpdateInfo, String> {
-    // Get current bd version
-    let version_str = get_bd_version().await;
-    if version_str.contains("not found") {
-        return Err("bd CLI not found".to_string());
+/// Copy `source` into `dest_dir` under its sanitized name, unless a file with the same
+/// content is already there — then that file's name is returned instead.
+/// Same name with different content still gets a -N suffix.
+fn copy_into_attachment_dir(source: &std::path::Path, dest_dir: &std::path::Path) -> Result<CopiedAttachment, String> {
+    let data = fs::read(source).map_err(|e| format!("Failed to read file: {}", e))?;
+    if let Some(existing) = find_identical_file(dest_dir, &data) {
+        log::info!("[copy_file_to_attachments] Identical file already attached: {}", existing);
+        return Ok(CopiedAttachment { filename: existing, deduplicated: true });
     }
 
-    // Parse semver from version string
-    let current_tuple = parse_bd_version(&version_str)
-        .ok_or_else(|| format!("Could not parse version from: {}", version_str))?;
-    let current_version = format!("{}.{}.{}", current_tuple.0, current_tuple.1, current_tuple.2);
+    // Sanitize the original filename and handle duplicates
+    let raw_filename = source
+        .file_name()
+        .ok_or_else(|| "Invalid source filename".to_string())?
+        .to_string_lossy()
+        .to_string();
+    let sanitized = sanitize_filename(&raw_filename);
+    let dest_filename = resolve_duplicate_filename(dest_dir, &sanitized);
+    let dest_path = dest_dir.join(&dest_filename);
 
-    // Determine the correct GitHub repo based on client type (bd vs br)
-    let client_type = detect_cli_client(&version_str);
-    let api_url = match client_type {
-        CliClient::Br => "https://api.github.com/repos/Dicklesworthstone/beads_rust/releases/latest",
-        _ => "https://api.github.com/repos/steveyegge/beads/releases/latest",
-    };
-    let releases_url = match client_type {
-        CliClient::Br => "https://github.com/Dicklesworthstone/beads_rust/releases",
-        _ => "https://github.com/steveyegge/beads/releases",
-    };
+    fs::write(&dest_path, &data).map_err(|e| format!("Failed to copy file: {}", e))?;
+    log::info!("[copy_file_to_attachments] Copied to: {}", dest_path.display());
+    Ok(CopiedAttachment { filename: dest_filename, deduplicated: false })
+}
 
-    let client = github_client()?;
+/// Name of a file in `dir` whose content equals `data`. Sizes are compared first,
+/// so only same-size candidates are read.
+fn find_identical_file(dir: &std::path::Path, data: &[u8]) -> Option<String> {
+    let entries = fs::read_dir(dir).ok()?;
+    let mut names: Vec<String> = entries
+        .flatten()
+        .filter(|e| e.metadata().map(|m| m.is_file() && m.len() == data.len() as u64).unwrap_or(false))
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+    names.sort();
+    names.into_iter().find(|name| fs::read(dir.join(name)).map(|c| c == data).unwrap_or(false))
+}
 
-    let response = with_github_auth(client.get(api_url))
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch releases: {}", e))?;
+// ============================================================================
+// Filesystem-based Attachment Commands
+// ============================================================================
 
-    if !response.status().is_success() {
-        return Err(format!("GitHub API returned status: {}", response.status()));
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachmentFile {
+    pub filename: String,
+    pub file_type: String,   // "image" or "markdown"
+    pub path: String,        // absolute path
+    pub modified: u64,       // mtime in epoch seconds (for sorting)
+}
+
+/// List all attachments for an issue by reading the filesystem directly.
+/// Returns images and markdown files sorted by modification time (newest first).
+#[tauri::command]
+async fn list_attachments(project_path: String, issue_id: String, include_all_types: Option<bool>) -> Result<Vec<AttachmentFile>, String> {
+    let abs_project_path = if project_path == "." || project_path.is_empty() {
+        env::current_dir().map_err(|e| format!("Failed to get current directory: {}", e))?
+    } else {
+        let p = PathBuf::from(&project_path);
+        if p.is_relative() {
+            let cwd = env::current_dir()
+                .map_err(|e| format!("Failed to get current directory: {}", e))?;
+            cwd.join(&p)
+        } else {
+            p
+        }
+    };
+
+    let attachments_dir = abs_project_path.join(".beads").join("attachments");
+    let issue_dir = resolve_attachment_dir(&attachments_dir, &issue_id)?;
+
+    if !issue_dir.exists() || !issue_dir.is_dir() {
+        return Ok(vec![]);
     }
 
-    let release: GitHubRelease = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse release info: {}", e))?;
+    collect_attachment_files(&issue_dir, include_all_types.unwrap_or(false))
+}
 
-    let latest_version = release.tag_name.trim_start_matches('v').to_string();
-    let has_update = compare_versions(&current_version, &latest_version);
+/// List attachment files in an issue folder, newest first.
+/// By default only images and markdown; `include_all_types` adds "other" files (PDFs, logs…).
+fn collect_attachment_files(issue_dir: &std::path::Path, include_all_types: bool) -> Result<Vec<AttachmentFile>, String> {
+    let mut files: Vec<AttachmentFile> = Vec::new();
 
-    Ok(BdCliUpdateInfo {
-        current_version,
-        latest_version,
-        has_update,
-        release_url: releases_url.to_string(),
-    })
+    let entries = fs::read_dir(issue_dir)
+        .map_err(|e| format!("Failed to read attachment directory: {}", e))?;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_file() { continue; }
+
+        let name = entry.file_name().to_string_lossy().to_string();
+        // Skip legacy index.json files
+        if name == "index.json" { continue; }
+
+        let file_type = classify_attachment(&name);
+        // Only return images and markdown unless all types were requested
+        if file_type == "other" && !include_all_types { continue; }
+
+        let modified = entry.metadata()
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        files.push(AttachmentFile {
+            filename: name,
+            file_type: file_type.to_string(),
+            path: path.to_string_lossy().to_string(),
+            modified,
+        });
+    }
+
+    // Sort by mtime descending (newest first)
+    files.sort_by(|a, b| b.modified.cmp(&a.modified));
+
+    Ok(files)
 }
 
+/// Delete an attachment file by filename within an issue's attachment directory.
 #[tauri::command]
-async fn download_and_install_update(download_url: String) -> Result<String, String> {
-    log::info!("[download_update] Starting download from: {}", download_url);
+async fn delete_attachment(project_path: String, issue_id: String, filename: String) -> Result<(), String> {
+    log::info!("[delete_attachment] project: {}, issue: {}, file: {}", project_path, issue_id, filename);
 
-    // Extract filename from URL
-    let filename = download_url
-        .rsplit('/')
-        .next()
-        .unwrap_or("update-download")
-        .to_string();
-    log::info!("[download_update] Target filename: {}", filename);
+    // Security: reject path traversal
+    if filename.contains('/') || filename.contains('\\') || filename.contains("..") {
+        return Err("Invalid filename".to_string());
+    }
 
-    // Download the file
-    let client = reqwest::Client::builder()
-        .user_agent("beads-task-issue-tracker")
-        .build()
-        .map_err(|e| {
-            log::error!("[download_update] Failed to create HTTP client: {}", e);
-            format!("Failed to create HTTP client: {}", e)
-        })?;
+    let abs_project_path = if project_path == "." || project_path.is_empty() {
+        env::current_dir().map_err(|e| format!("Failed to get current directory: {}", e))?
+    } else {
+        let p = PathBuf::from(&project_path);
+        if p.is_relative() {
+            let cwd = env::current_dir()
+                .map_err(|e| format!("Failed to get current directory: {}", e))?;
+            cwd.join(&p)
+        } else {
+            p
+        }
+    };
 
-    log::info!("[download_update] Sending GET request...");
-    let response = client
-        .get(&download_url)
-        .send()
-        .await
-        .map_err(|e| {
-            log::error!("[download_update] HTTP request failed: {} (url: {})", e, download_url);
-            format!("Failed to download update: {}", e)
-        })?;
+    let abs_project_path = abs_project_path
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve project path: {}", e))?;
 
-    let status = response.status();
-    let final_url = response.url().to_string();
-    log::info!("[download_update] Response status: {} (final URL: {})", status, final_url);
+    let attachments_dir = abs_project_path.join(".beads").join("attachments");
+    let issue_dir = resolve_attachment_dir(&attachments_dir, &issue_id)?;
+    let file_path = issue_dir.join(&filename);
 
-    if !status.is_success() {
-        log::error!("[download_update] Download failed with status: {} (url: {})", status, final_url);
-        return Err(format!("Download failed with status: {}", status));
+    if !file_path.exists() {
+        log::info!("[delete_attachment] File does not exist: {:?}", file_path);
+        return Ok(());
     }
 
-    log::info!("[download_update] Reading response bytes...");
-    let bytes = response
-        .bytes()
-        .await
-        .map_err(|e| {
-            log::error!("[download_update] Failed to read response bytes: {}", e);
-            format!("Failed to read download bytes: {}", e)
-        })?;
-    log::info!("[download_update] Downloaded {} bytes", bytes.len());
-
-    // Save to ~/Downloads
-    let download_dir = dirs::download_dir()
-        .ok_or_else(|| {
-            log::error!("[download_update] Could not find Downloads directory");
-            "Could not find Downloads directory".to_string()
-        })?;
+    // Security: verify file is inside .beads/attachments/
+    let canonical = file_path.canonicalize()
+        .map_err(|e| format!("Failed to resolve path: {}", e))?;
+    let canonical_str = canonical.to_string_lossy();
+    if !canonical_str.contains("/.beads/attachments/") {
+        return Err("Can only delete files inside .beads/attachments/".to_string());
+    }
 
-    let dest_path = download_dir.join(&filename);
-    log::info!("[download_update] Saving to: {}", dest_path.display());
-    fs::write(&dest_path, &bytes)
-        .map_err(|e| {
-            log::error!("[download_update] Failed to save file to {}: {}", dest_path.display(), e);
-            format!("Failed to save file: {}", e)
-        })?;
+    fs::remove_file(&file_path)
+        .map_err(|e| format!("Failed to delete file: {}", e))?;
 
-    let dest_str = dest_path.to_string_lossy().to_string();
-    log::info!("[download_update] Saved successfully: {} ({} bytes)", dest_str, bytes.len());
+    log::info!("[delete_attachment] Deleted: {:?}", file_path);
 
-    // On macOS, mount the DMG
-    #[cfg(target_os = "macos")]
-    {
-        if filename.ends_with(".dmg") {
-            log::info!("[download_update] Mounting DMG: {}", dest_str);
-            Command::new("open")
-                .arg(&dest_path)
-                .spawn()
-                .map_err(|e| {
-                    log::error!("[download_update] Failed to open DMG: {}", e);
-                    format!("Failed to open DMG: {}", e)
-                })?;
+    // Cleanup empty folder (issue_dir already resolved above via resolve_attachment_dir)
+    if issue_dir.exists() {
+        if let Ok(entries) = fs::read_dir(&issue_dir) {
+            // Count non-index.json entries
+            let count = entries.flatten()
+                .filter(|e| e.file_name().to_string_lossy() != "index.json")
+                .count();
+            if count == 0 {
+                // Remove index.json if present, then the directory
+                let _ = fs::remove_file(issue_dir.join("index.json"));
+                let _ = fs::remove_dir(&issue_dir);
+                log::info!("[delete_attachment] Cleaned up empty folder: {:?}", issue_dir);
+            }
         }
     }
 
-    Ok(dest_str)
+    Ok(())
 }
 
-// ============================================================================
-// Debug / Logging Commands
-// ============================================================================
+#[derive(Debug, Serialize)]
+pub struct TextData {
+    pub content: String,
+}
 
-fn get_log_path() -> PathBuf {
-    // Match tauri-plugin-log's LogDir resolution per platform:
-    //   macOS:   ~/Library/Logs/com.beads.manager/
-    //   Linux:   ~/.local/share/com.beads.manager/logs/  (XDG_DATA_HOME)
-    //   Windows: %APPDATA%/com.beads.manager/logs/
-    #[cfg(target_os = "macos")]
-    {
-        let home = env::var("HOME").unwrap_or_default();
-        PathBuf::from(home)
-            .join("Library/Logs/com.beads.manager/beads.log")
+#[tauri::command]
+async fn read_text_file(path: String) -> Result<TextData, String> {
+    log_info!("[read_text_file] Reading: {}", path);
+
+    // Security: Only allow markdown file extensions
+    let path_lower = path.to_lowercase();
+    let is_markdown = path_lower.ends_with(".md") || path_lower.ends_with(".markdown");
+
+    if !is_markdown {
+        return Err("Only markdown files are allowed".to_string());
     }
-    #[cfg(target_os = "linux")]
-    {
-        dirs::data_local_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join("com.beads.manager")
-            .join("logs")
-            .join("beads.log")
+
+    // Verify file exists
+    if !std::path::Path::new(&path).exists() {
+        return Err(format!("File not found: {}", path));
     }
-    #[cfg(target_os = "windows")]
-    {
-        dirs::data_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join("com.beads.manager")
-            .join("logs")
-            .join("beads.log")
+
+    // Security: Canonicalize to resolve symlinks/.. and verify inside .beads/attachments/
+    let canonical = std::path::Path::new(&path).canonicalize()
+        .map_err(|e| format!("Failed to resolve path: {}", e))?;
+    let canonical_str = canonical.to_string_lossy();
+    if !canonical_str.contains("/.beads/attachments/") {
+        log_warn!("[read_text_file] Refusing to read file outside attachments: {} (resolved: {})", path, canonical_str);
+        return Err("Can only read files inside .beads/attachments/".to_string());
     }
-}
 
-#[tauri::command]
-async fn get_logging_enabled() -> bool {
-    LOGGING_ENABLED.load(Ordering::Relaxed)
-}
+    // Read file as UTF-8
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
 
-#[tauri::command]
-async fn set_logging_enabled(enabled: bool) {
-    LOGGING_ENABLED.store(enabled, Ordering::Relaxed);
-    if enabled {
-        log_info!("[debug] Logging enabled");
-    }
+    Ok(TextData { content })
 }
 
 #[tauri::command]
-async fn get_verbose_logging() -> bool {
-    VERBOSE_LOGGING.load(Ordering::Relaxed)
-}
+async fn write_text_file(path: String, content: String) -> Result<(), String> {
+    log_info!("[write_text_file] Writing: {}", path);
 
-#[tauri::command]
-async fn set_verbose_logging(enabled: bool) {
-    VERBOSE_LOGGING.store(enabled, Ordering::Relaxed);
-    log_info!("[debug] Verbose logging: {}", if enabled { "ON" } else { "OFF" });
-}
+    // Security: Only allow markdown file extensions
+    let path_lower = path.to_lowercase();
+    let is_markdown = path_lower.ends_with(".md") || path_lower.ends_with(".markdown");
 
-#[tauri::command]
-async fn clear_logs() -> Result<(), String> {
-    let log_path = get_log_path();
-    if log_path.exists() {
-        fs::write(&log_path, "").map_err(|e| format!("Failed to clear logs: {}", e))?;
-        log_info!("[debug] Logs cleared");
+    if !is_markdown {
+        return Err("Only markdown files are allowed".to_string());
     }
-    Ok(())
-}
 
-#[tauri::command]
-async fn export_logs() -> Result<String, String> {
-    let log_path = get_log_path();
-    if !log_path.exists() {
-        return Err("No logs to export".to_string());
+    // Verify file exists (no creation of new files)
+    if !std::path::Path::new(&path).exists() {
+        return Err(format!("File not found: {}", path));
     }
 
-    // Get export folder: Downloads > Documents > Home
-    let export_dir = dirs::download_dir()
-        .or_else(dirs::document_dir)
-        .or_else(dirs::home_dir)
-        .ok_or_else(|| "Could not find a folder to export logs".to_string())?;
+    // Security: Canonicalize to resolve symlinks/.. and verify inside .beads/attachments/
+    let canonical = std::path::Path::new(&path).canonicalize()
+        .map_err(|e| format!("Failed to resolve path: {}", e))?;
+    let canonical_str = canonical.to_string_lossy();
+    if !canonical_str.contains("/.beads/attachments/") {
+        log_warn!("[write_text_file] Refusing to write file outside attachments: {} (resolved: {})", path, canonical_str);
+        return Err("Can only write files inside .beads/attachments/".to_string());
+    }
 
-    // Generate filename with timestamp
-    let now = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs();
-    let export_filename = format!("beads-logs-{}.log", now);
-    let export_path = export_dir.join(&export_filename);
+    // Write content to file
+    fs::write(&path, &content)
+        .map_err(|e| format!("Failed to write file: {}", e))?;
 
-    // Copy log file
-    fs::copy(&log_path, &export_path)
-        .map_err(|e| format!("Failed to export logs: {}", e))?;
+    log_info!("[write_text_file] Written {} bytes to {}", content.len(), path);
+    Ok(())
+}
 
-    Ok(export_path.to_string_lossy().to_string())
+// ============================================================================
+// File Watcher Commands
+// ============================================================================
+
+#[tauri::command]
+fn start_watching(
+    path: String,
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Mutex<WatcherState>>,
+) -> Result<(), String> {
+    let mut watcher_state = state.lock().map_err(|e| format!("Lock error: {}", e))?;
+    watch_project(path, &app, &mut watcher_state)
 }
 
+/// Set the watcher debounce (clamped to 200–5000ms) and persist it. An active
+/// watcher is restarted so the new interval applies right away. Returns the value used.
 #[tauri::command]
-async fn read_logs(tail_lines: Option<usize>) -> Result<String, String> {
-    let log_path = get_log_path();
-    if !log_path.exists() {
-        return Ok(String::new());
+fn set_watch_debounce(
+    ms: u64,
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Mutex<WatcherState>>,
+) -> Result<u64, String> {
+    let ms = clamp_watch_debounce(ms);
+    update_config(|config| config.watch_debounce_ms = ms)?;
+    WATCH_DEBOUNCE_MS.store(ms, Ordering::Relaxed);
+    log::info!("[watcher] Debounce set to {}ms", ms);
+
+    let mut watcher_state = state.lock().map_err(|e| format!("Lock error: {}", e))?;
+    if watcher_state.debouncer.is_some() {
+        if let Some(path) = watcher_state.watched_path.clone() {
+            watch_project(path, &app, &mut watcher_state)?;
+        }
+    }
+    Ok(ms)
+}
+
+/// (Re)start the watcher on `<path>/.beads`, replacing any current one.
+fn watch_project(path: String, app: &tauri::AppHandle, watcher_state: &mut WatcherState) -> Result<(), String> {
+    // Stop existing watcher if any
+    if watcher_state.debouncer.is_some() {
+        log::info!("[watcher] Stopping previous watcher for: {:?}", watcher_state.watched_path);
+        watcher_state.debouncer = None;
+        watcher_state.watched_path = None;
     }
 
-    let content = fs::read_to_string(&log_path)
-        .map_err(|e| format!("Failed to read logs: {}", e))?;
+    let beads_dir = PathBuf::from(&path).join(".beads");
+    if !beads_dir.exists() {
+        return Err(format!(".beads directory not found at: {}", beads_dir.display()));
+    }
 
-    // If tail_lines is specified, return only the last N lines
-    if let Some(n) = tail_lines {
-        let lines: Vec<&str> = content.lines().collect();
-        let start = if lines.len() > n { lines.len() - n } else { 0 };
-        Ok(lines[start..].join("\n"))
+    let project_path = path.clone();
+    let app_handle = app.clone();
+
+    let mut debouncer = new_debouncer(
+        Duration::from_millis(WATCH_DEBOUNCE_MS.load(Ordering::Relaxed)),
+        move |res: Result<Vec<notify_debouncer_mini::DebouncedEvent>, notify::Error>| {
+            match res {
+                Ok(events) => {
+                    // Filter: only emit if we have actual data-change events
+                    let has_data_events = events.iter().any(|e| {
+                        matches!(e.kind, DebouncedEventKind::Any | DebouncedEventKind::AnyContinuous)
+                    });
+                    if has_data_events {
+                        log::info!("[watcher] Change detected in .beads/ ({} events)", events.len());
+                        let _ = app_handle.emit(
+                            "beads-changed",
+                            BeadsChangedPayload { path: project_path.clone() },
+                        );
+                    }
+                }
+                Err(e) => {
+                    log::error!("[watcher] Error: {:?}", e);
+                }
+            }
+        },
+    ).map_err(|e| format!("Failed to create watcher: {}", e))?;
+
+    // Watch .beads/ directory
+    // Dolt backend: recursive (changes happen in .dolt/ subdirectories)
+    // SQLite backend: non-recursive (all target files are at root level)
+    let watch_mode = if project_uses_dolt(&beads_dir) {
+        notify::RecursiveMode::Recursive
     } else {
-        Ok(content)
-    }
-}
+        notify::RecursiveMode::NonRecursive
+    };
+    debouncer.watcher().watch(
+        beads_dir.as_path(),
+        watch_mode,
+    ).map_err(|e| format!("Failed to watch .beads/: {}", e))?;
 
-#[tauri::command]
-async fn get_log_path_string() -> String {
-    get_log_path().to_string_lossy().to_string()
-}
+    log::info!("[watcher] Started watching: {}", beads_dir.display());
+    watcher_state.debouncer = Some(debouncer);
+    watcher_state.watched_path = Some(path);
 
-#[tauri::command]
-async fn log_frontend(level: String, message: String) {
-    match level.as_str() {
-        "error" => log::error!("[frontend] {}", message),
-        "warn" => log::warn!("[frontend] {}", message),
-        _ => log::info!("[frontend] {}", message),
-    }
+    Ok(())
 }
 
 #[tauri::command]
-async fn get_bd_version() -> String {
-    let binary = get_cli_binary();
-    match new_command(&binary)
-        .arg("--version")
-        .env("PATH", get_extended_path())
-        .output()
-    {
-        Ok(output) if output.status.success() => {
-            let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            if binary != "bd" {
-                format!("{} ({})", version, binary)
-            } else {
-                version
-            }
-        }
-        _ => format!("{} not found", binary),
+fn stop_watching(
+    state: tauri::State<'_, Mutex<WatcherState>>,
+) -> Result<(), String> {
+    let mut watcher_state = state.lock().map_err(|e| format!("Lock error: {}", e))?;
+
+    if watcher_state.debouncer.is_some() {
+        log::info!("[watcher] Stopped watching: {:?}", watcher_state.watched_path);
+        watcher_state.debouncer = None;
+        watcher_state.watched_path = None;
     }
+
+    Ok(())
 }
 
 #[derive(Debug, Serialize)]
-struct CompatibilityInfo {
-    version: String,
-    /// "bd", "br", or "unknown"
-    #[serde(rename = "clientType")]
-    client_type: String,
-    #[serde(rename = "versionTuple")]
-    version_tuple: Option<Vec<u32>>,
-    #[serde(rename = "supportsDaemonFlag")]
-    supports_daemon_flag: bool,
-    #[serde(rename = "usesJsonlFiles")]
-    uses_jsonl_files: bool,
-    #[serde(rename = "usesDoltBackend")]
-    uses_dolt_backend: bool,
-    #[serde(rename = "supportsListAllFlag")]
-    supports_list_all_flag: bool,
-    warnings: Vec<String>,
+struct WatcherStatusInfo {
+    active: bool,
+    #[serde(rename = "watchedPath")]
+    watched_path: Option<String>,
 }
 
 #[tauri::command]
-async fn check_bd_compatibility() -> CompatibilityInfo {
-    let version_string = get_bd_version().await;
-    let info = get_cli_client_info();
-
-    let mut warnings = Vec::new();
-
-    let (client, tuple) = match info {
-        Some((client, major, minor, patch)) => (client, Some((major, minor, patch))),
-        None => {
-            warnings.push(format!("Could not detect CLI client from: {}", version_string));
-            (CliClient::Unknown, None)
-        }
-    };
-
-    let client_type_str = match client {
-        CliClient::Bd => "bd",
-        CliClient::Br => "br",
-        CliClient::Unknown => "unknown",
-    };
-
-    if client == CliClient::Br {
-        warnings.push("br (beads_rust) detected: frozen on classic SQLite+JSONL architecture, no daemon support".to_string());
-    }
-
-    if let Some((major, minor, _)) = tuple {
-        if client == CliClient::Bd && major == 0 && minor >= 50 {
-            warnings.push("bd >= 0.50.0 detected: daemon and JSONL systems have been removed".to_string());
-        }
-    }
+fn get_watcher_status(
+    state: tauri::State<'_, Mutex<WatcherState>>,
+) -> Result<WatcherStatusInfo, String> {
+    let watcher_state = state.lock().map_err(|e| format!("Lock error: {}", e))?;
 
-    CompatibilityInfo {
-        version: version_string,
-        client_type: client_type_str.to_string(),
-        version_tuple: tuple.map(|(a, b, c)| vec![a, b, c]),
-        supports_daemon_flag: supports_daemon_flag(),
-        uses_jsonl_files: uses_jsonl_files(),
-        uses_dolt_backend: uses_dolt_backend(),
-        supports_list_all_flag: supports_list_all_flag(),
-        warnings,
-    }
+    Ok(WatcherStatusInfo {
+        active: watcher_state.debouncer.is_some(),
+        watched_path: watcher_state.watched_path.clone(),
+    })
 }
 
 // ============================================================================
-// CLI Binary Configuration Commands
+// External Data Source Commands
 // ============================================================================
 
 #[tauri::command]
-async fn get_cli_binary_path() -> String {
-    get_cli_binary()
-}
-
-#[tauri::command]
-async fn set_cli_binary_path(path: String) -> Result<String, String> {
-    let binary = if path.trim().is_empty() { "bd".to_string() } else { path.trim().to_string() };
-
-    // Validate the binary first
-    let version = validate_cli_binary_internal(&binary)?;
+async fn fetch_external_data(url: String) -> Result<String, String> {
+    log_info!("[probe] GET {}", url);
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .map_err(|e| format!("HTTP client error: {}", e))?;
 
-    // Update global state and reset version cache (new binary may be different version)
-    *CLI_BINARY.lock().unwrap() = binary.clone();
-    reset_bd_version_cache();
+    let response = client
+        .get(&url)
+        .header("Accept", "application/json")
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
 
-    // Persist to config file
-    let mut config = load_config();
-    config.cli_binary = binary.clone();
-    save_config(&config)?;
+    if !response.status().is_success() {
+        let err = format!("HTTP {}: {}", response.status().as_u16(), response.status().canonical_reason().unwrap_or("Unknown"));
+        log_error!("[probe] GET failed: {}", err);
+        return Err(err);
+    }
 
-    log_info!("[config] CLI binary set to: {} ({})", binary, version);
-    Ok(version)
+    response.text().await.map_err(|e| format!("Failed to read response: {}", e))
 }
 
 #[tauri::command]
-async fn validate_cli_binary(path: String) -> Result<String, String> {
-    let binary = if path.trim().is_empty() { "bd".to_string() } else { path.trim().to_string() };
-    validate_cli_binary_internal(&binary)
-}
+async fn check_external_health(url: String) -> Result<bool, String> {
+    let health_url = format!("{}/health", url.trim_end_matches('/'));
+    log_info!("[probe] Health check: {}", health_url);
 
-fn validate_cli_binary_internal(binary: &str) -> Result<String, String> {
-    // Security: reject shell metacharacters — Command::new() doesn't use a shell,
-    // but defense-in-depth prevents any future misuse
-    let forbidden = [';', '|', '&', '$', '`', '>', '<', '(', ')', '{', '}', '!', '\n', '\r'];
-    if binary.chars().any(|c| forbidden.contains(&c)) {
-        return Err("Invalid binary path: contains shell metacharacters".to_string());
-    }
-    if binary.contains("..") {
-        return Err("Invalid binary path: directory traversal not allowed".to_string());
-    }
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(5))
+        .build()
+        .map_err(|e| format!("HTTP client error: {}", e))?;
 
-    match new_command(binary)
-        .arg("--version")
-        .env("PATH", get_extended_path())
-        .output()
-    {
-        Ok(output) if output.status.success() => {
-            let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            if version.is_empty() {
-                Err(format!("'{}' returned empty version output", binary))
-            } else {
-                Ok(version)
-            }
-        }
-        Ok(output) => {
-            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
-            Err(format!("'{}' failed: {}", binary, if stderr.is_empty() { "unknown error".to_string() } else { stderr }))
-        }
-        Err(e) => {
-            Err(format!("'{}' not found or not executable: {}", binary, e))
-        }
+    match client.get(&health_url).send().await {
+        Ok(response) => Ok(response.status().is_success()),
+        Err(_) => Ok(false),
     }
 }
 
 #[tauri::command]
-async fn open_image_file(path: String) -> Result<(), String> {
-    log_info!("[open_image_file] Opening: {}", path);
+async fn post_external_data(url: String, body: String) -> Result<String, String> {
+    log_info!("[probe] POST {}", url);
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .map_err(|e| format!("HTTP client error: {}", e))?;
 
-    // Security: Only allow image file extensions
-    let allowed_extensions = ["png", "jpg", "jpeg", "gif", "webp", "bmp", "svg", "ico", "tiff", "tif"];
-    let path_lower = path.to_lowercase();
-    let is_image = allowed_extensions.iter().any(|ext| path_lower.ends_with(&format!(".{}", ext)));
+    let response = client
+        .post(&url)
+        .header("Content-Type", "application/json")
+        .header("Accept", "application/json")
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
 
-    if !is_image {
-        return Err("Only image files are allowed".to_string());
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(format!("HTTP {}: {}", status.as_u16(), text));
     }
 
-    // Verify file exists
-    if !std::path::Path::new(&path).exists() {
-        return Err(format!("File not found: {}", path));
-    }
+    response.text().await.map_err(|e| format!("Failed to read response: {}", e))
+}
 
-    // Security: Canonicalize to resolve symlinks/.. and verify inside .beads/attachments/
-    let canonical = std::path::Path::new(&path).canonicalize()
-        .map_err(|e| format!("Failed to resolve path: {}", e))?;
-    let canonical_str = canonical.to_string_lossy();
-    if !canonical_str.contains("/.beads/attachments/") {
-        log_warn!("[open_image_file] Refusing to open file outside attachments: {} (resolved: {})", path, canonical_str);
-        return Err("Can only open files inside .beads/attachments/".to_string());
-    }
+#[tauri::command]
+async fn delete_external_data(url: String) -> Result<String, String> {
+    log_info!("[probe] DELETE {}", url);
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .map_err(|e| format!("HTTP client error: {}", e))?;
 
-    // Use platform-specific command to open file with default application
-    #[cfg(target_os = "macos")]
-    {
-        Command::new("open")
-            .arg(&path)
-            .spawn()
-            .map_err(|e| format!("Failed to open file: {}", e))?;
+    let response = client
+        .delete(&url)
+        .header("Accept", "application/json")
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(format!("HTTP {}: {}", status.as_u16(), text));
     }
 
-    #[cfg(target_os = "windows")]
-    {
-        new_command("cmd")
-            .args(["/C", "start", "", &path])
-            .spawn()
-            .map_err(|e| format!("Failed to open file: {}", e))?;
-    }
+    response.text().await.map_err(|e| format!("Failed to read response: {}", e))
+}
+
+#[tauri::command]
+async fn patch_external_data(url: String, body: String) -> Result<String, String> {
+    log_info!("[probe] PATCH {}", url);
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .map_err(|e| format!("HTTP client error: {}", e))?;
+
+    let response = client
+        .patch(&url)
+        .header("Content-Type", "application/json")
+        .header("Accept", "application/json")
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
 
-    #[cfg(target_os = "linux")]
-    {
-        Command::new("xdg-open")
-            .arg(&path)
-            .spawn()
-            .map_err(|e| format!("Failed to open file: {}", e))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(format!("HTTP {}: {}", status.as_u16(), text));
     }
 
-    Ok(())
+    response.text().await.map_err(|e| format!("Failed to read response: {}", e))
 }
 
-#[derive(Debug, Serialize)]
-pub struct ImageData {
-    pub base64: String,
-    pub mime_type: String,
-}
+// ============================================================================
+// Probe Launcher
+// ============================================================================
 
 #[tauri::command]
-async fn read_image_file(path: String) -> Result<ImageData, String> {
-    log_info!("[read_image_file] Reading: {}", path);
-
-    // Security: Only allow image file extensions
-    let allowed_extensions: &[(&str, &str)] = &[
-        ("png", "image/png"),
-        ("jpg", "image/jpeg"),
-        ("jpeg", "image/jpeg"),
-        ("gif", "image/gif"),
-        ("webp", "image/webp"),
-        ("bmp", "image/bmp"),
-        ("svg", "image/svg+xml"),
-        ("ico", "image/x-icon"),
-        ("tiff", "image/tiff"),
-        ("tif", "image/tiff"),
-    ];
+async fn launch_probe(port: u16) -> Result<String, String> {
+    use std::process::Stdio;
 
-    let path_lower = path.to_lowercase();
-    let mime_type = allowed_extensions
-        .iter()
-        .find(|(ext, _)| path_lower.ends_with(&format!(".{}", ext)))
-        .map(|(_, mime)| *mime);
+    let health_url = format!("http://127.0.0.1:{}/health", port);
 
-    let mime_type = match mime_type {
-        Some(m) => m.to_string(),
-        None => return Err("Only image files are allowed".to_string()),
-    };
+    // Check if probe is already reachable via HTTP health endpoint
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(2))
+        .build()
+        .map_err(|e| format!("HTTP client error: {}", e))?;
 
-    // Verify file exists
-    if !std::path::Path::new(&path).exists() {
-        return Err(format!("File not found: {}", path));
+    if let Ok(resp) = client.get(&health_url).send().await {
+        if resp.status().is_success() {
+            log_info!("[probe] Already running on port {}", port);
+            return Ok("already running".to_string());
+        }
     }
 
-    // Security: Canonicalize to resolve symlinks/.. and verify inside .beads/attachments/
-    let canonical = std::path::Path::new(&path).canonicalize()
-        .map_err(|e| format!("Failed to resolve path: {}", e))?;
-    let canonical_str = canonical.to_string_lossy();
-    if !canonical_str.contains("/.beads/attachments/") {
-        log_warn!("[read_image_file] Refusing to read file outside attachments: {} (resolved: {})", path, canonical_str);
-        return Err("Can only read files inside .beads/attachments/".to_string());
-    }
+    // Determine binary: BEADS_PROBE_BIN env var, fallback to "beads-probe"
+    let bin = env::var("BEADS_PROBE_BIN").unwrap_or_else(|_| "beads-probe".to_string());
+    log_info!("[probe] Launching: {} --port {}", bin, port);
 
-    // Read file and encode as base64
-    let data = fs::read(&path).map_err(|e| format!("Failed to read file: {}", e))?;
-    let base64 = base64_encode(&data);
+    let child = Command::new(&bin)
+        .arg("--port")
+        .arg(port.to_string())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to spawn {}: {}", bin, e))?;
 
-    Ok(ImageData { base64, mime_type })
-}
+    // Store child handle so it lives as long as the app
+    if let Ok(mut guard) = PROBE_CHILD.lock() {
+        *guard = Some(child);
+    }
 
-fn base64_encode(data: &[u8]) -> String {
-    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    log_info!("[probe] Launched on port {}", port);
+    Ok("launched".to_string())
+}
 
-    let mut result = String::with_capacity(data.len().div_ceil(3) * 4);
+// ============================================================================
+// App Entry Point
+// ============================================================================
 
-    for chunk in data.chunks(3) {
-        let mut buf = [0u8; 3];
-        buf[..chunk.len()].copy_from_slice(chunk);
+#[cfg_attr(mobile, tauri::mobile_entry_point)]
+pub fn run() {
+    // Load .env file (dev only — in prod there's no .env, env vars come from the system)
+    let _ = dotenvy::dotenv();
 
-        let n = ((buf[0] as u32) << 16) | ((buf[1] as u32) << 8) | (buf[2] as u32);
+    tauri::Builder::default()
+        .manage(Mutex::new(WatcherState::default()))
+        .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_dialog::init())
+        .setup(|app| {
+            // Enable logging in both debug and release builds
+            let log_level = if cfg!(debug_assertions) {
+                log::LevelFilter::Debug
+            } else {
+                log::LevelFilter::Info
+            };
+            app.handle().plugin(
+                tauri_plugin_log::Builder::default()
+                    .level(log_level)
+                    .max_file_size(5_000_000) // 5 MB max per log file
+                    .rotation_strategy(tauri_plugin_log::RotationStrategy::KeepOne) // Keep only one backup
+                    .target(tauri_plugin_log::Target::new(
+                        tauri_plugin_log::TargetKind::LogDir { file_name: Some("beads.log".into()) },
+                    ))
+                    .target(tauri_plugin_log::Target::new(
+                        tauri_plugin_log::TargetKind::Stdout,
+                    ))
+                    .build(),
+            )?;
 
-        result.push(ALPHABET[(n >> 18) as usize & 0x3F] as char);
-        result.push(ALPHABET[(n >> 12) as usize & 0x3F] as char);
+            // Log startup info
+            log::info!("=== Beads Task-Issue Tracker starting ===");
+            log::info!("[startup] Extended PATH: {}", get_extended_path());
 
-        if chunk.len() > 1 {
-            result.push(ALPHABET[(n >> 6) as usize & 0x3F] as char);
-        } else {
-            result.push('=');
-        }
+            // Load config and set CLI binary (auto-detects br→bd if no config exists)
+            let config = load_config();
+            log::info!("[startup] CLI binary: {}", config.cli_binary);
+            if env::var("BEADS_SAFE_MODE").map(|v| v == "1" || v.eq_ignore_ascii_case("true")).unwrap_or(false) {
+                SAFE_MODE.store(true, Ordering::Relaxed);
+                log::warn!("[startup] Safe mode enabled (BEADS_SAFE_MODE) — auto-sync and migrations disabled");
+            }
+            apply_config(&config);
 
-        if chunk.len() > 2 {
-            result.push(ALPHABET[n as usize & 0x3F] as char);
-        } else {
-            result.push('=');
-        }
-    }
+            // Check if CLI binary is accessible
+            // IMPORTANT: Run from /tmp to avoid bd auto-migrating projects in cwd
+            let binary = get_cli_binary();
+            match new_command(&binary)
+                .arg("--version")
+                .current_dir(std::env::temp_dir())
+                .env("PATH", get_extended_path())
+                .output()
+            {
+                Ok(output) if output.status.success() => {
+                    let version = String::from_utf8_lossy(&output.stdout);
+                    log::info!("[startup] {} found: {}", binary, version.trim());
+                }
+                Ok(output) => {
+                    log::warn!("[startup] {} command failed: {}", binary, String::from_utf8_lossy(&output.stderr));
+                }
+                Err(e) => {
+                    log::error!("[startup] {} not found or not executable: {}", binary, e);
+                }
+            }
 
-    result
+            Ok(())
+        })
+        .invoke_handler(tauri::generate_handler![
+            bd_sync,
+            set_auto_sync_interval,
+            get_auto_sync_interval,
+            set_favorite_projects,
+            set_auto_backup,
+            run_backup_now,
+            bd_export,
+            bd_repair_database,
+            bd_migrate_to_dolt,
+            bd_check_needs_migration,
+            bd_cleanup_stale_locks,
+            bd_check_changed,
+            set_poll_active,
+            bd_resolved_backend,
+            bd_reset_mtime,
+            bd_poll_data,
+            bd_closed,
+            bd_list,
+            bd_list_cursor,
+            bd_list_minimal,
+            bd_list_grouped,
+            bd_list_csv,
+            bd_needs_attention,
+            bd_current_actor,
+            bd_count,
+            bd_dashboard,
+            bd_types,
+            set_safe_mode,
+            get_safe_mode,
+            bd_ready,
+            bd_status,
+            bd_show,
+            bd_create,
+            get_logging_enabled,
+            set_logging_enabled,
+            get_verbose_logging,
+            set_verbose_logging,
+            set_verbose_logging_timed,
+            clear_logs,
+            export_logs,
+            read_logs,
+            read_logs_structured,
+            get_log_path_string,
+            log_frontend,
+            get_bd_version,
+            check_bd_compatibility,
+            run_diagnostics,
+            get_cli_binary_path,
+            resolve_cli_binary_path,
+            detect_available_clis,
+            set_cli_binary_path,
+            get_config,
+            set_config,
+            get_project_config,
+            get_env_passthrough,
+            set_env_passthrough,
+            set_project_config,
+            validate_cli_binary,
+            bd_update,
+            bd_close,
+            bd_reopen,
+            bd_search,
+            get_recent_searches,
+            clear_recent_searches,
+            bd_label_add,
+            bd_label_remove,
+            bd_set_labels,
+            bd_delete,
+            bd_bulk_delete,
+            bd_bulk_update,
+            bd_comments_add,
+            bd_comment_delete,
+            bd_comment_with_attachment,
+            bd_dep_add,
+            bd_dep_add_many,
+            bd_create_batch,
+            bd_add_blocker,
+            bd_add_blocks,
+            bd_dep_remove,
+            bd_dep_prune,
+            bd_dep_subgraph,
+            bd_relations_of_type,
+            bd_validate_dependencies,
+            bd_dep_add_relation,
+            bd_dep_remove_relation,
+            bd_available_relation_types,
+            fs_exists,
+            fs_list,
+            check_for_updates,
+            check_for_updates_demo,
+            check_bd_cli_update,
+            download_and_install_update,
+            open_image_file,
+            read_image_file,
+            copy_file_to_attachments,
+            write_image_file,
+            list_attachments,
+            delete_attachment,
+            read_text_file,
+            write_text_file,
+            purge_orphan_attachments,
+            check_refs_migration,
+            migrate_attachment_refs,
+            start_watching,
+            stop_watching,
+            set_watch_debounce,
+            get_watcher_status,
+            fetch_external_data,
+            check_external_health,
+            post_external_data,
+            delete_external_data,
+            patch_external_data,
+            launch_probe,
+        ])
+        .run(tauri::generate_context!())
+        .expect("error while running tauri application");
 }
 
-#[tauri::command]
-async fn purge_orphan_attachments(project_path: String) -> Result<PurgeResult, String> {
-    log::info!("[purge_orphan_attachments] project: {}", project_path);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    // Calculate absolute project path (reusing pattern from bd_delete)
-    let abs_project_path = if project_path == "." || project_path.is_empty() {
-        env::current_dir().map_err(|e| format!("Failed to get current directory: {}", e))?
-    } else {
-        let p = PathBuf::from(&project_path);
-        if p.is_relative() {
-            let cwd = env::current_dir()
-                .map_err(|e| format!("Failed to get current directory: {}", e))?;
-            cwd.join(&p)
-        } else {
-            p
+    fn minimal_issue_json(id: &str, title: &str) -> String {
+        format!(
+            r#"{{"id":"{}","title":"{}","description":null,"status":"open","priority":3,"issue_type":"task","owner":null,"assignee":null,"labels":[],"created_at":"2025-01-01T00:00:00Z","created_by":null,"updated_at":"2025-01-01T00:00:00Z","closed_at":null,"close_reason":null,"blocked_by":null,"blocks":null,"comments":null,"external_ref":null,"estimate":null,"design":null,"acceptance_criteria":null,"notes":null,"parent":null,"dependents":null,"dependencies":null,"dependency_count":null,"dependent_count":null,"metadata":null,"spec_id":null,"comment_count":null}}"#,
+            id, title
+        )
+    }
+
+    /// `minimal_issue_json` with some fields overridden, e.g. `json!({"status": "closed"})`.
+    fn issue_json_with(id: &str, title: &str, fields: serde_json::Value) -> String {
+        let mut issue: serde_json::Value = serde_json::from_str(&minimal_issue_json(id, title)).unwrap();
+        for (key, value) in fields.as_object().expect("fields must be a JSON object") {
+            issue[key] = value.clone();
         }
-    };
+        issue.to_string()
+    }
 
-    let abs_project_path = abs_project_path
-        .canonicalize()
-        .map_err(|e| format!("Failed to resolve project path: {}", e))?;
+    /// A raw issue titled after its id, with some fields overridden.
+    fn raw_issue_with(id: &str, fields: serde_json::Value) -> BdRawIssue {
+        serde_json::from_str(&issue_json_with(id, id, fields)).unwrap()
+    }
 
-    // Build attachments directory path
-    let attachments_dir = abs_project_path.join(".beads").join("attachments");
+    /// An issue with its parent set the way `bd list` reports it.
+    fn issue_with_parent(id: &str, parent: Option<&str>) -> Issue {
+        transform_issue(raw_issue_with(id, serde_json::json!({ "parent": parent })))
+    }
 
-    // If attachments directory doesn't exist, nothing to purge
-    if !attachments_dir.exists() || !attachments_dir.is_dir() {
-        log::info!("[purge_orphan_attachments] No attachments directory found");
-        return Ok(PurgeResult {
-            deleted_count: 0,
-            deleted_folders: vec![],
-        });
+    /// Scratch directory for one test, unique to this process.
+    fn test_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("beads-{}-test-{}", name, std::process::id()))
     }
 
-    // Get list of all existing issue IDs via bd list --all
-    let existing_ids: std::collections::HashSet<String> = {
-        let output = execute_bd("list", &["--all".to_string(), "--limit=0".to_string()], Some(&abs_project_path.to_string_lossy()))?;
-        let issues = parse_issues_tolerant(&output, "purge_orphan_attachments")?;
-        issues.into_iter().map(|i| i.id).collect()
-    };
+    #[test]
+    fn parse_flat_array() {
+        let json = format!("[{}]", minimal_issue_json("abc-123", "Bug fix"));
+        let result = parse_issues_tolerant(&json, "test_flat");
+        assert!(result.is_ok());
+        let issues = result.unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].id, "abc-123");
+        assert_eq!(issues[0].title, "Bug fix");
+    }
 
-    log::info!("[purge_orphan_attachments] Found {} existing issues", existing_ids.len());
+    #[test]
+    fn parse_paginated_envelope() {
+        let json = format!(
+            r#"{{"issues":[{},{}],"total":2,"offset":0,"limit":50,"has_more":false}}"#,
+            minimal_issue_json("abc-123", "First"),
+            minimal_issue_json("def-456", "Second")
+        );
+        let result = parse_issues_tolerant(&json, "test_envelope");
+        assert!(result.is_ok());
+        let issues = result.unwrap();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].id, "abc-123");
+        assert_eq!(issues[1].id, "def-456");
+    }
 
-    // List all subdirectories in attachments folder
-    let entries = fs::read_dir(&attachments_dir)
-        .map_err(|e| format!("Failed to read attachments directory: {}", e))?;
+    #[test]
+    fn parse_empty_flat_array() {
+        let result = parse_issues_tolerant("[]", "test_empty_flat");
+        assert!(result.is_ok());
+        assert!(result.unwrap().is_empty());
+    }
 
-    let mut deleted_folders: Vec<String> = Vec::new();
+    #[test]
+    fn parse_empty_envelope() {
+        let json = r#"{"issues":[],"total":0,"offset":0,"limit":50,"has_more":false}"#;
+        let result = parse_issues_tolerant(json, "test_empty_envelope");
+        assert!(result.is_ok());
+        assert!(result.unwrap().is_empty());
+    }
 
-    for entry in entries.flatten() {
-        let path = entry.path();
-        if !path.is_dir() {
-            continue;
-        }
+    #[test]
+    fn parse_object_without_issues_key_fails() {
+        let json = r#"{"error":"something went wrong"}"#;
+        let result = parse_issues_tolerant(json, "test_bad_object");
+        assert!(result.is_err());
+    }
 
-        let folder_name = match path.file_name() {
-            Some(name) => name.to_string_lossy().to_string(),
-            None => continue,
-        };
+    #[test]
+    fn parse_invalid_json_fails() {
+        let result = parse_issues_tolerant("not json at all", "test_invalid");
+        assert!(result.is_err());
+    }
 
-        // Check if this folder corresponds to an existing issue (folders use short IDs)
-        let is_owned = existing_ids.iter().any(|id| issue_short_id(id) == folder_name);
-        if !is_owned {
-            log::info!("[purge_orphan_attachments] Deleting orphan folder: {}", folder_name);
-            if let Err(e) = fs::remove_dir_all(&path) {
-                log::warn!("[purge_orphan_attachments] Failed to delete {}: {}", folder_name, e);
-            } else {
-                deleted_folders.push(folder_name);
-            }
-        }
+    #[test]
+    fn parse_real_br_envelope() {
+        // Matches the shape from br 0.1.30+ (`br list --json --limit 1`):
+        // br omits many optional fields (owner, assignee, labels, etc.) and includes extra
+        // fields (source_repo, compaction_level). serde_json defaults missing Option<T> to None
+        // and ignores unknown fields, so this parses correctly.
+        let json = r#"{"issues":[{"id":"proj-abc","title":"Example bug report","description":"A test description","status":"open","priority":2,"issue_type":"bug","created_at":"2025-06-15T09:30:00.000000000Z","updated_at":"2025-06-15T10:45:00.000000000Z","source_repo":".","compaction_level":0,"dependency_count":0,"dependent_count":0}],"total":1,"limit":1,"offset":0,"has_more":true}"#;
+        let result = parse_issues_tolerant(json, "test_real_br");
+        assert!(result.is_ok());
+        let issues = result.unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].id, "proj-abc");
+        assert_eq!(issues[0].issue_type, "bug");
+        assert_eq!(issues[0].priority, 2);
     }
 
-    let deleted_count = deleted_folders.len();
-    log::info!("[purge_orphan_attachments] Purged {} orphan folders", deleted_count);
+    #[test]
+    fn parse_envelope_skips_malformed_entries() {
+        let good = minimal_issue_json("abc-123", "Good");
+        let bad = r#"{"id":"bad-456","title":"Bad"}"#; // missing required fields
+        let json = format!(r#"{{"issues":[{},{}],"total":2,"offset":0,"limit":50,"has_more":false}}"#, good, bad);
+        let result = parse_issues_tolerant(&json, "test_tolerant_envelope");
+        assert!(result.is_ok());
+        let issues = result.unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].id, "abc-123");
+    }
 
-    Ok(PurgeResult {
-        deleted_count,
-        deleted_folders,
-    })
-}
+    fn issue_created_at(id: &str, created_at: &str) -> Issue {
+        let raw: BdRawIssue = serde_json::from_str(&minimal_issue_json(id, id)).unwrap();
+        let mut issue = transform_issue(raw);
+        issue.created_at = created_at.to_string();
+        issue
+    }
 
-/// Sanitize a filename for safe storage and br JSONL compatibility.
-/// Converts to kebab-case, strips diacritics, removes unsafe chars.
-/// Example: "Screenshot 2026-02-24 à 10.30.png" → "screenshot-2026-02-24-a-10-30.png"
-fn sanitize_filename(filename: &str) -> String {
-    // Split into stem and extension
-    let (stem, ext) = match filename.rfind('.') {
-        Some(pos) => (&filename[..pos], &filename[pos..]),
-        None => (filename, ""),
-    };
+    #[test]
+    fn list_csv_quotes_fields_and_respects_filters() {
+        let mut quoted = issue_created_at("p-1", "2025-01-01T00:00:00Z");
+        quoted.title = "Crash, then \"hang\"".to_string();
+        quoted.labels = vec!["ui".to_string(), "bug".to_string()];
+        quoted.assignee = Some("ana".to_string());
+        let mut other = issue_created_at("p-2", "2025-01-02T00:00:00Z");
+        other.labels = vec!["backend".to_string()];
+
+        let options = ListOptions { labels: Some(vec!["ui".to_string()]), ..Default::default() };
+        let issues = apply_list_post_filters(vec![quoted, other], &options).unwrap();
+        assert_eq!(
+            issues_to_csv(&issues),
+            "id,title,status,priority,type,assignee,labels,createdAt,updatedAt,closedAt\r\n\
+             p-1,\"Crash, then \"\"hang\"\"\",open,p3,task,ana,ui;bug,2025-01-01T00:00:00Z,2025-01-01T00:00:00Z,\r\n"
+        );
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+        assert_eq!(csv_field("=1+1"), "'=1+1");
+        assert_eq!(csv_field("@SUM(A1:A2)"), "'@SUM(A1:A2)");
+        assert_eq!(csv_field("-2, maybe"), "\"'-2, maybe\"");
+        assert_eq!(csv_field("a=b"), "a=b");
+    }
 
-    // Strip diacritics by replacing common accented chars, then lowercase + kebab-case
-    let mut sanitized = String::with_capacity(stem.len());
-    for c in stem.chars() {
-        let replacement = match c {
-            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' => "a",
-            'è' | 'é' | 'ê' | 'ë' | 'È' | 'É' | 'Ê' | 'Ë' => "e",
-            'ì' | 'í' | 'î' | 'ï' | 'Ì' | 'Í' | 'Î' | 'Ï' => "i",
-            'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' => "o",
-            'ù' | 'ú' | 'û' | 'ü' | 'Ù' | 'Ú' | 'Û' | 'Ü' => "u",
-            'ñ' | 'Ñ' => "n",
-            'ç' | 'Ç' => "c",
-            'ß' => "ss",
-            'æ' | 'Æ' => "ae",
-            'œ' | 'Œ' => "oe",
-            'ý' | 'ÿ' | 'Ý' => "y",
-            'A'..='Z' => { sanitized.push((c as u8 + 32) as char); continue; },
-            'a'..='z' | '0'..='9' => { sanitized.push(c); continue; },
-            '-' => { sanitized.push('-'); continue; },
-            ' ' | '_' | '.' => { sanitized.push('-'); continue; },
-            _ => "-",
+    #[test]
+    fn label_filter_matches_any_or_all() {
+        let labeled = |id: &str, labels: &[&str]| {
+            let mut issue = issue_created_at(id, "2025-01-01T00:00:00Z");
+            issue.labels = labels.iter().map(|l| l.to_string()).collect();
+            issue
         };
-        sanitized.push_str(replacement);
-    }
+        let issues = vec![
+            labeled("ui-bug", &["ui", "bug"]),
+            labeled("ui", &["ui"]),
+            labeled("bug", &["bug", "backend"]),
+            labeled("none", &[]),
+        ];
+        let ids = |labels: &[&str], labels_match: Option<&str>| -> Vec<String> {
+            let options = ListOptions {
+                labels: Some(labels.iter().map(|l| l.to_string()).collect()),
+                labels_match: labels_match.map(String::from),
+                ..Default::default()
+            };
+            apply_list_post_filters(issues.clone(), &options).unwrap().into_iter().map(|i| i.id).collect()
+        };
+        assert_eq!(ids(&["ui", "bug"], None), vec!["ui-bug", "ui", "bug"]);
+        assert_eq!(ids(&["ui", "bug"], Some("any")), vec!["ui-bug", "ui", "bug"]);
+        assert_eq!(ids(&["ui", "bug"], Some("all")), vec!["ui-bug"]);
+        assert_eq!(ids(&["backend"], Some("all")), vec!["bug"]);
+        // Blank entries are ignored, so this is no filter at all
+        assert_eq!(ids(&[" "], None).len(), 4);
 
-    // Collapse multiple consecutive dashes and trim
-    let mut result = String::with_capacity(sanitized.len());
-    let mut prev_dash = false;
-    for c in sanitized.chars() {
-        if c == '-' {
-            if !prev_dash {
-                result.push('-');
-            }
-            prev_dash = true;
-        } else {
-            result.push(c);
-            prev_dash = false;
-        }
+        let bad = ListOptions { labels: Some(vec!["ui".to_string()]), labels_match: Some("most".to_string()), ..Default::default() };
+        assert!(apply_list_post_filters(issues, &bad).unwrap_err().contains("labelsMatch"));
     }
-    let result = result.trim_matches('-');
 
-    let ext_lower = ext.to_lowercase();
-    if result.is_empty() {
-        format!("file{}", ext_lower)
-    } else {
-        format!("{}{}", result, ext_lower)
-    }
-}
+    #[test]
+    fn top_level_only_excludes_children() {
+        let issues = vec![
+            issue_with_parent("epic", None),
+            issue_with_parent("child", Some("epic")),
+            issue_with_parent("loose", None),
+            issue_with_parent("blank-parent", Some("")),
+        ];
+        let options = ListOptions { top_level_only: Some(true), id_pattern: Some("*e*".to_string()), ..Default::default() };
+        let ids: Vec<String> = apply_list_post_filters(issues.clone(), &options).unwrap()
+            .into_iter().map(|i| i.id).collect();
+        assert_eq!(ids, vec!["epic", "loose", "blank-parent"]);
 
-// ============================================================================
-// Attachment helpers
-// ============================================================================
+        assert_eq!(apply_list_post_filters(issues, &ListOptions::default()).unwrap().len(), 4);
+    }
 
-/// Image file extensions supported for attachment preview
-const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp", "bmp", "svg", "ico", "tiff", "tif"];
+    #[test]
+    fn list_limit_offset_returns_newest_first_slice() {
+        let issues: Vec<Issue> = (1..=10)
+            .map(|n| issue_created_at(&format!("t-{}", n), &format!("2025-01-{:02}T00:00:00Z", n)))
+            .collect();
+        let options = ListOptions { limit: Some(3), offset: Some(3), ..Default::default() };
+        let ids: Vec<String> = apply_list_post_filters(issues.clone(), &options).unwrap()
+            .into_iter().map(|i| i.id).collect();
+        assert_eq!(ids, vec!["t-7", "t-6", "t-5"]);
+
+        let past_end = ListOptions { offset: Some(9), ..Default::default() };
+        let ids: Vec<String> = apply_list_post_filters(issues, &past_end).unwrap()
+            .into_iter().map(|i| i.id).collect();
+        assert_eq!(ids, vec!["t-1"]);
+
+        // Ordered by instant, not by string: 12:00+02:00 is 10:00Z, older than "11:00:00"
+        let mixed = vec![
+            issue_created_at("m-1", "2025-01-15T12:00:00+02:00"),
+            issue_created_at("m-2", "2025-01-15 11:00:00"),
+            issue_created_at("m-3", "2025-01-15T10:30:00Z"),
+        ];
+        let options = ListOptions { limit: Some(3), ..Default::default() };
+        let ids: Vec<String> = apply_list_post_filters(mixed, &options).unwrap()
+            .into_iter().map(|i| i.id).collect();
+        assert_eq!(ids, vec!["m-2", "m-3", "m-1"]);
+    }
 
-/// Markdown file extensions supported for attachment preview
-const MARKDOWN_EXTENSIONS: &[&str] = &["md", "markdown"];
+    #[test]
+    fn cursor_paging_has_no_gaps_or_duplicates_when_issue_added_mid_paging() {
+        let mut issues = vec![
+            issue_created_at("p-1", "2025-01-01T00:00:00Z"),
+            issue_created_at("p-2", "2025-01-02T00:00:00Z"),
+            issue_created_at("p-3", "2025-01-03T00:00:00Z"),
+            // Same timestamp: id breaks the tie
+            issue_created_at("p-4", "2025-01-03T00:00:00Z"),
+            issue_created_at("p-5", "2025-01-05T00:00:00Z"),
+        ];
 
-/// Extract the short ID from a full issue ID by stripping the project prefix.
-/// e.g. "beads-manager-2qk" → "2qk", "kybio-1pxe" → "1pxe",
-///      "kybio-front-nuxt-4-466d" → "466d", "beads-manager-02e.1" → "02e.1"
-/// Falls back to the full ID if no prefix separator is found.
-fn issue_short_id(full_id: &str) -> &str {
-    // The short ID is after the last '-' that isn't followed by another segment
-    // containing only digits (which would be part of the prefix like "nuxt-4").
-    // Strategy: find the last '-' where everything after it matches [a-z0-9.]+ (the short ID).
-    // But "kybio-front-nuxt-4-466d": after last '-' is "466d" ✓
-    // "kybio-front-nuxt-4": after last '-' is "4" which could be a short ID or prefix part.
-    // Since we only call this with real issue IDs (not project names), the last segment is always the short ID.
-    match full_id.rfind('-') {
-        Some(pos) => &full_id[pos + 1..],
-        None => full_id,
-    }
-}
+        let first = paginate_by_cursor(issues.iter().map(|i| issue_created_at(&i.id, &i.created_at)).collect(), None, Some(2));
+        let mut seen: Vec<String> = first.issues.iter().map(|i| i.id.clone()).collect();
+        assert_eq!(seen, vec!["p-5", "p-4"]);
 
-/// Resolve the attachment directory for an issue.
-/// Always uses short ID: .beads/attachments/{short_id}/
-fn resolve_attachment_dir(attachments_dir: &std::path::Path, issue_id: &str) -> PathBuf {
-    attachments_dir.join(issue_short_id(issue_id))
-}
+        // A new issue is created while the user is scrolling
+        issues.push(issue_created_at("p-6", "2025-01-06T00:00:00Z"));
 
-/// Classify a filename as "image", "markdown", or "other"
-fn classify_attachment(filename: &str) -> &'static str {
-    let lower = filename.to_lowercase();
-    if IMAGE_EXTENSIONS.iter().any(|ext| lower.ends_with(&format!(".{}", ext))) {
-        "image"
-    } else if MARKDOWN_EXTENSIONS.iter().any(|ext| lower.ends_with(&format!(".{}", ext))) {
-        "markdown"
-    } else {
-        "other"
+        let mut cursor = first.next_cursor;
+        while let Some(c) = cursor {
+            let page = paginate_by_cursor(issues.iter().map(|i| issue_created_at(&i.id, &i.created_at)).collect(), Some(&c), Some(2));
+            seen.extend(page.issues.iter().map(|i| i.id.clone()));
+            cursor = page.next_cursor;
+        }
+
+        assert_eq!(seen, vec!["p-5", "p-4", "p-3", "p-2", "p-1"]);
     }
-}
 
-/// Resolve a duplicate filename: image.png → image-1.png → image-2.png
-fn resolve_duplicate_filename(dir: &std::path::Path, name: &str) -> String {
-    if !dir.join(name).exists() {
-        return name.to_string();
+    #[test]
+    fn cursor_paging_last_page_has_no_next_cursor() {
+        let issues = vec![
+            issue_created_at("p-1", "2025-01-01T00:00:00Z"),
+            issue_created_at("p-2", "2025-01-02T00:00:00Z"),
+        ];
+        let page = paginate_by_cursor(issues, None, Some(2));
+        assert_eq!(page.issues.len(), 2);
+        assert!(page.next_cursor.is_none());
     }
-    let (stem, ext) = match name.rfind('.') {
-        Some(pos) => (&name[..pos], &name[pos..]),
-        None => (name, ""),
-    };
-    for i in 1..1000 {
-        let candidate = format!("{}-{}{}", stem, i, ext);
-        if !dir.join(&candidate).exists() {
-            return candidate;
-        }
+
+    #[test]
+    fn cursor_paging_orders_mixed_timestamp_formats_as_instants() {
+        let issues = || vec![
+            issue_created_at("m-1", "2025-01-02 08:00:00"),
+            issue_created_at("m-2", "2025-01-02T09:00:00+02:00"), // 07:00Z
+            issue_created_at("m-3", "2025-01-02T07:30:00Z"),
+        ];
+        let ids = |page: &IssuePage| page.issues.iter().map(|i| i.id.clone()).collect::<Vec<_>>();
+
+        let all = paginate_by_cursor(issues(), None, None);
+        assert_eq!(ids(&all), vec!["m-1", "m-3", "m-2"]);
+        assert!(all.next_cursor.is_none());
+
+        let first = paginate_by_cursor(issues(), None, Some(1));
+        let rest = paginate_by_cursor(issues(), first.next_cursor.as_ref(), None);
+        assert_eq!(ids(&rest), vec!["m-3", "m-2"]);
     }
-    // Fallback with timestamp
-    let ts = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis();
-    format!("{}-{}{}", stem, ts, ext)
-}
 
-// ============================================================================
-// Attachment Refs Migration v3 — filesystem-only
-// ============================================================================
+    #[test]
+    fn dangling_dep_refs_are_reported_and_prunable() {
+        let mut issue = issue_created_at("p-1", "2025-01-01T00:00:00Z");
+        issue.blocked_by = Some(vec!["p-2".to_string(), "p-gone".to_string()]);
+        issue.blocks = Some(vec!["p-3".to_string()]);
+
+        // p-gone was hard-deleted; p-3 has also disappeared
+        let existing: std::collections::HashSet<String> =
+            ["p-1", "p-2"].iter().map(|s| s.to_string()).collect();
+
+        assert_eq!(find_dangling_refs(&issue, &existing), vec!["p-gone", "p-3"]);
+        assert_eq!(
+            plan_dep_prune(&issue, &existing),
+            vec![
+                ("p-1".to_string(), "p-gone".to_string()),
+                ("p-3".to_string(), "p-1".to_string()),
+            ]
+        );
+    }
 
-/// Check if a ref is a "real" external reference (Redmine, GitHub, or other URL/ID).
-/// Returns false for att: refs, local file paths, cleared: sentinels.
-fn is_real_external_ref(r: &str) -> bool {
-    let trimmed = r.trim();
-    if trimmed.is_empty() { return false; }
-    if trimmed.starts_with("cleared:") { return false; }
-    if trimmed.starts_with("att:") { return false; }
-    // Local file paths (absolute or relative .beads/)
-    if trimmed.starts_with('/') { return false; }
-    if trimmed.starts_with(".beads/") { return false; }
-    // Anything with path separators inside .beads or attachments is local
-    if trimmed.contains("/attachments/") || trimmed.contains("/.beads/") { return false; }
-    true
-}
+    #[test]
+    fn parse_timestamp_handles_sqlite_and_offset_formats() {
+        assert_eq!(parse_timestamp_utc("1970-01-01 00:00:00"), Some((0, 0)));
+        assert_eq!(parse_timestamp_utc("2025-06-15T09:30:00Z"), parse_timestamp_utc("2025-06-15 09:30:00"));
+        assert_eq!(parse_timestamp_utc("2025-06-15T11:30:00+02:00"), parse_timestamp_utc("2025-06-15T09:30:00Z"));
+        assert_eq!(parse_timestamp_utc("2025-06-15T09:30:00.5Z").map(|t| t.1), Some(500_000_000));
+        assert_eq!(parse_timestamp_utc("not a date"), None);
+        assert_eq!(format_timestamp_utc(parse_timestamp_utc("2024-02-29T23:59:59-01:00").unwrap().0), "2024-03-01T00:59:59Z");
+    }
 
-#[derive(serde::Serialize)]
-#[serde(rename_all = "camelCase")]
-struct RefsMigrationStatus {
-    needs_migration: bool,
-    ref_count: u32,
-    just_migrated: bool,
-}
+    #[test]
+    fn latest_timestamp_compares_mixed_formats_chronologically() {
+        // Lexicographically "2025-06-15T10:00:00+05:00" is the max, but it's 05:00 UTC.
+        // "2025-06-15 09:00:00" (SQLite, UTC) is the real latest.
+        let values = [
+            "2025-06-15T10:00:00+05:00",
+            "2025-06-15 09:00:00",
+            "2025-06-15T08:59:59.999999999Z",
+        ];
+        assert_eq!(latest_timestamp(values.iter().copied()), Some("2025-06-15T09:00:00Z".to_string()));
+        assert_eq!(latest_timestamp(std::iter::empty()), None);
+    }
 
-#[derive(serde::Serialize)]
-#[serde(rename_all = "camelCase")]
-struct MigrateRefsResult {
-    success: bool,
-    refs_updated: u32,
-}
+    #[test]
+    fn delete_keeps_attachments_unless_issue_is_confirmed_gone() {
+        let project = test_dir("delete");
+        let att_dir = project.join(".beads").join("attachments").join("abc");
+        fs::create_dir_all(&att_dir).unwrap();
+        fs::write(att_dir.join("shot.png"), b"x").unwrap();
+        let cwd = project.to_str();
+
+        // Delete command fails: error is returned, attachments untouched
+        let result = delete_then_cleanup_attachments("proj-abc", cwd, || Err("boom".to_string()), || Ok(false));
+        assert!(result.is_err());
+        assert!(att_dir.exists());
 
-/// Check if a project needs attachment refs migration v3.
-/// v3 strips all attachment refs (att:, local paths) from external_ref,
-/// keeping only real external refs (Redmine, GitHub, URLs).
-/// Returns quickly if the .migrated-attachments marker file exists.
-#[tauri::command]
-async fn check_refs_migration(cwd: Option<String>) -> Result<RefsMigrationStatus, String> {
-    let working_dir = cwd
-        .or_else(|| env::var("BEADS_PATH").ok())
-        .unwrap_or_else(|| {
-            env::current_dir()
-                .map(|p| p.to_string_lossy().to_string())
-                .unwrap_or_else(|_| ".".to_string())
-        });
+        // Soft delete: issue still visible afterwards
+        delete_then_cleanup_attachments("proj-abc", cwd, || Ok(()), || Ok(true)).unwrap();
+        assert!(att_dir.exists());
 
-    let beads_dir = PathBuf::from(&working_dir).join(".beads");
-    if !beads_dir.exists() {
-        return Ok(RefsMigrationStatus { needs_migration: false, ref_count: 0, just_migrated: false });
+        // Confirmed gone: attachments removed
+        delete_then_cleanup_attachments("proj-abc", cwd, || Ok(()), || Ok(false)).unwrap();
+        assert!(!att_dir.exists());
+
+        let _ = fs::remove_dir_all(&project);
     }
 
-    // Already migrated to v3?
-    if beads_dir.join(".migrated-attachments").exists() {
-        // Check if auto-migration just ran (notify signal)
-        let notify_path = beads_dir.join(".migrated-attachments-notify");
-        let just_migrated = notify_path.exists();
-        if just_migrated {
-            let _ = std::fs::remove_file(&notify_path);
-        }
-        return Ok(RefsMigrationStatus { needs_migration: false, ref_count: 0, just_migrated });
+    #[test]
+    fn timed_verbose_logging_expires_only_at_deadline_and_if_not_toggled() {
+        let start = Instant::now();
+        let deadline = start + Duration::from_secs(30 * 60);
+        assert!(!verbose_logging_expired(deadline, start, 3, 3));
+        assert!(!verbose_logging_expired(deadline, deadline - Duration::from_millis(1), 3, 3));
+        assert!(verbose_logging_expired(deadline, deadline, 3, 3));
+        assert!(verbose_logging_expired(deadline, deadline + Duration::from_secs(1), 3, 3));
+        // Toggled again since the timer was armed: leave the newer setting alone
+        assert!(!verbose_logging_expired(deadline, deadline + Duration::from_secs(1), 3, 4));
     }
 
-    let jsonl_path = beads_dir.join("issues.jsonl");
-    if !jsonl_path.exists() {
-        let _ = std::fs::write(beads_dir.join(".migrated-attachments"), "");
-        return Ok(RefsMigrationStatus { needs_migration: false, ref_count: 0, just_migrated: false });
+    #[test]
+    fn search_query_blank_is_skipped_and_quotes_pass_through() {
+        assert_eq!(normalize_search_query("   "), None);
+        assert_eq!(normalize_search_query(""), None);
+        assert_eq!(normalize_search_query(" say \"hi "), Some("say \"hi".to_string()));
+        assert_eq!(
+            build_bd_args("search", &[normalize_search_query("\"exact phrase\"").unwrap()], false, true),
+            vec!["search", "\"exact phrase\"", "--json"]
+        );
     }
 
-    // Scan JSONL for refs that need cleanup (non-real external refs)
-    let content = std::fs::read_to_string(&jsonl_path)
-        .map_err(|e| format!("Failed to read issues.jsonl: {}", e))?;
+    #[test]
+    fn resolved_backend_follows_per_project_dolt_detection() {
+        let root = test_dir("backend");
+        let dolt_beads = root.join("dolt-project").join(".beads");
+        let sqlite_beads = root.join("sqlite-project").join(".beads");
+        fs::create_dir_all(dolt_beads.join(".dolt")).unwrap();
+        fs::create_dir_all(&sqlite_beads).unwrap();
+        fs::write(sqlite_beads.join("beads.db"), b"").unwrap();
 
-    let mut ref_count: u32 = 0;
+        let bd_new = Some((CliClient::Bd, 0, 55, 0));
+        assert_eq!(resolve_project_backend(bd_new, &dolt_beads), "dolt");
+        assert_eq!(resolve_project_backend(bd_new, &sqlite_beads), "bd");
+        assert_eq!(resolve_project_backend(Some((CliClient::Bd, 0, 49, 0)), &dolt_beads), "bd");
+        assert_eq!(resolve_project_backend(Some((CliClient::Br, 0, 1, 0)), &dolt_beads), "br");
 
-    for line in content.lines() {
-        if line.trim().is_empty() { continue; }
-        let v: serde_json::Value = match serde_json::from_str(line) {
-            Ok(v) => v,
-            Err(_) => continue,
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn create_applies_project_defaults_only_when_omitted() {
+        let config = ProjectConfig {
+            default_issue_type: Some("bug".to_string()),
+            default_priority: Some("p1".to_string()),
+            ..Default::default()
         };
-        if let Some(ext_ref) = v.get("external_ref").and_then(|r| r.as_str()) {
-            if ext_ref.is_empty() { continue; }
-            let refs: Vec<&str> = ext_ref.split(|c: char| c == '\n' || c == '|').collect();
-            for r in &refs {
-                let trimmed = r.trim();
-                if !trimmed.is_empty() && !is_real_external_ref(trimmed) {
-                    ref_count += 1;
-                    break; // One bad ref per issue is enough to flag it
-                }
-            }
-        }
+
+        let mut payload: CreatePayload = serde_json::from_str(r#"{"title":"New"}"#).unwrap();
+        apply_project_defaults(&mut payload, &config);
+        assert_eq!(payload.issue_type.as_deref(), Some("bug"));
+        assert_eq!(payload.priority.as_deref(), Some("p1"));
+
+        let mut explicit: CreatePayload =
+            serde_json::from_str(r#"{"title":"New","type":"feature","priority":"p3"}"#).unwrap();
+        apply_project_defaults(&mut explicit, &config);
+        assert_eq!(explicit.issue_type.as_deref(), Some("feature"));
+        assert_eq!(explicit.priority.as_deref(), Some("p3"));
+
+        let mut unconfigured: CreatePayload = serde_json::from_str(r#"{"title":"New"}"#).unwrap();
+        apply_project_defaults(&mut unconfigured, &ProjectConfig::default());
+        assert_eq!(unconfigured.issue_type, None);
+        assert_eq!(unconfigured.priority, None);
     }
 
-    // Also check if attachment folders need renaming (full-id → short-id)
-    let mut folder_work_count: u32 = 0;
-    let attachments_dir = beads_dir.join("attachments");
-    if attachments_dir.exists() {
-        if let Ok(entries) = std::fs::read_dir(&attachments_dir) {
-            for entry in entries.flatten() {
-                if !entry.path().is_dir() { continue; }
-                let name = entry.file_name().to_string_lossy().to_string();
-                if issue_short_id(&name) != name {
-                    folder_work_count += 1;
-                }
-            }
-        }
+    #[test]
+    fn closed_blocker_produces_warning() {
+        let mut blocker = issue_created_at("p-2", "2025-01-01T00:00:00Z");
+        assert_eq!(closed_blocker_warning(&blocker), None);
+        blocker.status = "closed".to_string();
+        assert_eq!(closed_blocker_warning(&blocker).as_deref(), Some("blocker is already closed"));
     }
 
-    let total = ref_count + folder_work_count;
-    if total == 0 {
-        let _ = std::fs::write(beads_dir.join(".migrated-attachments"), "");
-        return Ok(RefsMigrationStatus { needs_migration: false, ref_count: 0, just_migrated: false });
+    #[test]
+    fn poll_data_returns_partial_results_when_ready_fails() {
+        let raw = |id: &str| -> BdRawIssue { serde_json::from_str(&minimal_issue_json(id, id)).unwrap() };
+
+        let data = assemble_poll_data(
+            Ok(vec![raw("p-1"), raw("p-2")]),
+            Ok(vec![raw("p-3")]),
+            Err("ready exploded".to_string()),
+        ).unwrap();
+        assert_eq!(data.open_issues.len(), 2);
+        assert_eq!(data.closed_issues.len(), 1);
+        assert!(data.ready_issues.is_empty());
+        assert_eq!(data.errors, vec!["ready: ready exploded"]);
+
+        let all_failed = assemble_poll_data(Err("a".into()), Err("b".into()), Err("c".into()));
+        assert!(all_failed.is_err());
     }
 
-    log_info!("[refs_migration_v3] Project needs migration: {} ref(s) to clean, {} folder(s) to update", ref_count, folder_work_count);
-    Ok(RefsMigrationStatus { needs_migration: true, ref_count: total, just_migrated: false })
-}
+    #[test]
+    fn actor_resolution_order_and_mine_only_filter() {
+        assert_eq!(
+            pick_actor(Some("alice".into()), || panic!("git not needed"), Some("os".into())),
+            Some("alice".to_string())
+        );
+        assert_eq!(pick_actor(Some("  ".into()), || Some("Bob\n".into()), None), Some("Bob".to_string()));
+        assert_eq!(pick_actor(None, || None, Some("os".into())), Some("os".to_string()));
 
-/// Perform the attachment refs migration v3 (filesystem-only).
-/// Delegates to ensure_refs_migrated_v3 which handles backup, cleanup, dedup, and marker.
-/// The br sync is NOT called here — it will happen naturally after via sync_bd_database.
-#[tauri::command]
-async fn migrate_attachment_refs(cwd: Option<String>) -> Result<MigrateRefsResult, String> {
-    let working_dir = cwd
-        .or_else(|| env::var("BEADS_PATH").ok())
-        .unwrap_or_else(|| {
-            env::current_dir()
-                .map(|p| p.to_string_lossy().to_string())
-                .unwrap_or_else(|_| ".".to_string())
-        });
+        let mut mine = issue_created_at("p-1", "2025-01-01T00:00:00Z");
+        mine.assignee = Some("alice".to_string());
+        let mut theirs = issue_created_at("p-2", "2025-01-01T00:00:00Z");
+        theirs.assignee = Some("bob".to_string());
+        let unassigned = issue_created_at("p-3", "2025-01-01T00:00:00Z");
 
-    let beads_dir = PathBuf::from(&working_dir).join(".beads");
-    ensure_refs_migrated_v3(&beads_dir, &working_dir);
-    Ok(MigrateRefsResult { success: true, refs_updated: 0 })
-}
+        let mut issues = vec![mine, theirs, unassigned];
+        assert!(retain_assigned_to_actor(&mut issues.clone(), None).is_err());
+        retain_assigned_to_actor(&mut issues, Some("alice".to_string())).unwrap();
+        assert_eq!(issues.iter().map(|i| i.id.as_str()).collect::<Vec<_>>(), vec!["p-1"]);
+    }
 
-#[tauri::command]
-async fn copy_file_to_attachments(
-    project_path: String,
-    source_path: String,
-    issue_id: String,
-) -> Result<String, String> {
-    log::info!(
-        "[copy_file_to_attachments] project: {}, source: {}, issue: {}",
-        project_path,
-        source_path,
-        issue_id
-    );
+    #[test]
+    fn jsonl_export_is_written_and_lines_counted() {
+        let dir = test_dir("export");
+        let path = dir.join("nested").join("backup.jsonl");
+        let content = "{\"id\":\"p-1\"}\n{\"id\":\"p-2\"}\n\n";
 
-    // Validate file extension (images + markdown)
-    let source_lower = source_path.to_lowercase();
-    let is_allowed = IMAGE_EXTENSIONS.iter().chain(MARKDOWN_EXTENSIONS.iter())
-        .any(|ext| source_lower.ends_with(&format!(".{}", ext)));
+        assert_eq!(write_jsonl_export(&path, content).unwrap(), 2);
+        assert_eq!(fs::read_to_string(&path).unwrap(), content);
 
-    if !is_allowed {
-        return Err("Only image and markdown files are allowed".to_string());
+        let _ = fs::remove_dir_all(&dir);
     }
 
-    // Verify source file exists
-    let source = PathBuf::from(&source_path);
-    if !source.exists() {
-        return Err(format!("Source file not found: {}", source_path));
+    #[test]
+    fn show_cache_hits_until_mtime_changes_or_invalidated() {
+        let dir = "/tmp/show-cache-test-project";
+        let t0 = std::time::SystemTime::UNIX_EPOCH + Duration::from_secs(100);
+        let t1 = t0 + Duration::from_secs(1);
+        let issue = issue_created_at("p-1", "2025-01-01T00:00:00Z");
+
+        show_cache_put(dir, &issue, t0);
+        assert_eq!(show_cache_get(dir, "p-1", t0).map(|i| i.id), Some("p-1".to_string()));
+        assert!(show_cache_get(dir, "p-1", t1).is_none());
+
+        assert!(!is_read_only_command("update"));
+        assert!(is_read_only_command("show"));
+        show_cache_invalidate(dir);
+        assert!(show_cache_get(dir, "p-1", t0).is_none());
     }
 
-    // Calculate absolute project path
-    let abs_project_path = if project_path == "." || project_path.is_empty() {
-        env::current_dir().map_err(|e| format!("Failed to get current directory: {}", e))?
-    } else {
-        let p = PathBuf::from(&project_path);
-        if p.is_relative() {
-            let cwd = env::current_dir()
-                .map_err(|e| format!("Failed to get current directory: {}", e))?;
-            cwd.join(&p)
-        } else {
-            p
+    #[test]
+    fn created_range_filter_keeps_only_in_range_issues() {
+        let issues = vec![
+            issue_created_at("p-1", "2025-01-01T00:00:00Z"),
+            issue_created_at("p-2", "2025-01-15 12:00:00"),
+            issue_created_at("p-3", "2025-02-01T00:00:00+01:00"),
+            issue_created_at("p-4", "2025-03-01T00:00:00Z"),
+        ];
+        let options = ListOptions {
+            created_after: Some("2025-01-10".to_string()),
+            created_before: Some("2025-02-01T00:00:00Z".to_string()),
+            ..Default::default()
+        };
+        let ids: Vec<String> = apply_list_post_filters(issues, &options).unwrap().into_iter().map(|i| i.id).collect();
+        // p-3 is 2025-01-31T23:00:00Z once normalized
+        assert_eq!(ids, vec!["p-2", "p-3"]);
+
+        // A date-only upper bound includes the whole day
+        let issues = vec![
+            issue_created_at("p-5", "2025-02-01T00:00:00Z"),
+            issue_created_at("p-6", "2025-02-01T23:59:59.5Z"),
+            issue_created_at("p-7", "2025-02-02T00:00:00Z"),
+        ];
+        let options = ListOptions { created_before: Some("2025-02-01".to_string()), ..Default::default() };
+        let ids: Vec<String> = apply_list_post_filters(issues, &options).unwrap().into_iter().map(|i| i.id).collect();
+        assert_eq!(ids, vec!["p-5", "p-6"]);
+
+        let bad = ListOptions { created_after: Some("last tuesday".to_string()), ..Default::default() };
+        let err = apply_list_post_filters(vec![], &bad).unwrap_err();
+        assert!(err.contains("createdAfter"));
+    }
+
+    #[test]
+    fn create_recovers_when_output_fails_to_parse() {
+        // Valid JSON but missing required fields: id is still recoverable
+        let partial = r#"{"id":"p-9","title":"New"}"#;
+        assert_eq!(extract_created_id(partial).as_deref(), Some("p-9"));
+        assert_eq!(extract_created_id("✓ Created issue: p-10\n  Title: x").as_deref(), Some("p-10"));
+        assert_eq!(extract_created_id("garbage"), None);
+
+        let recovered = recover_created_issue(partial, "missing field", |id| {
+            Ok(Some(issue_created_at(id, "2025-01-01T00:00:00Z")))
+        }).unwrap();
+        assert_eq!(recovered.map(|i| i.id).as_deref(), Some("p-9"));
+
+        let err = recover_created_issue(partial, "missing field", |_| Err("show failed".to_string())).unwrap_err();
+        assert!(err.starts_with("CREATE_PARTIAL:p-9:"));
+        assert_eq!(create_partial_id(&err), Some("p-9"));
+        assert_eq!(create_partial_id("Failed to parse created issue: missing field"), None);
+    }
+
+    #[test]
+    fn dep_subgraph_contains_exactly_in_range_nodes() {
+        // Chain a → b → c → d → e, plus an unrelated f → g component and a relation b ~ h
+        let blocks: &[(&str, &str)] = &[("a", "b"), ("b", "c"), ("c", "d"), ("d", "e"), ("f", "g")];
+        let mut graph: HashMap<String, Issue> = HashMap::new();
+        for id in ["a", "b", "c", "d", "e", "f", "g", "h"] {
+            graph.insert(id.to_string(), issue_created_at(id, "2025-01-01T00:00:00Z"));
         }
-    };
+        for (blocker, blocked) in blocks {
+            graph.get_mut(*blocked).unwrap().blocked_by.get_or_insert_with(Vec::new).push(blocker.to_string());
+            graph.get_mut(*blocker).unwrap().blocks.get_or_insert_with(Vec::new).push(blocked.to_string());
+        }
+        graph.get_mut("b").unwrap().relations = Some(vec![Relation {
+            id: "h".to_string(),
+            title: String::new(),
+            status: String::new(),
+            priority: String::new(),
+            relation_type: "related".to_string(),
+            direction: "dependency".to_string(),
+        }]);
+        // Only the blocked side reports a→b, as a list may leave `blocks` out
+        graph.get_mut("a").unwrap().blocks = None;
+        let all: Vec<Issue> = graph.into_values().collect();
+
+        let sub = build_dep_subgraph("c", 1, 100, &all).unwrap();
+        let mut ids: Vec<&str> = sub.nodes.iter().map(|n| n.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["b", "c", "d"]);
+        assert_eq!(sub.edges.len(), 2);
+        assert!(!sub.truncated);
+
+        let sub = build_dep_subgraph("c", 2, 100, &all).unwrap();
+        let mut ids: Vec<&str> = sub.nodes.iter().map(|n| n.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["a", "b", "c", "d", "e", "h"]);
+
+        let capped = build_dep_subgraph("c", 10, 3, &all).unwrap();
+        assert_eq!(capped.nodes.len(), 3);
+        assert!(capped.truncated);
+
+        // A blocker that was deleted is skipped; a missing root is None
+        let mut dangling = all.clone();
+        dangling.retain(|i| i.id != "b");
+        let sub = build_dep_subgraph("c", 1, 100, &dangling).unwrap();
+        let mut ids: Vec<&str> = sub.nodes.iter().map(|n| n.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["c", "d"]);
+        assert!(build_dep_subgraph("zz", 1, 100, &all).is_none());
+    }
 
-    let abs_project_path = abs_project_path
-        .canonicalize()
-        .map_err(|e| format!("Failed to resolve project path: {}", e))?;
+    #[test]
+    fn sync_gating_follows_cooldown_or_auto_sync_interval() {
+        let secs = Duration::from_secs;
+        // Auto-sync off: reads sync after the 10s cooldown
+        assert!(sync_due(None, 0));
+        assert!(!sync_due(Some(secs(5)), 0));
+        assert!(sync_due(Some(secs(10)), 0));
+        // Auto-sync every 2 minutes: reads only sync when stale beyond the interval
+        assert!(!sync_due(Some(secs(30)), 120));
+        assert!(sync_due(Some(secs(120)), 120));
+        assert!(sync_due(None, 120));
+    }
 
-    // Build destination directory: {project}/.beads/attachments/{short_id}/
-    let attachments_dir = abs_project_path.join(".beads").join("attachments");
-    let dest_dir = resolve_attachment_dir(&attachments_dir, &issue_id);
+    #[test]
+    fn conflict_markers_in_text_fields_are_flagged() {
+        let mut value: serde_json::Value = serde_json::from_str(&minimal_issue_json("p-1", "t")).unwrap();
+        value["description"] = "Intro\n<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> origin/main\n".into();
+        assert!(transform_issue(serde_json::from_value(value.clone()).unwrap()).has_conflict_markers);
 
-    // Create directory if needed
-    fs::create_dir_all(&dest_dir)
-        .map_err(|e| format!("Failed to create attachments directory: {}", e))?;
+        value["description"] = "Title\n=======\n\nNot a conflict".into();
+        assert!(!transform_issue(serde_json::from_value(value.clone()).unwrap()).has_conflict_markers);
 
-    // Sanitize the original filename and handle duplicates
-    let raw_filename = source
-        .file_name()
-        .ok_or_else(|| "Invalid source filename".to_string())?
-        .to_string_lossy()
-        .to_string();
-    let sanitized = sanitize_filename(&raw_filename);
-    let dest_filename = resolve_duplicate_filename(&dest_dir, &sanitized);
-    let dest_path = dest_dir.join(&dest_filename);
+        value["notes"] = "<<<<<<< ours\na\n>>>>>>> theirs".into();
+        assert!(transform_issue(serde_json::from_value(value).unwrap()).has_conflict_markers);
+        assert!(!text_has_conflict_markers(">>>>>>> theirs\n<<<<<<< ours"));
+        assert!(!text_has_conflict_markers("quote: <<<<<<< inline"));
+    }
 
-    // Copy the file
-    fs::copy(&source, &dest_path).map_err(|e| format!("Failed to copy file: {}", e))?;
+    #[test]
+    fn markdown_flags_distinguish_markdown_from_plain_text() {
+        let issue = transform_issue(raw_issue_with("p-1", serde_json::json!({
+            "acceptance_criteria": "## Done when\n- [ ] tests pass\n- [ ] docs updated",
+            "design": "Use the existing cache. Nothing fancy - keep it simple.",
+        })));
 
-    log::info!("[copy_file_to_attachments] Copied to: {}", dest_path.display());
+        assert!(issue.acceptance_criteria_is_markdown);
+        assert!(!issue.design_notes_is_markdown);
+        assert!(!issue.working_notes_is_markdown);
+        assert!(looks_like_markdown("See [spec](https://example.com)"));
+        assert!(looks_like_markdown("1. first\n2. second"));
+        assert!(!looks_like_markdown("#hashtag and 3.5 ratio"));
+    }
 
-    // Return just the filename (frontend doesn't need to store it in external_ref)
-    Ok(dest_filename)
-}
+    #[test]
+    fn bulk_delete_removes_attachments_only_for_hard_delete() {
+        let project = test_dir("bulk-delete");
+        let att_root = project.join(".beads").join("attachments");
+        let ids: Vec<String> = ["proj-a1", "proj-b2", "proj-c3"].iter().map(|s| s.to_string()).collect();
+        let make_folders = || {
+            for short in ["a1", "b2", "c3"] {
+                fs::create_dir_all(att_root.join(short)).unwrap();
+                fs::write(att_root.join(short).join("f.png"), b"x").unwrap();
+            }
+        };
+        let cwd = project.to_str();
 
-// ============================================================================
-// Filesystem-based Attachment Commands
-// ============================================================================
+        make_folders();
+        let soft = bulk_delete_with(&ids, false, cwd, |_| Ok(()), |_| Ok(true));
+        assert!(soft.iter().all(|r| r.success));
+        assert!(["a1", "b2", "c3"].iter().all(|s| att_root.join(s).exists()));
+
+        let hard = bulk_delete_with(&ids, true, cwd, |_| Ok(()), |_| Ok(false));
+        assert!(hard.iter().all(|r| r.success));
+        assert!(["a1", "b2", "c3"].iter().all(|s| !att_root.join(s).exists()));
 
-#[derive(Debug, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct AttachmentFile {
-    pub filename: String,
-    pub file_type: String,   // "image" or "markdown"
-    pub path: String,        // absolute path
-    pub modified: u64,       // mtime in epoch seconds (for sorting)
-}
+        make_folders();
+        let partial = bulk_delete_with(&ids, true, cwd, |id| {
+            if id == "proj-b2" { Err("locked".to_string()) } else { Ok(()) }
+        }, |_| Ok(false));
+        assert_eq!(partial[1], BulkItemResult { id: "proj-b2".into(), success: false, error: Some("locked".into()) });
+        assert!(att_root.join("b2").exists());
+        assert!(!att_root.join("a1").exists());
 
-/// List all attachments for an issue by reading the filesystem directly.
-/// Returns images and markdown files sorted by modification time (newest first).
-#[tauri::command]
-async fn list_attachments(project_path: String, issue_id: String) -> Result<Vec<AttachmentFile>, String> {
-    let abs_project_path = if project_path == "." || project_path.is_empty() {
-        env::current_dir().map_err(|e| format!("Failed to get current directory: {}", e))?
-    } else {
-        let p = PathBuf::from(&project_path);
-        if p.is_relative() {
-            let cwd = env::current_dir()
-                .map_err(|e| format!("Failed to get current directory: {}", e))?;
-            cwd.join(&p)
-        } else {
-            p
+        let _ = fs::remove_dir_all(&project);
+    }
+
+    #[test]
+    fn list_attachments_includes_other_files_only_when_requested() {
+        let dir = test_dir("attachments");
+        fs::create_dir_all(&dir).unwrap();
+        for name in ["shot.png", "notes.md", "spec.pdf", "index.json"] {
+            fs::write(dir.join(name), b"x").unwrap();
         }
-    };
+        let names = |files: Vec<AttachmentFile>| {
+            let mut n: Vec<String> = files.into_iter().map(|f| format!("{}:{}", f.filename, f.file_type)).collect();
+            n.sort();
+            n
+        };
 
-    let attachments_dir = abs_project_path.join(".beads").join("attachments");
-    let issue_dir = resolve_attachment_dir(&attachments_dir, &issue_id);
+        assert_eq!(names(collect_attachment_files(&dir, false).unwrap()), vec!["notes.md:markdown", "shot.png:image"]);
+        assert_eq!(
+            names(collect_attachment_files(&dir, true).unwrap()),
+            vec!["notes.md:markdown", "shot.png:image", "spec.pdf:other"]
+        );
 
-    if !issue_dir.exists() || !issue_dir.is_dir() {
-        return Ok(vec![]);
+        let _ = fs::remove_dir_all(&dir);
     }
 
-    let mut files: Vec<AttachmentFile> = Vec::new();
+    #[test]
+    fn first_paint_poll_skips_closed_issues() {
+        assert_eq!(poll_list_plan(false, true), PollListPlan::OpenOnly);
+        assert_eq!(poll_list_plan(false, false), PollListPlan::OpenOnly);
+        assert_eq!(poll_list_plan(true, true), PollListPlan::AllInOne);
+        assert_eq!(poll_list_plan(true, false), PollListPlan::OpenThenClosed);
 
-    let entries = fs::read_dir(&issue_dir)
-        .map_err(|e| format!("Failed to read attachment directory: {}", e))?;
+        let raw = |id: &str| -> BdRawIssue { serde_json::from_str(&minimal_issue_json(id, id)).unwrap() };
+        let data = assemble_poll_data(Ok(vec![raw("p-1")]), Ok(Vec::new()), Ok(vec![raw("p-1")])).unwrap();
+        assert!(data.closed_issues.is_empty());
+        assert!(data.errors.is_empty());
+    }
 
-    for entry in entries.flatten() {
-        let path = entry.path();
-        if !path.is_file() { continue; }
+    #[test]
+    fn labels_are_trimmed_deduped_and_optionally_lowercased() {
+        let labels: Vec<String> = ["Backend", "backend ", " backend", "", "  ", "UI", "Backend"]
+            .iter().map(|s| s.to_string()).collect();
+        assert_eq!(normalize_labels(&labels, false), vec!["Backend", "backend", "UI"]);
+        assert_eq!(normalize_labels(&labels, true), vec!["backend", "ui"]);
+    }
 
-        let name = entry.file_name().to_string_lossy().to_string();
-        // Skip legacy index.json files
-        if name == "index.json" { continue; }
+    #[test]
+    fn relations_of_type_returns_only_requested_type() {
+        let dup = raw_issue_with("p-1", serde_json::json!({ "dependencies": [
+            {"issue_id": "p-1", "depends_on_id": "p-2", "type": "duplicates"},
+            {"issue_id": "p-1", "depends_on_id": "p-3", "type": "supersedes"}
+        ]}));
+        let issues = vec![
+            transform_issue(dup),
+            transform_issue(serde_json::from_str(&minimal_issue_json("p-2", "Original")).unwrap()),
+            transform_issue(serde_json::from_str(&minimal_issue_json("p-3", "Old")).unwrap()),
+        ];
 
-        let file_type = classify_attachment(&name);
-        // Only return images and markdown
-        if file_type == "other" { continue; }
+        let pairs = relation_pairs_of_type(&issues, "duplicates");
+        assert_eq!(pairs.len(), 1);
+        assert_eq!((pairs[0].from.id.as_str(), pairs[0].to.id.as_str()), ("p-1", "p-2"));
+        assert_eq!(pairs[0].to.title, "Original");
+        assert!(relation_pairs_of_type(&issues, "related").is_empty());
+    }
 
-        let modified = entry.metadata()
-            .and_then(|m| m.modified())
-            .ok()
-            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-            .map(|d| d.as_secs())
-            .unwrap_or(0);
+    #[test]
+    fn env_restriction_is_opt_in_and_only_strips_unlisted_bd_vars() {
+        let vars = || vec![
+            ("BEADS_ACTOR".to_string(), "alice".to_string()),
+            ("DOLT_REMOTE_PASSWORD".to_string(), "secret".to_string()),
+            ("GIT_SSH_COMMAND".to_string(), "ssh -i key".to_string()),
+            ("BD_CONFIG".to_string(), "/etc/bd".to_string()),
+            ("HOME".to_string(), "/home/alice".to_string()),
+        ].into_iter();
+        // Vars the command overrides or removes; anything absent is inherited
+        let overrides = |cmd: &Command| -> Vec<(String, Option<String>)> {
+            cmd.get_envs()
+                .map(|(k, v)| (k.to_string_lossy().to_string(), v.map(|v| v.to_string_lossy().to_string())))
+                .collect()
+        };
 
-        files.push(AttachmentFile {
-            filename: name,
-            file_type: file_type.to_string(),
-            path: path.to_string_lossy().to_string(),
-            modified,
+        let mut cmd = Command::new("bd");
+        restrict_env(&mut cmd, vars(), None);
+        assert!(overrides(&cmd).is_empty());
+
+        let allow = vec!["BEADS_*".to_string(), "BD_CONFIG".to_string()];
+        let mut cmd = Command::new("bd");
+        restrict_env(&mut cmd, vars(), Some(&allow));
+        assert_eq!(overrides(&cmd), vec![
+            ("DOLT_REMOTE_PASSWORD".to_string(), None),
+            ("GIT_SSH_COMMAND".to_string(), None),
+        ]);
+    }
+
+    #[test]
+    fn id_pattern_filters_mixed_prefix_set() {
+        let issues = vec![
+            issue_created_at("frontend-1a", "2025-01-01T00:00:00Z"),
+            issue_created_at("backend-2b", "2025-01-01T00:00:00Z"),
+            issue_created_at("frontend-3c", "2025-01-01T00:00:00Z"),
+            issue_created_at("frontend-ops-4d", "2025-01-01T00:00:00Z"),
+        ];
+        let filter = |pattern: &str| -> Result<Vec<String>, String> {
+            let options = ListOptions { id_pattern: Some(pattern.to_string()), ..Default::default() };
+            Ok(apply_list_post_filters(issues.clone(), &options)?.into_iter().map(|i| i.id).collect())
+        };
+
+        assert_eq!(filter("frontend-").unwrap(), vec!["frontend-1a", "frontend-3c", "frontend-ops-4d"]);
+        assert_eq!(filter("frontend-??").unwrap(), vec!["frontend-1a", "frontend-3c"]);
+        assert_eq!(filter("*-2*").unwrap(), vec!["backend-2b"]);
+        assert!(filter("front%' OR 1=1").is_err());
+    }
+
+    #[test]
+    fn app_config_round_trips_and_fills_defaults() {
+        let mut projects = HashMap::new();
+        projects.insert("/work/app".to_string(), ProjectConfig {
+            default_priority: Some("p1".to_string()),
+            lowercase_labels: true,
+            ..Default::default()
         });
+        let config = AppConfig {
+            cli_binary: "/opt/bin/br".to_string(),
+            projects,
+            env_passthrough: Some(vec!["BEADS_*".to_string()]),
+            auto_sync_interval_secs: 120,
+            auto_backup: Some(AutoBackupConfig { dir: "/backups".to_string(), interval_secs: 3600, keep: 5 }),
+            command_timeout_secs: 45,
+            recent_searches: HashMap::from([("/work/app".to_string(), vec!["crash".to_string()])]),
+            watch_debounce_ms: 500,
+            logging_enabled: true,
+            verbose_logging: true,
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(serde_json::from_str::<AppConfig>(&json).unwrap(), config);
+
+        // A settings.json from before the new fields still loads
+        let old: AppConfig = serde_json::from_str(r#"{"cli_binary":"bd"}"#).unwrap();
+        assert_eq!(old.env_passthrough, None);
+        assert_eq!(old.auto_sync_interval_secs, 0);
+        assert_eq!(old.auto_backup, None);
+        assert_eq!(old.command_timeout_secs, DEFAULT_COMMAND_TIMEOUT_SECS);
+        assert_eq!(old.watch_debounce_ms, DEFAULT_WATCH_DEBOUNCE_MS);
+        assert!(!old.logging_enabled && !old.verbose_logging);
+        assert!(old.projects.is_empty());
     }
 
-    // Sort by mtime descending (newest first)
-    files.sort_by(|a, b| b.modified.cmp(&a.modified));
+    #[test]
+    fn config_update_refuses_to_overwrite_unreadable_settings() {
+        let dir = test_dir("config-update");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("settings.json");
 
-    Ok(files)
-}
+        fs::write(&path, "{ not json").unwrap();
+        assert!(update_config_at(&path, |c| c.watch_debounce_ms = 700).unwrap_err().starts_with("Settings not saved"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{ not json");
 
-/// Delete an attachment file by filename within an issue's attachment directory.
-#[tauri::command]
-async fn delete_attachment(project_path: String, issue_id: String, filename: String) -> Result<(), String> {
-    log::info!("[delete_attachment] project: {}, issue: {}, file: {}", project_path, issue_id, filename);
+        fs::write(&path, r#"{"cli_binary":"bd","recent_searches":{"/p":["old"]}}"#).unwrap();
+        update_config_at(&path, |c| push_recent_search(c.recent_searches.entry("/p".to_string()).or_default(), "new", 5)).unwrap();
+        let saved = read_config_at(&path).unwrap();
+        assert_eq!(saved.cli_binary, "bd");
+        assert_eq!(saved.recent_searches["/p"], vec!["new", "old"]);
+        let _ = fs::remove_dir_all(&dir);
+    }
 
-    // Security: reject path traversal
-    if filename.contains('/') || filename.contains('\\') || filename.contains("..") {
-        return Err("Invalid filename".to_string());
+    #[test]
+    fn tree_order_puts_parents_first_with_depth() {
+        let issues = vec![
+            issue_with_parent("task-b", Some("story-1")),
+            issue_with_parent("story-1", Some("epic")),
+            issue_with_parent("orphan", Some("deleted-epic")),
+            issue_with_parent("epic", None),
+            issue_with_parent("task-a", Some("story-1")),
+        ];
+        let options = ListOptions { tree: Some(true), ..Default::default() };
+        let ordered: Vec<(String, usize)> = apply_list_post_filters(issues, &options).unwrap()
+            .into_iter().map(|i| (i.id, i.depth.unwrap())).collect();
+
+        assert_eq!(ordered, vec![
+            ("orphan".to_string(), 0),
+            ("epic".to_string(), 0),
+            ("story-1".to_string(), 1),
+            ("task-b".to_string(), 2),
+            ("task-a".to_string(), 2),
+        ]);
+
+        let options = ListOptions { tree: Some(true), ..Default::default() };
+        let counts: Vec<(String, usize)> = apply_list_post_filters(vec![
+            issue_with_parent("epic", None),
+            issue_with_parent("a", Some("epic")),
+            issue_with_parent("b", Some("epic")),
+        ], &options).unwrap()
+            .into_iter().map(|i| (i.id, i.child_count.unwrap())).collect();
+        assert_eq!(counts, vec![("epic".to_string(), 2), ("a".to_string(), 0), ("b".to_string(), 0)]);
     }
 
-    let abs_project_path = if project_path == "." || project_path.is_empty() {
-        env::current_dir().map_err(|e| format!("Failed to get current directory: {}", e))?
-    } else {
-        let p = PathBuf::from(&project_path);
-        if p.is_relative() {
-            let cwd = env::current_dir()
-                .map_err(|e| format!("Failed to get current directory: {}", e))?;
-            cwd.join(&p)
-        } else {
-            p
+    #[test]
+    fn jsonl_conflict_markers_are_detected_with_issue_ids() {
+        let clean = "{\"id\":\"p-1\",\"title\":\"a\"}\n{\"id\":\"p-2\",\"title\":\"b\"}\n";
+        assert_eq!(jsonl_conflict_ids(clean), None);
+
+        let conflicted = concat!(
+            "{\"id\":\"p-1\",\"title\":\"a\"}\n",
+            "<<<<<<< HEAD\n",
+            "{\"id\":\"p-2\",\"title\":\"mine\"}\n",
+            "=======\n",
+            "{\"id\":\"p-2\",\"title\":\"theirs\"}\n",
+            "{\"id\":\"p-3\",\"title\":\"new\"}\n",
+            ">>>>>>> origin/main\n",
+            "{\"id\":\"p-4\",\"title\":\"d\"}\n",
+        );
+        assert_eq!(jsonl_conflict_ids(conflicted), Some(vec!["p-2".to_string(), "p-3".to_string()]));
+
+        let dir = test_dir("conflict");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("issues.jsonl"), conflicted).unwrap();
+        let err = check_jsonl_merge_conflict(&dir).unwrap_err();
+        assert!(err.starts_with("MERGE_CONFLICT:p-2,p-3:"));
+        fs::write(dir.join("issues.jsonl"), clean).unwrap();
+        assert!(check_jsonl_merge_conflict(&dir).is_ok());
+
+        // The cached check only re-reads the file after its mtime moved
+        let key = dir.to_string_lossy().to_string();
+        let jsonl = dir.join("issues.jsonl");
+        fs::write(&jsonl, conflicted).unwrap();
+        let conflicted_mtime = fs::metadata(&jsonl).unwrap().modified().unwrap();
+        assert!(cached_merge_conflict(&key, &dir).is_some());
+        fs::write(&jsonl, clean).unwrap();
+        fs::File::options().write(true).open(&jsonl).unwrap().set_modified(conflicted_mtime).unwrap();
+        assert!(cached_merge_conflict(&key, &dir).is_some());
+        fs::File::options().write(true).open(&jsonl).unwrap()
+            .set_modified(conflicted_mtime + Duration::from_secs(1)).unwrap();
+        assert!(cached_merge_conflict(&key, &dir).is_none());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn poll_data_compression_round_trips_large_payloads() {
+        use std::io::Read;
+        let open: Vec<Issue> = (0..5000)
+            .map(|n| issue_created_at(&format!("p-{}", n), "2025-01-01T00:00:00Z"))
+            .collect();
+        let poll = |issues: Vec<Issue>| PollData {
+            open_issues: issues,
+            closed_issues: Vec::new(),
+            ready_issues: Vec::new(),
+            errors: Vec::new(),
+            high_watermark: None,
+            deleted_ids: None,
+            live_ids: None,
+            parse_warnings: Vec::new(),
+            merge_conflict: None,
+        };
+        let expected = serde_json::to_vec(&poll(open.clone())).unwrap();
+
+        let gz = gzip_bytes(&expected).unwrap();
+        assert!(gz.len() < expected.len() / 4);
+        let mut decoded = Vec::new();
+        flate2::read::GzDecoder::new(&gz[..]).read_to_end(&mut decoded).unwrap();
+        assert_eq!(decoded, expected);
+
+        match compress_poll_data(poll(open), POLL_COMPRESS_THRESHOLD_BYTES).unwrap() {
+            PollPayload::Compressed { compressed, data } => {
+                assert!(compressed);
+                assert_eq!(data, base64_encode(&gz));
+            }
+            PollPayload::Raw(_) => panic!("expected a compressed payload"),
         }
-    };
+        let small = compress_poll_data(poll(Vec::new()), POLL_COMPRESS_THRESHOLD_BYTES).unwrap();
+        assert!(matches!(small, PollPayload::Raw(_)));
+    }
 
-    let abs_project_path = abs_project_path
-        .canonicalize()
-        .map_err(|e| format!("Failed to resolve project path: {}", e))?;
+    #[test]
+    fn validate_dependencies_finds_dangling_self_and_duplicate_edges() {
+        let raw = |id: &str, deps: &[(&str, &str)]| -> BdRawIssue {
+            let deps: Vec<serde_json::Value> = deps.iter()
+                .map(|(to, t)| serde_json::json!({ "issue_id": id, "depends_on_id": to, "type": t }))
+                .collect();
+            raw_issue_with(id, serde_json::json!({ "dependencies": deps }))
+        };
+        let a = raw("p-a", &[("p-b", "blocks"), ("p-gone", "blocks"), ("p-b", "blocks"), ("p-c", "parent-child")]);
+        let b = raw("p-b", &[("p-b", "blocks"), ("p-a", "related")]);
+        let c = raw("p-c", &[("p-a", "blocks"), ("p-a", "related")]);
 
-    let attachments_dir = abs_project_path.join(".beads").join("attachments");
-    let issue_dir = resolve_attachment_dir(&attachments_dir, &issue_id);
-    let file_path = issue_dir.join(&filename);
+        let report = validate_dependencies(&[a.clone(), b, c]);
+        let pairs = |edges: &[DepEdge]| edges.iter().map(|e| (e.from.clone(), e.to.clone())).collect::<Vec<_>>();
 
-    if !file_path.exists() {
-        log::info!("[delete_attachment] File does not exist: {:?}", file_path);
-        return Ok(());
+        assert_eq!(report.checked_issues, 3);
+        assert_eq!(pairs(&report.dangling), vec![("p-a".to_string(), "p-gone".to_string())]);
+        assert_eq!(pairs(&report.self_deps), vec![("p-b".to_string(), "p-b".to_string())]);
+        assert_eq!(pairs(&report.duplicates), vec![("p-a".to_string(), "p-b".to_string())]);
+        assert_eq!(report.duplicates[0].edge_type, "blocks");
+        assert_eq!(report.affected_ids, vec!["p-a".to_string(), "p-b".to_string()]);
+
+        // The copies are only visible before transform_issue collapses them
+        assert_eq!(transform_issue(a).blocked_by, Some(vec!["p-b".to_string(), "p-gone".to_string()]));
+
+        let check = dependency_check(&report);
+        assert_eq!(check.status, CheckStatus::Warn);
+        assert_eq!(check.message, "1 dangling, 1 self, 1 duplicate dependencies in p-a, p-b");
+        assert_eq!(dependency_check(&DepValidationReport { checked_issues: 2, ..Default::default() }).status, CheckStatus::Pass);
     }
 
-    // Security: verify file is inside .beads/attachments/
-    let canonical = file_path.canonicalize()
-        .map_err(|e| format!("Failed to resolve path: {}", e))?;
-    let canonical_str = canonical.to_string_lossy();
-    if !canonical_str.contains("/.beads/attachments/") {
-        return Err("Can only delete files inside .beads/attachments/".to_string());
+    #[test]
+    fn create_can_start_in_progress() {
+        assert_eq!(initial_status(Some("in_progress")).unwrap(), Some("in_progress"));
+        assert_eq!(initial_status(Some("open")).unwrap(), None);
+        assert_eq!(initial_status(None).unwrap(), None);
+        assert!(initial_status(Some("closed")).is_err());
+        assert!(initial_status(Some("doing")).is_err());
+
+        // `create` itself never carries the status; it's applied with `update` afterwards
+        let payload: CreatePayload = serde_json::from_value(serde_json::json!({
+            "title": "Start now",
+            "status": "in_progress",
+            "assignee": "me",
+        })).unwrap();
+        let args = create_args(&payload).unwrap();
+        assert_eq!(args, vec!["Start now", "--assignee", "me"]);
     }
 
-    fs::remove_file(&file_path)
-        .map_err(|e| format!("Failed to delete file: {}", e))?;
+    #[test]
+    fn log_lines_parse_into_entries() {
+        let sync = parse_log_line("[2025-03-01][09:15:02][beads_task_issue_tracker_lib][INFO] [sync] Sync completed successfully");
+        assert_eq!(sync, LogEntry {
+            timestamp: Some("2025-03-01 09:15:02".to_string()),
+            level: Some("INFO".to_string()),
+            category: Some("sync".to_string()),
+            message: "Sync completed successfully".to_string(),
+        });
 
-    log::info!("[delete_attachment] Deleted: {:?}", file_path);
+        let plain = parse_log_line("[2025-03-01][09:15:03][tauri][WARN] === starting ===");
+        assert_eq!(plain.level.as_deref(), Some("WARN"));
+        assert_eq!(plain.category, None);
+        assert_eq!(plain.message, "=== starting ===");
 
-    // Cleanup empty folder (issue_dir already resolved above via resolve_attachment_dir)
-    if issue_dir.exists() {
-        if let Ok(entries) = fs::read_dir(&issue_dir) {
-            // Count non-index.json entries
-            let count = entries.flatten()
-                .filter(|e| e.file_name().to_string_lossy() != "index.json")
-                .count();
-            if count == 0 {
-                // Remove index.json if present, then the directory
-                let _ = fs::remove_file(issue_dir.join("index.json"));
-                let _ = fs::remove_dir(&issue_dir);
-                log::info!("[delete_attachment] Cleaned up empty folder: {:?}", issue_dir);
-            }
-        }
+        let raw = parse_log_line("  continuation of a multi-line error");
+        assert_eq!(raw.timestamp, None);
+        assert_eq!(raw.level, None);
+        assert_eq!(raw.message, "  continuation of a multi-line error");
     }
 
-    Ok(())
-}
+    #[test]
+    fn changed_fields_reports_only_edited_fields() {
+        let before = issue_created_at("p-1", "2025-01-01T00:00:00Z");
+        let mut after = before.clone();
+        after.title = "Renamed".to_string();
+        after.priority = "p0".to_string();
+        after.updated_at = "2025-02-01T00:00:00Z".to_string();
 
-#[derive(Debug, Serialize)]
-pub struct TextData {
-    pub content: String,
-}
+        assert_eq!(changed_issue_fields(&before, &after), vec!["title", "priority"]);
+        assert!(changed_issue_fields(&before, &before).is_empty());
+    }
 
-#[tauri::command]
-async fn read_text_file(path: String) -> Result<TextData, String> {
-    log_info!("[read_text_file] Reading: {}", path);
+    #[test]
+    fn type_counts_include_custom_types() {
+        let raw = |id: &str, issue_type: &str, status: &str| -> BdRawIssue {
+            raw_issue_with(id, serde_json::json!({ "issue_type": issue_type, "status": status }))
+        };
+        let issues = vec![
+            raw("p-1", "bug", "open"),
+            raw("p-2", "spike", "open"),
+            raw("p-3", "Bug", "closed"),
+            raw("p-4", "task", "open"),
+            raw("p-5", "spike", "tombstone"),
+        ];
+        let count = |t: &str, n: usize| TypeCount { issue_type: t.to_string(), count: n };
+        assert_eq!(count_issue_types(&issues), vec![count("bug", 2), count("spike", 1), count("task", 1)]);
+    }
 
-    // Security: Only allow markdown file extensions
-    let path_lower = path.to_lowercase();
-    let is_markdown = path_lower.ends_with(".md") || path_lower.ends_with(".markdown");
+    #[test]
+    fn base64_decode_round_trips_encode() {
+        assert_eq!(base64_decode("TWFu").unwrap(), b"Man");
+        assert_eq!(base64_decode("TWE=").unwrap(), b"Ma");
+        assert_eq!(base64_decode("TQ==").unwrap(), b"M");
+        assert_eq!(base64_decode("TQ").unwrap(), b"M");
+        assert_eq!(base64_decode("").unwrap(), b"");
+
+        let all_bytes: Vec<u8> = (0..=255).collect();
+        for len in 0..=all_bytes.len() {
+            let data = &all_bytes[..len];
+            assert_eq!(base64_decode(&base64_encode(data)).unwrap(), data, "len {}", len);
+        }
+        let png_header = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+        let wrapped = base64_encode(&png_header).chars()
+            .enumerate()
+            .flat_map(|(i, c)| if i == 4 { vec!['\n', c] } else { vec![c] })
+            .collect::<String>();
+        assert_eq!(base64_decode(&wrapped).unwrap(), png_header);
+    }
 
-    if !is_markdown {
-        return Err("Only markdown files are allowed".to_string());
+    #[test]
+    fn base64_decode_rejects_malformed_input() {
+        assert!(base64_decode("TWF!").is_err());
+        assert!(base64_decode("TWFuT").is_err());
+        assert!(base64_decode("TQ=").is_err());
+        assert!(base64_decode("T===").is_err());
+        assert!(base64_decode("TW=u").is_err());
     }
 
-    // Verify file exists
-    if !std::path::Path::new(&path).exists() {
-        return Err(format!("File not found: {}", path));
+    #[test]
+    fn attachment_dir_rejects_ids_that_escape_it() {
+        let attachments = std::path::Path::new("/p/.beads/attachments");
+        assert_eq!(resolve_attachment_dir(attachments, "proj-a1b2").unwrap(), attachments.join("a1b2"));
+        assert_eq!(resolve_attachment_dir(attachments, "proj-a1b2.1").unwrap(), attachments.join("a1b2.1"));
+        for bad in ["a-../../../x", "../../etc", "a-x/y", "a-x\\y", "a-..", "a-"] {
+            assert!(resolve_attachment_dir(attachments, bad).is_err(), "{}", bad);
+        }
     }
 
-    // Security: Canonicalize to resolve symlinks/.. and verify inside .beads/attachments/
-    let canonical = std::path::Path::new(&path).canonicalize()
-        .map_err(|e| format!("Failed to resolve path: {}", e))?;
-    let canonical_str = canonical.to_string_lossy();
-    if !canonical_str.contains("/.beads/attachments/") {
-        log_warn!("[read_text_file] Refusing to read file outside attachments: {} (resolved: {})", path, canonical_str);
-        return Err("Can only read files inside .beads/attachments/".to_string());
+    #[test]
+    fn identical_attachment_is_copied_once() {
+        let dir = test_dir("attach-dedupe");
+        let dest = dir.join("attachments");
+        fs::create_dir_all(&dest).unwrap();
+        let first = dir.join("shot.png");
+        let second = dir.join("shot copy.png");
+        let other = dir.join("other.png");
+        fs::write(&first, b"same pixels").unwrap();
+        fs::write(&second, b"same pixels").unwrap();
+        fs::write(&other, b"other pixels").unwrap();
+
+        let a = copy_into_attachment_dir(&first, &dest).unwrap();
+        let b = copy_into_attachment_dir(&second, &dest).unwrap();
+        assert!(!a.deduplicated);
+        assert!(b.deduplicated);
+        assert_eq!(b.filename, a.filename);
+        assert_eq!(fs::read_dir(&dest).unwrap().count(), 1);
+
+        // Different content under the same sanitized name still gets its own file
+        fs::rename(&other, &first).unwrap();
+        let c = copy_into_attachment_dir(&first, &dest).unwrap();
+        assert!(!c.deduplicated);
+        assert_ne!(c.filename, a.filename);
+        assert_eq!(fs::read_dir(&dest).unwrap().count(), 2);
+        let _ = fs::remove_dir_all(&dir);
     }
 
-    // Read file as UTF-8
-    let content = fs::read_to_string(&path)
-        .map_err(|e| format!("Failed to read file: {}", e))?;
+    #[test]
+    fn last_actor_filter_matches_nothing_without_tracking() {
+        let issues = vec![issue_created_at("p-1", "2025-01-01T00:00:00Z")];
+        let options = ListOptions { last_actor: Some("bob".to_string()), ..Default::default() };
+        assert!(apply_list_post_filters(issues.clone(), &options).unwrap().is_empty());
 
-    Ok(TextData { content })
-}
+        let blank = ListOptions { last_actor: Some(String::new()), ..Default::default() };
+        assert_eq!(apply_list_post_filters(issues, &blank).unwrap().len(), 1);
+    }
 
-#[tauri::command]
-async fn write_text_file(path: String, content: String) -> Result<(), String> {
-    log_info!("[write_text_file] Writing: {}", path);
+    #[test]
+    fn safe_mode_skips_sync_and_gates_migrations() {
+        assert!(!sync_bd_database_in_mode(Some("/nonexistent/beads-safe-mode-test"), true));
 
-    // Security: Only allow markdown file extensions
-    let path_lower = path.to_lowercase();
-    let is_markdown = path_lower.ends_with(".md") || path_lower.ends_with(".markdown");
+        let unconfirmed = check_safe_mode_confirmation(true, "Dolt migration", None);
+        assert!(unconfirmed.unwrap_err().starts_with("SAFE_MODE:"));
+        assert!(check_safe_mode_confirmation(true, "Dolt migration", Some(true)).is_ok());
+        assert!(check_safe_mode_confirmation(false, "Dolt migration", None).is_ok());
+    }
 
-    if !is_markdown {
-        return Err("Only markdown files are allowed".to_string());
+    #[test]
+    fn blocker_batch_rejects_cycles_and_skips_duplicates() {
+        let blocked_by = |id: &str, blockers: &[&str]| {
+            let mut issue = issue_created_at(id, "2025-01-01T00:00:00Z");
+            issue.blocked_by = Some(blockers.iter().map(|b| b.to_string()).collect());
+            issue
+        };
+        // t blocks u, u blocks v: making v a blocker of t would close t → u → v → t
+        let issues = vec![
+            blocked_by("t", &["old"]),
+            blocked_by("u", &["t"]),
+            blocked_by("v", &["u"]),
+            blocked_by("old", &[]),
+            blocked_by("a", &[]),
+            blocked_by("b", &[]),
+        ];
+        let ids = |ids: &[&str]| ids.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let results = plan_blocker_batch("t", &ids(&["a", "v", "b", "a", "old", "t", "ghost"]), &issues);
+        let summary: Vec<(&str, bool, bool)> = results.iter()
+            .map(|r| (r.blocker_id.as_str(), r.added, r.error.is_some()))
+            .collect();
+        assert_eq!(summary, vec![
+            ("a", true, false),
+            ("v", false, true),
+            ("b", true, false),
+            ("a", false, false),
+            ("old", false, false),
+            ("t", false, true),
+            ("ghost", false, true),
+        ]);
+        assert!(results[1].error.as_deref().unwrap().contains("cycle"));
     }
 
-    // Verify file exists (no creation of new files)
-    if !std::path::Path::new(&path).exists() {
-        return Err(format!("File not found: {}", path));
+    #[test]
+    fn subtree_nests_children_recursively() {
+        let issues = vec![
+            issue_with_parent("epic", None),
+            issue_with_parent("story-1", Some("epic")),
+            issue_with_parent("task-1a", Some("story-1")),
+            issue_with_parent("task-1b", Some("story-1")),
+            issue_with_parent("story-2", Some("epic")),
+            issue_with_parent("other", None),
+            // Parent cycle below the epic must not loop forever
+            issue_with_parent("loop-a", Some("loop-b")),
+            issue_with_parent("loop-b", Some("loop-a")),
+        ];
+        let tree = build_subtree("epic", &issues);
+        let shape: Vec<(String, Vec<String>)> = tree.iter()
+            .map(|c| (c.id.clone(), c.children.iter().flatten().map(|g| g.id.clone()).collect()))
+            .collect();
+        assert_eq!(shape, vec![
+            ("story-1".to_string(), vec!["task-1a".to_string(), "task-1b".to_string()]),
+            ("story-2".to_string(), vec![]),
+        ]);
+        assert!(tree[0].children.as_ref().unwrap()[0].children.is_none());
+
+        let looped = build_subtree("loop-a", &issues);
+        assert_eq!(looped.len(), 1);
+        assert_eq!(looped[0].id, "loop-b");
+        assert!(looped[0].children.is_none());
+        assert!(build_subtree("other", &issues).is_empty());
     }
 
-    // Security: Canonicalize to resolve symlinks/.. and verify inside .beads/attachments/
-    let canonical = std::path::Path::new(&path).canonicalize()
-        .map_err(|e| format!("Failed to resolve path: {}", e))?;
-    let canonical_str = canonical.to_string_lossy();
-    if !canonical_str.contains("/.beads/attachments/") {
-        log_warn!("[write_text_file] Refusing to write file outside attachments: {} (resolved: {})", path, canonical_str);
-        return Err("Can only write files inside .beads/attachments/".to_string());
+    #[test]
+    fn transitive_blocking_count_follows_chains() {
+        let blocked_by = |id: &str, blockers: &[&str]| {
+            let mut issue = issue_created_at(id, "2025-01-01T00:00:00Z");
+            issue.blocked_by = Some(blockers.iter().map(|b| b.to_string()).collect());
+            issue
+        };
+        // A blocks B, B blocks C; C blocks A closes a cycle
+        let mut issues = vec![blocked_by("a", &[]), blocked_by("b", &["a"]), blocked_by("c", &["b"])];
+        assert_eq!(transitive_blocking_count("a", &issues), 2);
+        assert_eq!(transitive_blocking_count("b", &issues), 1);
+        assert_eq!(transitive_blocking_count("c", &issues), 0);
+
+        issues[0].blocked_by = Some(vec!["c".to_string()]);
+        assert_eq!(transitive_blocking_count("a", &issues), 2);
+
+        issues[1].status = "closed".to_string();
+        assert_eq!(transitive_blocking_count("a", &issues), 0);
     }
 
-    // Write content to file
-    fs::write(&path, &content)
-        .map_err(|e| format!("Failed to write file: {}", e))?;
+    #[test]
+    fn minimal_list_entries_omit_heavy_fields() {
+        let mut issue = issue_created_at("p-1", "2025-01-01T00:00:00Z");
+        issue.description = "long text".to_string();
+        issue.labels = vec!["ui".to_string()];
+        let value = serde_json::to_value(IssueSummary::from(issue)).unwrap();
+        let mut keys: Vec<&String> = value.as_object().unwrap().keys().collect();
+        keys.sort();
+        assert_eq!(keys, vec!["id", "status", "title", "type"]);
+    }
 
-    log_info!("[write_text_file] Written {} bytes to {}", content.len(), path);
-    Ok(())
-}
+    #[test]
+    fn blocking_edge_direction_is_explicit() {
+        // bd_add_blocker("a", "b"): b blocks a → `dep add a b`
+        assert_eq!(blocking_edge("a", "b", BlockDirection::BlockedBy), ("a".to_string(), "b".to_string()));
+        // bd_add_blocks("a", "b"): a blocks b → `dep add b a`
+        assert_eq!(blocking_edge("a", "b", BlockDirection::Blocks), ("b".to_string(), "a".to_string()));
+    }
 
-// ============================================================================
-// File Watcher Commands
-// ============================================================================
+    #[test]
+    fn inactive_polling_skips_the_mtime_check() {
+        let dir = test_dir("poll-active").to_string_lossy().to_string();
+        let mut stats = 0;
+        let mut stat = |_: &std::path::Path| {
+            stats += 1;
+            Some(std::time::SystemTime::UNIX_EPOCH)
+        };
 
-#[tauri::command]
-fn start_watching(
-    path: String,
-    app: tauri::AppHandle,
-    state: tauri::State<'_, Mutex<WatcherState>>,
-) -> Result<(), String> {
-    let mut watcher_state = state.lock().map_err(|e| format!("Lock error: {}", e))?;
+        assert!(!check_project_changed(dir.clone(), false, &mut stat));
+        assert!(check_project_changed(dir.clone(), true, &mut stat));
+        assert!(!check_project_changed(dir.clone(), true, &mut stat));
+        assert_eq!(stats, 2);
+        LAST_KNOWN_MTIME.lock().unwrap().remove(&dir);
+    }
 
-    // Stop existing watcher if any
-    if watcher_state.debouncer.is_some() {
-        log::info!("[watcher] Stopping previous watcher for: {:?}", watcher_state.watched_path);
-        watcher_state.debouncer = None;
-        watcher_state.watched_path = None;
+    #[test]
+    fn relative_due_dates_resolve_to_iso() {
+        // 2025-06-12 is a Thursday
+        let today = days_from_civil(2025, 6, 12);
+        assert_eq!(parse_relative_date("+3d", today).unwrap(), "2025-06-15");
+        assert_eq!(parse_relative_date("+2w", today).unwrap(), "2025-06-26");
+        assert_eq!(parse_relative_date("in 3 days", today).unwrap(), "2025-06-15");
+        assert_eq!(parse_relative_date("Tomorrow", today).unwrap(), "2025-06-13");
+        assert_eq!(parse_relative_date("next friday", today).unwrap(), "2025-06-13");
+        assert_eq!(parse_relative_date("thursday", today).unwrap(), "2025-06-19");
+        assert_eq!(parse_relative_date("2025-07-01", today).unwrap(), "2025-07-01");
+
+        assert!(parse_relative_date("someday", today).is_err());
+        assert!(parse_relative_date("+3x", today).is_err());
+        assert!(parse_relative_date("2025-02-30", today).is_err());
+
+        // Huge offsets are rejected instead of overflowing
+        let too_far = parse_relative_date("+9223372036854775807d", today).unwrap_err();
+        assert!(too_far.contains("too far"), "{}", too_far);
+        assert!(parse_relative_date("+2000000000000000000w", today).is_err());
+        assert!(parse_relative_date("in 3000000 days", today).is_err());
+    }
+
+    #[test]
+    fn group_issues_buckets_by_priority_and_label() {
+        let issue = |id: &str, priority: &str, labels: &[&str]| {
+            let mut issue = issue_created_at(id, "2025-01-01T00:00:00Z");
+            issue.priority = priority.to_string();
+            issue.labels = labels.iter().map(|l| l.to_string()).collect();
+            issue
+        };
+        let issues = vec![
+            issue("p-1", "p2", &["ui"]),
+            issue("p-2", "p0", &["ui", "api"]),
+            issue("p-3", "p2", &[]),
+        ];
+        let summary = |groups: Vec<IssueGroup>| -> Vec<(String, Vec<String>)> {
+            groups.into_iter().map(|g| (g.group, g.issues.into_iter().map(|i| i.id).collect())).collect()
+        };
+
+        assert_eq!(summary(group_issues(issues.clone(), "priority")), vec![
+            ("p0".to_string(), vec!["p-2".to_string()]),
+            ("p2".to_string(), vec!["p-1".to_string(), "p-3".to_string()]),
+        ]);
+        assert_eq!(summary(group_issues(issues, "label")), vec![
+            ("api".to_string(), vec!["p-2".to_string()]),
+            ("ui".to_string(), vec!["p-1".to_string(), "p-2".to_string()]),
+            ("unlabeled".to_string(), vec!["p-3".to_string()]),
+        ]);
+    }
+
+    #[test]
+    fn dolt_concurrency_bug_version_range() {
+        assert!(!has_dolt_concurrency_bug(CliClient::Bd, (0, 54, 9)));
+        assert!(has_dolt_concurrency_bug(CliClient::Bd, (0, 55, 0)));
+        assert!(has_dolt_concurrency_bug(CliClient::Bd, (0, 56, 2)));
+        assert!(has_dolt_concurrency_bug(CliClient::Bd, (1, 0, 0)));
+        assert!(!has_dolt_concurrency_bug(CliClient::Br, (0, 55, 0)));
+        assert!(!has_dolt_concurrency_bug(CliClient::Unknown, (0, 55, 0)));
     }
 
-    let beads_dir = PathBuf::from(&path).join(".beads");
-    if !beads_dir.exists() {
-        return Err(format!(".beads directory not found at: {}", beads_dir.display()));
+    #[test]
+    fn relations_get_titles_from_the_project_list() {
+        let mut other = issue_created_at("p-2", "2025-01-01T00:00:00Z");
+        other.title = "Related work".to_string();
+        other.status = "in_progress".to_string();
+        let mut issue = issue_created_at("p-1", "2025-01-01T00:00:00Z");
+        let bare = |id: &str| Relation {
+            id: id.to_string(),
+            title: String::new(),
+            status: String::new(),
+            priority: String::new(),
+            relation_type: "related".to_string(),
+            direction: "dependency".to_string(),
+        };
+        issue.relations = Some(vec![bare("p-2"), bare("p-gone")]);
+
+        enrich_relations(&mut issue, &[other]);
+        let relations = issue.relations.unwrap();
+        assert_eq!(relations[0].title, "Related work");
+        assert_eq!(relations[0].status, "in_progress");
+        assert_eq!(relations[1].title, "");
     }
 
-    let project_path = path.clone();
-    let app_handle = app.clone();
+    #[test]
+    fn count_excludes_tombstones() {
+        let raw = |id: &str, status: &str, updated: &str| -> BdRawIssue {
+            raw_issue_with(id, serde_json::json!({ "status": status, "updated_at": updated }))
+        };
+        let issues = vec![
+            raw("p-1", "open", "2025-01-01T00:00:00Z"),
+            raw("p-2", "closed", "2025-01-02T00:00:00Z"),
+            raw("p-3", "tombstone", "2025-03-01T00:00:00Z"),
+        ];
+        let counts = count_raw_issues(&issues);
+        assert_eq!(counts.count, 2);
+        assert_eq!(counts.tombstone_count, 1);
+        assert_eq!(counts.by_type["task"], 2);
+        assert_eq!(counts.by_priority["p3"], 2);
+        assert_eq!(counts.last_updated.as_deref(), Some("2025-01-02T00:00:00Z"));
+    }
 
-    let mut debouncer = new_debouncer(
-        Duration::from_millis(1000),
-        move |res: Result<Vec<notify_debouncer_mini::DebouncedEvent>, notify::Error>| {
-            match res {
-                Ok(events) => {
-                    // Filter: only emit if we have actual data-change events
-                    let has_data_events = events.iter().any(|e| {
-                        matches!(e.kind, DebouncedEventKind::Any | DebouncedEventKind::AnyContinuous)
-                    });
-                    if has_data_events {
-                        log::info!("[watcher] Change detected in .beads/ ({} events)", events.len());
-                        let _ = app_handle.emit(
-                            "beads-changed",
-                            BeadsChangedPayload { path: project_path.clone() },
-                        );
-                    }
-                }
-                Err(e) => {
-                    log::error!("[watcher] Error: {:?}", e);
-                }
-            }
-        },
-    ).map_err(|e| format!("Failed to create watcher: {}", e))?;
+    #[test]
+    fn dashboard_matches_full_poll() {
+        let raw = |id: &str, status: &str, priority: u8| -> BdRawIssue {
+            raw_issue_with(id, serde_json::json!({ "status": status, "priority": priority }))
+        };
+        let all = || vec![
+            raw("p-1", "open", 1),
+            raw("p-2", "in_progress", 2),
+            raw("p-3", "closed", 3),
+            raw("p-4", "open", 1),
+        ];
+        let ready = || vec![raw("p-1", "open", 1), raw("p-4", "open", 1)];
 
-    // Watch .beads/ directory
-    // Dolt backend: recursive (changes happen in .dolt/ subdirectories)
-    // SQLite backend: non-recursive (all target files are at root level)
-    let watch_mode = if project_uses_dolt(&beads_dir) {
-        notify::RecursiveMode::Recursive
-    } else {
-        notify::RecursiveMode::NonRecursive
-    };
-    debouncer.watcher().watch(
-        beads_dir.as_path(),
-        watch_mode,
-    ).map_err(|e| format!("Failed to watch .beads/: {}", e))?;
+        let (open, closed): (Vec<_>, Vec<_>) = all().into_iter().partition(|i| i.status != "closed");
+        let poll = assemble_poll_data(Ok(open), Ok(closed), Ok(ready())).unwrap();
+        let dashboard = dashboard_data(&all(), ready());
 
-    log::info!("[watcher] Started watching: {}", beads_dir.display());
-    watcher_state.debouncer = Some(debouncer);
-    watcher_state.watched_path = Some(path);
+        let polled: Vec<&Issue> = poll.open_issues.iter().chain(&poll.closed_issues).collect();
+        assert_eq!(dashboard.counts.count, polled.len());
+        for priority in ["p1", "p2", "p3"] {
+            let expected = polled.iter().filter(|i| i.priority == priority).count();
+            assert_eq!(dashboard.counts.by_priority[priority], expected, "{}", priority);
+        }
+        let ids = |issues: &[Issue]| issues.iter().map(|i| i.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&dashboard.ready), ids(&poll.ready_issues));
+    }
 
-    Ok(())
-}
+    #[test]
+    fn count_groups_by_assignee_with_unassigned_under_empty_key() {
+        let raw = |id: &str, assignee: Option<&str>| -> BdRawIssue {
+            raw_issue_with(id, serde_json::json!({ "assignee": assignee }))
+        };
+        let issues = vec![
+            raw("p-1", Some("alice")),
+            raw("p-2", Some("alice")),
+            raw("p-3", Some("bob")),
+            raw("p-4", None),
+            raw("p-5", Some("")),
+        ];
+        let counts = count_raw_issues(&issues);
+        let expected: HashMap<String, usize> =
+            [("alice", 2), ("bob", 1), ("", 2)].iter().map(|(k, v)| (k.to_string(), *v)).collect();
+        assert_eq!(counts.by_assignee, expected);
+    }
 
-#[tauri::command]
-fn stop_watching(
-    state: tauri::State<'_, Mutex<WatcherState>>,
-) -> Result<(), String> {
-    let mut watcher_state = state.lock().map_err(|e| format!("Lock error: {}", e))?;
+    #[test]
+    fn excerpt_cuts_at_word_boundary() {
+        let text = "The quick brown fox jumps over the lazy dog";
+        assert_eq!(excerpt(text, 12), "The quick…");
+        assert_eq!(excerpt(text, 15), "The quick brown…");
+        assert_eq!(excerpt(text, 100), text);
+        assert_eq!(excerpt("Supercalifragilistic", 5), "Super…");
+        assert_eq!(excerpt("héllo wörld ünïcode", 11), "héllo wörld…");
 
-    if watcher_state.debouncer.is_some() {
-        log::info!("[watcher] Stopped watching: {:?}", watcher_state.watched_path);
-        watcher_state.debouncer = None;
-        watcher_state.watched_path = None;
+        let mut issue = issue_created_at("p-1", "2025-01-01T00:00:00Z");
+        issue.description = text.to_string();
+        let options = ListOptions { description_excerpt: Some(12), ..Default::default() };
+        let listed = apply_list_post_filters(vec![issue], &options).unwrap();
+        assert_eq!(listed[0].description, "The quick…");
     }
 
-    Ok(())
-}
+    #[test]
+    fn prune_backups_keeps_newest_n() {
+        let dir = test_dir("backup-prune");
+        fs::create_dir_all(&dir).unwrap();
+        for stamp in ["20250101-090000", "20250102-090000", "20250103-090000", "20250104-090000"] {
+            fs::write(dir.join(format!("app-{}.jsonl", stamp)), "{}").unwrap();
+        }
+        fs::write(dir.join("other-20250101-090000.jsonl"), "{}").unwrap();
+        // Another project whose name starts with "app-" must be left alone
+        fs::write(dir.join("app-v2-20240101-090000.jsonl"), "{}").unwrap();
+        fs::write(dir.join("app-notes.jsonl"), "{}").unwrap();
+
+        let removed = prune_backups(&dir, "app-", 2);
+        let names = |paths: Vec<PathBuf>| -> Vec<String> {
+            let mut names: Vec<String> = paths.iter().map(|p| p.file_name().unwrap().to_string_lossy().to_string()).collect();
+            names.sort();
+            names
+        };
+        assert_eq!(names(removed), vec!["app-20250101-090000.jsonl", "app-20250102-090000.jsonl"]);
+        let left = names(fs::read_dir(&dir).unwrap().flatten().map(|e| e.path()).collect());
+        assert_eq!(left, vec![
+            "app-20250103-090000.jsonl",
+            "app-20250104-090000.jsonl",
+            "app-notes.jsonl",
+            "app-v2-20240101-090000.jsonl",
+            "other-20250101-090000.jsonl",
+        ]);
+        assert_eq!(prune_backups(&dir, "app-v2-", 0).len(), 1);
+        assert_eq!(backup_prefix("/nonexistent/a/my-app"), format!("my-app-{:08x}-", fnv1a_32(b"/nonexistent/a/my-app")));
+        assert_ne!(backup_prefix("/nonexistent/a/my-app"), backup_prefix("/nonexistent/b/my-app"));
+        assert_eq!(fnv1a_32(b"a"), 0xe40c_292c);
+        let _ = fs::remove_dir_all(&dir);
+    }
 
-#[derive(Debug, Serialize)]
-struct WatcherStatusInfo {
-    active: bool,
-    #[serde(rename = "watchedPath")]
-    watched_path: Option<String>,
-}
+    #[test]
+    fn child_count_for_epic_with_two_children() {
+        let child = |id: &str| serde_json::json!({
+            "id": id, "title": "Child", "status": "open", "priority": 2, "dependency_type": "parent-child",
+        });
+        let mut epic = transform_issue(raw_issue_with("epic", serde_json::json!({
+            "dependents": [child("epic.1"), child("epic.2")],
+        })));
+        let count = epic.children.as_ref().map_or(0, Vec::len);
+        set_child_count(&mut epic, count);
+        assert_eq!((epic.child_count, epic.has_children), (Some(2), Some(true)));
 
-#[tauri::command]
-fn get_watcher_status(
-    state: tauri::State<'_, Mutex<WatcherState>>,
-) -> Result<WatcherStatusInfo, String> {
-    let watcher_state = state.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let mut leaf = transform_issue(serde_json::from_str(&minimal_issue_json("leaf", "Leaf")).unwrap());
+        let count = leaf.children.as_ref().map_or(0, Vec::len);
+        set_child_count(&mut leaf, count);
+        assert_eq!((leaf.child_count, leaf.has_children), (Some(0), Some(false)));
+    }
 
-    Ok(WatcherStatusInfo {
-        active: watcher_state.debouncer.is_some(),
-        watched_path: watcher_state.watched_path.clone(),
-    })
-}
+    #[test]
+    fn export_count_check_ignores_tombstones_and_blank_lines() {
+        let tombstone = issue_json_with("a-3", "Gone", serde_json::json!({ "status": "tombstone" }));
+        let source = format!("{}\n\n{}\n{}\nnot json\n", minimal_issue_json("a-1", "One"), minimal_issue_json("a-2", "Two"), tombstone);
+        assert_eq!(count_jsonl_issues(&source), 2);
+
+        let exported = format!("{}\n{}\n", minimal_issue_json("a-1", "One"), minimal_issue_json("a-2", "Two"));
+        let check = verify_export_count(Some(count_jsonl_issues(&source)), count_jsonl_issues(&exported));
+        assert_eq!(check, JsonlExportCheck { exported: 2, expected: Some(2), matches: true, replaced: false });
+        assert!(!verify_export_count(Some(3), 2).matches);
+        // No issues.jsonl before: nothing to compare against, not a mismatch
+        assert!(verify_export_count(None, 2).matches);
+
+        let dir = test_dir("export-verify");
+        fs::create_dir_all(&dir).unwrap();
+        let jsonl = dir.join("issues.jsonl");
+        fs::write(&jsonl, &source).unwrap();
+        let short = format!("{}\n", minimal_issue_json("a-1", "One"));
+        let check = replace_jsonl_if_verified(&jsonl, Some(2), &short).unwrap();
+        assert!(!check.matches && !check.replaced);
+        assert_eq!(fs::read_to_string(&jsonl).unwrap(), source);
+        assert!(!dir.join("issues.jsonl.tmp").exists());
+
+        assert!(replace_jsonl_if_verified(&jsonl, Some(2), &exported).unwrap().replaced);
+        assert_eq!(fs::read_to_string(&jsonl).unwrap(), exported);
+        let _ = fs::remove_dir_all(&dir);
+    }
 
-// ============================================================================
-// External Data Source Commands
-// ============================================================================
+    #[test]
+    fn comment_with_attachment_references_stored_file() {
+        let dir = test_dir("comment-attach");
+        let dest = dir.join("attachments");
+        fs::create_dir_all(&dest).unwrap();
+        fs::write(dest.join("shot.png"), b"older screenshot").unwrap();
+        let source = dir.join("shot.png");
+        fs::write(&source, b"new screenshot").unwrap();
+
+        let copied = copy_into_attachment_dir(&source, &dest).unwrap();
+        assert_eq!(copied.filename, "shot-1.png");
+        let body = comment_with_image("See the crash  \n", &copied.filename);
+        assert_eq!(body, "See the crash\n\n![shot-1.png](shot-1.png)");
+        assert_eq!(comment_with_image("", "a.png"), "![a.png](a.png)");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn output_with_crash_retry_reruns_crashed_reads() {
+        let dir = test_dir("crash-retry");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let script = dir.join("flaky-bd.sh");
+        // Crashes on the first two runs, succeeds on the third
+        fs::write(
+            &script,
+            "#!/bin/sh\necho run >> \"$(dirname \"$0\")/runs\"\n\
+             if [ $(wc -l < \"$(dirname \"$0\")/runs\") -lt 3 ]; then\n\
+             echo 'panic: runtime error: invalid memory address or nil pointer dereference [signal SIGSEGV]' >&2\nexit 2\nfi\n\
+             echo '[{\"id\":\"t-1\"}]'\n",
+        )
+        .unwrap();
 
-#[tauri::command]
-async fn fetch_external_data(url: String) -> Result<String, String> {
-    log_info!("[probe] GET {}", url);
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
-        .build()
-        .map_err(|e| format!("HTTP client error: {}", e))?;
+        let mut cmd = Command::new("sh");
+        cmd.arg(&script);
+        let output = output_with_crash_retry("list", &mut cmd, None).unwrap();
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "[{\"id\":\"t-1\"}]\n");
+        assert_eq!(fs::read_to_string(dir.join("runs")).unwrap().lines().count(), 3);
 
-    let response = client
-        .get(&url)
-        .header("Accept", "application/json")
-        .send()
-        .await
-        .map_err(|e| format!("Request failed: {}", e))?;
+        // Writes are never retried
+        fs::remove_file(dir.join("runs")).unwrap();
+        let output = output_with_crash_retry("create", &mut cmd, None).unwrap();
+        assert!(!output.status.success());
+        assert_eq!(fs::read_to_string(dir.join("runs")).unwrap().lines().count(), 1);
+        let _ = fs::remove_dir_all(&dir);
+    }
 
-    if !response.status().is_success() {
-        let err = format!("HTTP {}: {}", response.status().as_u16(), response.status().canonical_reason().unwrap_or("Unknown"));
-        log_error!("[probe] GET failed: {}", err);
-        return Err(err);
+    #[cfg(unix)]
+    #[test]
+    fn output_with_timeout_kills_slow_binary() {
+        let dir = test_dir("timeout");
+        fs::create_dir_all(&dir).unwrap();
+        let script = dir.join("slow-bd.sh");
+        fs::write(&script, "#!/bin/sh\necho started\nsleep 10\n").unwrap();
+
+        let started = Instant::now();
+        let mut cmd = Command::new("sh");
+        cmd.arg(&script);
+        let err = output_with_timeout(&mut cmd, Some(Duration::from_secs(1))).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+        assert_eq!(err.to_string(), "bd command timed out after 1s");
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "echo done"]);
+        let output = output_with_timeout(&mut cmd, Some(Duration::from_secs(5))).unwrap();
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "done\n");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn find_in_path_returns_first_executable_match() {
+        use std::os::unix::fs::PermissionsExt;
+        let root = test_dir("which");
+        let (first, second) = (root.join("first"), root.join("second"));
+        fs::create_dir_all(&first).unwrap();
+        fs::create_dir_all(&second).unwrap();
+        let make = |dir: &std::path::Path, name: &str, mode: u32| {
+            let path = dir.join(name);
+            fs::write(&path, "#!/bin/sh\n").unwrap();
+            fs::set_permissions(&path, fs::Permissions::from_mode(mode)).unwrap();
+        };
+        make(&first, "br", 0o644); // not executable: skipped
+        make(&second, "br", 0o755);
+        make(&first, "bd", 0o755);
+        make(&second, "bd", 0o755);
+
+        let path_var = env::join_paths([&first, &second]).unwrap().to_string_lossy().to_string();
+        let canonical = |p: PathBuf| p.canonicalize().unwrap();
+        assert_eq!(find_in_path("bd", &path_var), Some(canonical(first.join("bd"))));
+        assert_eq!(find_in_path("br", &path_var), Some(canonical(second.join("br"))));
+        assert_eq!(find_in_path("nope", &path_var), None);
+        let explicit = second.join("bd").to_string_lossy().to_string();
+        assert_eq!(find_in_path(&explicit, ""), Some(canonical(second.join("bd"))));
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn detect_clis_lists_each_working_binary_once() {
+        use std::os::unix::fs::PermissionsExt;
+        let root = test_dir("detect-clis");
+        let (brew, cargo) = (root.join("brew"), root.join("cargo"));
+        fs::create_dir_all(&brew).unwrap();
+        fs::create_dir_all(&cargo).unwrap();
+        let make = |dir: &std::path::Path, name: &str, script: &str| {
+            let path = dir.join(name);
+            fs::write(&path, format!("#!/bin/sh\n{}\n", script)).unwrap();
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        };
+        make(&brew, "bd", "echo 'bd version 0.49.6 (Homebrew)'");
+        make(&cargo, "br", "echo 'br 0.1.13 (rustc 1.85.0-nightly)'");
+        make(&cargo, "bd", "exit 1"); // broken: skipped
+
+        // The same directory twice must not list its binaries twice
+        let path_var = env::join_paths([&brew, &cargo, &brew]).unwrap().to_string_lossy().to_string();
+        let found = detect_clis_in(&path_var);
+        let summary: Vec<(&str, &str, &str)> = found.iter()
+            .map(|c| (c.name.as_str(), c.client_type.as_str(), c.version.as_str()))
+            .collect();
+        assert_eq!(summary, vec![
+            ("bd", "bd", "bd version 0.49.6 (Homebrew)"),
+            ("br", "br", "br 0.1.13 (rustc 1.85.0-nightly)"),
+        ]);
+        assert_eq!(PathBuf::from(&found[0].path), brew.join("bd").canonicalize().unwrap());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn diagnostic_checks_report_environment() {
+        use std::os::unix::fs::PermissionsExt;
+        let root = test_dir("diagnostics");
+        let bin = root.join("bin");
+        let project = root.join("project");
+        fs::create_dir_all(&bin).unwrap();
+        fs::create_dir_all(&project).unwrap();
+        let make = |name: &str, script: &str| {
+            let path = bin.join(name);
+            fs::write(&path, format!("#!/bin/sh\n{}\n", script)).unwrap();
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+            path
+        };
+        let bd = make("bd", "echo 'bd version 0.49.6 (Homebrew)'");
+        let odd = make("odd", "echo 'something 1.0'");
+        let broken = make("broken", "echo boom >&2; exit 1");
+        let path_var = bin.to_string_lossy().to_string();
+
+        assert_eq!(check_cli_binary("bd", &path_var).status, CheckStatus::Pass);
+        assert_eq!(check_cli_binary("br", &path_var).status, CheckStatus::Fail);
+        let version = check_cli_version(&bd);
+        assert_eq!((version.status, version.message.as_str()), (CheckStatus::Pass, "bd version 0.49.6 (Homebrew) (bd)"));
+        assert_eq!(check_cli_version(&odd).status, CheckStatus::Warn);
+        assert_eq!(check_cli_version(&broken).status, CheckStatus::Fail);
+        assert_eq!(check_sqlite3(&path_var).status, CheckStatus::Warn);
+        make("sqlite3", "exit 0");
+        assert_eq!(check_sqlite3(&path_var).status, CheckStatus::Pass);
+
+        assert_eq!(check_beads_dir(&project).status, CheckStatus::Fail);
+        assert_eq!(check_beads_dir(&root.join("missing")).status, CheckStatus::Fail);
+        let beads_dir = project.join(".beads");
+        fs::create_dir_all(beads_dir.join(".dolt")).unwrap();
+        assert_eq!(check_beads_dir(&project).status, CheckStatus::Pass);
+        let old_bd = Some((CliClient::Bd, 0, 49, 6));
+        let dolt_bd = Some((CliClient::Bd, 0, 52, 0));
+        assert_eq!(check_dolt(old_bd, &beads_dir).status, CheckStatus::Fail);
+        assert_eq!(check_dolt(dolt_bd, &beads_dir).status, CheckStatus::Pass);
+        assert_eq!(check_backend(dolt_bd, &beads_dir).message, "dolt");
+        assert_eq!(check_backend(None, &root).status, CheckStatus::Warn);
+
+        let config_dir = root.join("config");
+        assert_eq!(check_config_dir_writable(&config_dir).status, CheckStatus::Pass);
+        assert_eq!(fs::read_dir(&config_dir).unwrap().count(), 0);
+        let _ = fs::remove_dir_all(&root);
     }
 
-    response.text().await.map_err(|e| format!("Failed to read response: {}", e))
-}
+    #[test]
+    fn list_fallback_export_round_trips_as_jsonl() {
+        let json = format!("[{},{}]", minimal_issue_json("a-1", "One"), minimal_issue_json("a-2", "Two"));
+        let issues = parse_issues_tolerant(&json, "test_export").unwrap();
+        let jsonl = raw_issues_to_jsonl(&issues).unwrap();
+        assert_eq!(jsonl.lines().count(), 2);
+        assert!(jsonl.ends_with('\n'));
 
-#[tauri::command]
-async fn check_external_health(url: String) -> Result<bool, String> {
-    let health_url = format!("{}/health", url.trim_end_matches('/'));
-    log_info!("[probe] Health check: {}", health_url);
+        let reparsed: Vec<BdRawIssue> = jsonl.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(reparsed.iter().map(|i| i.id.as_str()).collect::<Vec<_>>(), vec!["a-1", "a-2"]);
+        assert_eq!(count_jsonl_issues(&jsonl), 2);
+    }
 
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(5))
-        .build()
-        .map_err(|e| format!("HTTP client error: {}", e))?;
+    #[test]
+    fn needs_attention_flags_stale_unassigned_ready_issues() {
+        let idle_since = |id: &str, updated_at: &str| {
+            let mut issue = issue_created_at(id, "2025-01-01T00:00:00Z");
+            issue.updated_at = updated_at.to_string();
+            issue
+        };
+        let now = parse_timestamp_utc("2025-03-01T00:00:00Z").unwrap().0;
+        let mut assigned = idle_since("assigned", "2025-01-01T00:00:00Z");
+        assigned.assignee = Some("alice".to_string());
+        let mut blocked = idle_since("blocked", "2025-01-01T00:00:00Z");
+        blocked.blocked_by = Some(vec!["blocker".to_string()]);
+        let mut unblocked = idle_since("unblocked", "2025-01-01T00:00:00Z");
+        unblocked.blocked_by = Some(vec!["done".to_string()]);
+        let mut done = idle_since("done", "2025-01-01T00:00:00Z");
+        done.status = "closed".to_string();
+        let issues = vec![
+            idle_since("stale", "2025-02-01T00:00:00Z"),
+            idle_since("fresh", "2025-02-26T00:00:00Z"),
+            assigned,
+            blocked,
+            unblocked,
+            done,
+            idle_since("blocker", "2025-02-28T00:00:00Z"),
+        ];
 
-    match client.get(&health_url).send().await {
-        Ok(response) => Ok(response.status().is_success()),
-        Err(_) => Ok(false),
+        let flagged: Vec<(String, Vec<String>)> = needs_attention(issues, &AttentionThresholds::default(), now)
+            .into_iter().map(|a| (a.issue.id, a.reasons)).collect();
+        assert_eq!(flagged, vec![
+            ("stale".to_string(), vec!["Ready and unassigned for 28 days".to_string()]),
+            ("unblocked".to_string(), vec!["Ready and unassigned for 59 days".to_string()]),
+        ]);
     }
-}
 
-#[tauri::command]
-async fn post_external_data(url: String, body: String) -> Result<String, String> {
-    log_info!("[probe] POST {}", url);
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
-        .build()
-        .map_err(|e| format!("HTTP client error: {}", e))?;
+    #[test]
+    fn assignee_filter_accepts_several_people() {
+        let assigned = |id: &str, who: &str| {
+            let mut issue = issue_created_at(id, "2025-01-01T00:00:00Z");
+            issue.assignee = Some(who.to_string());
+            issue
+        };
+        let issues = || vec![assigned("p-1", "alice"), assigned("p-2", "bob"), assigned("p-3", "carol")];
+        let ids = |options: &ListOptions| -> Vec<String> {
+            apply_list_post_filters(issues(), options).unwrap().into_iter().map(|i| i.id).collect()
+        };
 
-    let response = client
-        .post(&url)
-        .header("Content-Type", "application/json")
-        .header("Accept", "application/json")
-        .body(body)
-        .send()
-        .await
-        .map_err(|e| format!("Request failed: {}", e))?;
+        let options: ListOptions = serde_json::from_str(r#"{"assignee":["alice","carol"]}"#).unwrap();
+        assert_eq!(ids(&options), vec!["p-1", "p-3"]);
+        let options: ListOptions = serde_json::from_str(r#"{"assignee":[]}"#).unwrap();
+        assert_eq!(ids(&options).len(), 3);
 
-    if !response.status().is_success() {
-        let status = response.status();
-        let text = response.text().await.unwrap_or_default();
-        return Err(format!("HTTP {}: {}", status.as_u16(), text));
+        // A bare string still deserializes; single values are left to the CLI flag
+        let options: ListOptions = serde_json::from_str(r#"{"assignee":"bob"}"#).unwrap();
+        assert_eq!(options.assignee, Some(vec!["bob".to_string()]));
+        assert_eq!(requested_assignees(&options), vec!["bob"]);
+        let options: ListOptions = serde_json::from_str(r#"{"assignee":null}"#).unwrap();
+        assert_eq!(options.assignee, None);
     }
 
-    response.text().await.map_err(|e| format!("Failed to read response: {}", e))
-}
-
-#[tauri::command]
-async fn delete_external_data(url: String) -> Result<String, String> {
-    log_info!("[probe] DELETE {}", url);
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
-        .build()
-        .map_err(|e| format!("HTTP client error: {}", e))?;
+    #[test]
+    fn delete_guard_rejects_open_children_unless_cascade() {
+        let child = |id: &str, status: &str| ChildIssue {
+            id: id.to_string(),
+            title: String::new(),
+            status: status.to_string(),
+            priority: "p2".to_string(),
+            children: None,
+        };
+        let tree: HashMap<&str, Vec<ChildIssue>> = HashMap::from([
+            ("epic", vec![child("epic.1", "open"), child("epic.2", "closed")]),
+            ("epic.1", vec![child("epic.1.1", "in_progress")]),
+        ]);
+        let children_of = |id: &str| Ok(tree.get(id).cloned().unwrap_or_default());
+
+        let mut deleted: Vec<String> = Vec::new();
+        let err = delete_with_children("epic", false, false, children_of, |id| {
+            deleted.push(id.to_string());
+            Ok(())
+        }).unwrap_err();
+        assert!(err.starts_with("HAS_OPEN_CHILDREN:epic.1: epic has open children (epic.1)"), "{}", err);
+        assert!(deleted.is_empty());
 
-    let response = client
-        .delete(&url)
-        .header("Accept", "application/json")
-        .send()
-        .await
-        .map_err(|e| format!("Request failed: {}", e))?;
+        let order = delete_with_children("epic", false, true, children_of, |id| {
+            deleted.push(id.to_string());
+            Ok(())
+        }).unwrap();
+        assert_eq!(order, vec!["epic.1.1", "epic.1", "epic.2", "epic"]);
+        assert_eq!(deleted, order);
 
-    if !response.status().is_success() {
-        let status = response.status();
-        let text = response.text().await.unwrap_or_default();
-        return Err(format!("HTTP {}: {}", status.as_u16(), text));
+        let order = delete_with_children("epic", true, false, children_of, |_| Ok(())).unwrap();
+        assert_eq!(order, vec!["epic"]);
+        // Only closed children: no flag needed
+        assert_eq!(delete_with_children("epic.2", false, false, children_of, |_| Ok(())).unwrap(), vec!["epic.2"]);
     }
 
-    response.text().await.map_err(|e| format!("Failed to read response: {}", e))
-}
+    #[test]
+    fn children_come_from_parent_field_or_dotted_id() {
+        let issue = |id: &str, parent: Option<&str>| {
+            let mut issue = issue_created_at(id, "2025-01-01T00:00:00Z");
+            issue.parent = parent.map(|p| ParentIssue {
+                id: p.to_string(),
+                title: String::new(),
+                status: "open".to_string(),
+                priority: "p2".to_string(),
+            });
+            issue
+        };
+        let all = vec![
+            issue("epic", None),
+            issue("epic.1", None),
+            issue("epic.10", None),
+            issue("epic.1.1", None),
+            issue("epicx.1", None),
+            issue("p-2", Some("epic")),
+            issue("epic.3", Some("other")),
+        ];
+        let ids: Vec<String> = children_in_list("epic", &all).into_iter().map(|c| c.id).collect();
+        assert_eq!(ids, vec!["epic.1", "epic.10", "p-2"]);
+        let ids: Vec<String> = children_in_list("epic.1", &all).into_iter().map(|c| c.id).collect();
+        assert_eq!(ids, vec!["epic.1.1"]);
+    }
 
-#[tauri::command]
-async fn patch_external_data(url: String, body: String) -> Result<String, String> {
-    log_info!("[probe] PATCH {}", url);
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
-        .build()
-        .map_err(|e| format!("HTTP client error: {}", e))?;
+    #[test]
+    fn bulk_update_closes_all_and_can_stop_on_first_error() {
+        let ids: Vec<String> = ["b-1", "b-2", "b-3"].iter().map(|s| s.to_string()).collect();
+        let mut statuses: HashMap<String, String> = ids.iter().map(|id| (id.clone(), "open".to_string())).collect();
+        let results = bulk_update_with(&ids, true, |id| {
+            statuses.insert(id.to_string(), "closed".to_string());
+            Ok(())
+        });
+        assert!(results.iter().all(|r| r.success));
+        assert!(statuses.values().all(|s| s == "closed"));
 
-    let response = client
-        .patch(&url)
-        .header("Content-Type", "application/json")
-        .header("Accept", "application/json")
-        .body(body)
-        .send()
-        .await
-        .map_err(|e| format!("Request failed: {}", e))?;
+        let fail_b2 = |id: &str| if id == "b-2" { Err("locked".to_string()) } else { Ok(()) };
+        assert_eq!(bulk_update_with(&ids, true, fail_b2).len(), 3);
+        let stopped = bulk_update_with(&ids, false, fail_b2);
+        assert_eq!(stopped.len(), 2);
+        assert_eq!(stopped[1], BulkItemResult { id: "b-2".into(), success: false, error: Some("locked".into()) });
+    }
 
-    if !response.status().is_success() {
-        let status = response.status();
-        let text = response.text().await.unwrap_or_default();
-        return Err(format!("HTTP {}: {}", status.as_u16(), text));
+    #[test]
+    fn project_lock_is_reentrant_within_a_batch() {
+        let cwd = test_dir("batch-lock");
+        let dir = cwd.to_string_lossy().to_string();
+        let held = with_project_lock(Some(&dir), || {
+            HELD_PROJECT_LOCK.with(|h| h.borrow().clone())
+        });
+        assert_eq!(held.as_deref(), Some(resolve_working_dir(Some(&dir)).as_str()));
+        assert_eq!(HELD_PROJECT_LOCK.with(|h| h.borrow().clone()), None);
+        // Released: taking it again doesn't block
+        assert!(project_lock(&resolve_working_dir(Some(&dir))).try_lock().is_ok());
     }
 
-    response.text().await.map_err(|e| format!("Failed to read response: {}", e))
-}
+    #[test]
+    fn recent_searches_dedupe_and_cap() {
+        let mut recent = Vec::new();
+        for query in ["login", "crash", "login"] {
+            push_recent_search(&mut recent, query, 3);
+        }
+        assert_eq!(recent, vec!["login", "crash"]);
 
-// ============================================================================
-// Probe Launcher
-// ============================================================================
+        push_recent_search(&mut recent, "timeout", 3);
+        push_recent_search(&mut recent, "dolt", 3);
+        assert_eq!(recent, vec!["dolt", "timeout", "login"]);
+        // Re-running an older query moves it to the front without growing the list
+        push_recent_search(&mut recent, "login", 3);
+        assert_eq!(recent, vec!["login", "dolt", "timeout"]);
+    }
 
-#[tauri::command]
-async fn launch_probe(port: u16) -> Result<String, String> {
-    use std::process::Stdio;
+    #[test]
+    fn delta_poll_keeps_only_changed_issues() {
+        let updated = |id: &str, status: &str, updated_at: &str| -> BdRawIssue {
+            raw_issue_with(id, serde_json::json!({ "status": status, "updated_at": updated_at }))
+        };
+        let raw = vec![
+            updated("old", "open", "2025-03-01T09:00:00Z"),
+            updated("same-second", "open", "2025-03-01T10:00:00Z"),
+            updated("new", "open", "2025-03-01T10:00:01Z"),
+            updated("gone", "tombstone", "2025-03-02T00:00:00Z"),
+            updated("gone-long-ago", "tombstone", "2025-02-01T00:00:00Z"),
+        ];
+        assert_eq!(live_issue_ids(raw.iter()), vec!["old", "same-second", "new"]);
 
-    let health_url = format!("http://127.0.0.1:{}/health", port);
+        let since = parse_timestamp_utc("2025-03-01T10:00:00Z").unwrap();
+        let mut deleted = Vec::new();
+        let changed: Vec<String> = changed_since(raw, since, &mut deleted).into_iter().map(|i| i.id).collect();
+        assert_eq!(changed, vec!["same-second", "new"]);
+        assert_eq!(deleted, vec!["gone"]);
+    }
 
-    // Check if probe is already reachable via HTTP health endpoint
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(2))
-        .build()
-        .map_err(|e| format!("HTTP client error: {}", e))?;
+    #[test]
+    fn failed_bucket_holds_the_poll_watermark() {
+        let seen = Some("2025-03-02T00:00:00Z".to_string());
+        let since = Some("2025-03-01T00:00:00Z".to_string());
+        assert_eq!(poll_watermark(seen.clone(), since.clone(), false), seen);
+        assert_eq!(poll_watermark(seen.clone(), since.clone(), true), since);
+        assert_eq!(poll_watermark(seen, None, true), None);
+    }
 
-    if let Ok(resp) = client.get(&health_url).send().await {
-        if resp.status().is_success() {
-            log_info!("[probe] Already running on port {}", port);
-            return Ok("already running".to_string());
+    #[test]
+    fn reopened_issue_loses_its_close_date() {
+        let with_status = |status: &str| {
+            transform_issue(raw_issue_with("r-1", serde_json::json!({
+                "status": status, "closed_at": "2025-02-01T00:00:00Z",
+            })))
+        };
+        let closed = with_status("closed");
+        assert_eq!(closed.closed_at.as_deref(), Some("2025-02-01T00:00:00Z"));
+
+        // What an older CLI returns after `update --status open`
+        for status in ["open", "in_progress", "deferred"] {
+            let reopened = with_status(status);
+            assert_eq!(reopened.status, status);
+            assert_eq!(reopened.closed_at, None);
         }
     }
 
-    // Determine binary: BEADS_PROBE_BIN env var, fallback to "beads-probe"
-    let bin = env::var("BEADS_PROBE_BIN").unwrap_or_else(|_| "beads-probe".to_string());
-    log_info!("[probe] Launching: {} --port {}", bin, port);
+    #[test]
+    fn unknown_subcommands_are_recognized_for_both_clis() {
+        assert!(is_unknown_command("Error: unknown command \"reopen\" for \"bd\""));
+        assert!(is_unknown_command("error: unrecognized subcommand 'reopen'"));
+        assert!(!is_unknown_command("Error: issue r-1 not found"));
+    }
+
+    #[test]
+    fn tolerant_parse_reports_skipped_entries() {
+        let missing_title = minimal_issue_json("bad-1", "x").replace("\"title\":\"x\",", "");
+        let json = format!("[{},{}]", minimal_issue_json("ok-1", "Fine"), missing_title);
+        let parsed = parse_issues_report(&json, "test_skips").unwrap();
+        assert_eq!(parsed.issues.len(), 1);
+        assert_eq!(parsed.issues[0].id, "ok-1");
+        assert_eq!(parsed.skipped.len(), 1);
+        assert_eq!(parsed.skipped[0].index, 1);
+        assert_eq!(parsed.skipped[0].id.as_deref(), Some("bad-1"));
+        assert!(parsed.skipped[0].reason.contains("title"), "{}", parsed.skipped[0].reason);
+
+        let twice = dedupe_skipped([parsed.skipped.clone(), parsed.skipped.clone()].concat());
+        assert_eq!(twice.len(), 1);
+
+        // bd_list keeps its bare array unless parse warnings were asked for
+        let plain = serde_json::to_value(ListResponse::Issues(Vec::new())).unwrap();
+        assert_eq!(plain, serde_json::json!([]));
+        let report = serde_json::to_value(ListResponse::WithWarnings { issues: Vec::new(), parse_warnings: parsed.skipped }).unwrap();
+        assert_eq!(report["parseWarnings"][0]["id"], "bad-1");
+        assert_eq!(report["issues"], serde_json::json!([]));
+    }
 
-    let child = Command::new(&bin)
-        .arg("--port")
-        .arg(port.to_string())
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .spawn()
-        .map_err(|e| format!("Failed to spawn {}: {}", bin, e))?;
+    #[test]
+    fn attachment_folder_rename_skips_existing_target() {
+        let dir = test_dir("rename-attachments");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("app-abc")).unwrap();
+        std::fs::write(dir.join("app-abc").join("shot.png"), "a").unwrap();
 
-    // Store child handle so it lives as long as the app
-    if let Ok(mut guard) = PROBE_CHILD.lock() {
-        *guard = Some(child);
+        assert!(!rename_attachment_folder(&dir, "abc", "abc").unwrap());
+        assert!(rename_attachment_folder(&dir, "app-abc", "abc").unwrap());
+        assert!(dir.join("abc").join("shot.png").exists());
+
+        // Target exists: nothing is moved or merged
+        std::fs::create_dir_all(dir.join("old-abc")).unwrap();
+        std::fs::write(dir.join("old-abc").join("shot.png"), "b").unwrap();
+        assert!(rename_attachment_folder(&dir, "old-abc", "abc").is_err());
+        assert_eq!(std::fs::read_to_string(dir.join("abc").join("shot.png")).unwrap(), "a");
+        assert_eq!(std::fs::read_to_string(dir.join("old-abc").join("shot.png")).unwrap(), "b");
+        assert_eq!(std::fs::read_dir(dir.join("abc")).unwrap().count(), 1);
+
+        let _ = std::fs::remove_dir_all(&dir);
     }
 
-    log_info!("[probe] Launched on port {}", port);
-    Ok("launched".to_string())
-}
+    #[test]
+    fn latest_comment_picks_newest_not_last_listed() {
+        let comment = |author: &str, content: &str, created_at: &str| Comment {
+            id: "1".to_string(),
+            author: author.to_string(),
+            content: content.to_string(),
+            created_at: created_at.to_string(),
+        };
+        let comments = vec![
+            comment("ana", "old news", "2025-03-01T10:00:00Z"),
+            comment("bo", "  Fixed in the latest build  ", "2025-03-02 09:00:00"),
+            comment("cy", "older still", "2025-02-27T10:00:00+02:00"),
+        ];
+        let latest = latest_comment(&comments).unwrap();
+        assert_eq!(latest.author, "bo");
+        assert_eq!(latest.excerpt, "Fixed in the latest build");
+        assert_eq!(latest.created_at, "2025-03-02 09:00:00");
 
-// ============================================================================
-// App Entry Point
-// ============================================================================
+        let long = vec![comment("ana", &"word ".repeat(60), "2025-03-01T10:00:00Z")];
+        assert!(latest_comment(&long).unwrap().excerpt.ends_with('…'));
+        assert_eq!(latest_comment(&[]), None);
+    }
 
-#[cfg_attr(mobile, tauri::mobile_entry_point)]
-pub fn run() {
-    // Load .env file (dev only — in prod there's no .env, env vars come from the system)
-    let _ = dotenvy::dotenv();
+    #[test]
+    fn latest_comment_enrichment_is_capped() {
+        let mut issues: Vec<Issue> = ["p-1", "p-2", "p-3", "p-4"].iter()
+            .map(|id| {
+                let mut issue = issue_created_at(id, "2025-01-01T00:00:00Z");
+                issue.comment_count = Some(1);
+                issue
+            })
+            .collect();
+        issues[3].comment_count = Some(0);
+        let calls = std::cell::Cell::new(0);
+        attach_latest_comments(&mut issues, 2, |id| {
+            calls.set(calls.get() + 1);
+            let mut full = issue_created_at(id, "2025-01-01T00:00:00Z");
+            full.comments = vec![Comment {
+                id: "1".to_string(),
+                author: "ana".to_string(),
+                content: format!("on {}", id),
+                created_at: "2025-03-01T10:00:00Z".to_string(),
+            }];
+            Ok(Some(full))
+        });
+        assert_eq!(calls.get(), 2);
+        let excerpts: Vec<Option<String>> = issues.iter().map(|i| i.latest_comment.as_ref().map(|c| c.excerpt.clone())).collect();
+        assert_eq!(excerpts, vec![Some("on p-1".to_string()), Some("on p-2".to_string()), None, None]);
+    }
 
-    tauri::Builder::default()
-        .manage(Mutex::new(WatcherState::default()))
-        .plugin(tauri_plugin_shell::init())
-        .plugin(tauri_plugin_dialog::init())
-        .setup(|app| {
-            // Enable logging in both debug and release builds
-            let log_level = if cfg!(debug_assertions) {
-                log::LevelFilter::Debug
-            } else {
-                log::LevelFilter::Info
-            };
-            app.handle().plugin(
-                tauri_plugin_log::Builder::default()
-                    .level(log_level)
-                    .max_file_size(5_000_000) // 5 MB max per log file
-                    .rotation_strategy(tauri_plugin_log::RotationStrategy::KeepOne) // Keep only one backup
-                    .target(tauri_plugin_log::Target::new(
-                        tauri_plugin_log::TargetKind::LogDir { file_name: Some("beads.log".into()) },
-                    ))
-                    .target(tauri_plugin_log::Target::new(
-                        tauri_plugin_log::TargetKind::Stdout,
-                    ))
-                    .build(),
-            )?;
+    #[test]
+    fn set_labels_args_replace_the_whole_set() {
+        let labels = vec![" UI ".to_string(), "ui".to_string(), "Backend".to_string(), "".to_string()];
+        assert_eq!(set_labels_args(&labels, true), vec!["--set-labels", "ui,backend"]);
+        assert_eq!(set_labels_args(&labels, false), vec!["--set-labels", "UI,ui,Backend"]);
+        // An empty set still sends the flag, clearing every existing label
+        assert_eq!(set_labels_args(&[], false), vec!["--set-labels", ""]);
+    }
 
-            // Log startup info
-            log::info!("=== Beads Task-Issue Tracker starting ===");
-            log::info!("[startup] Extended PATH: {}", get_extended_path());
+    #[test]
+    fn comment_delete_requires_recent_bd() {
+        assert!(supports_comment_delete(Some((CliClient::Bd, 0, 56, 0))));
+        assert!(supports_comment_delete(Some((CliClient::Bd, 1, 0, 0))));
+        assert!(!supports_comment_delete(Some((CliClient::Bd, 0, 55, 3))));
+        assert!(!supports_comment_delete(Some((CliClient::Br, 0, 1, 30))));
+        assert!(!supports_comment_delete(None));
+    }
 
-            // Load config and set CLI binary (auto-detects br→bd if no config exists)
-            let config = load_config();
-            log::info!("[startup] CLI binary: {}", config.cli_binary);
-            *CLI_BINARY.lock().unwrap() = config.cli_binary.clone();
+    #[test]
+    fn due_flag_requires_recent_bd() {
+        assert!(supports_due_flag(Some((CliClient::Bd, 0, 40, 0))));
+        assert!(!supports_due_flag(Some((CliClient::Bd, 0, 39, 2))));
+        assert!(supports_due_flag(Some((CliClient::Br, 0, 1, 30))));
+        assert!(!supports_due_flag(None));
+    }
 
-            // Check if CLI binary is accessible
-            // IMPORTANT: Run from /tmp to avoid bd auto-migrating projects in cwd
-            let binary = get_cli_binary();
-            match new_command(&binary)
-                .arg("--version")
-                .current_dir(std::env::temp_dir())
-                .env("PATH", get_extended_path())
-                .output()
-            {
-                Ok(output) if output.status.success() => {
-                    let version = String::from_utf8_lossy(&output.stdout);
-                    log::info!("[startup] {} found: {}", binary, version.trim());
-                }
-                Ok(output) => {
-                    log::warn!("[startup] {} command failed: {}", binary, String::from_utf8_lossy(&output.stderr));
-                }
-                Err(e) => {
-                    log::error!("[startup] {} not found or not executable: {}", binary, e);
-                }
-            }
+    #[test]
+    fn watch_debounce_is_clamped() {
+        assert_eq!(clamp_watch_debounce(0), 200);
+        assert_eq!(clamp_watch_debounce(199), 200);
+        assert_eq!(clamp_watch_debounce(750), 750);
+        assert_eq!(clamp_watch_debounce(60_000), 5000);
+    }
 
-            Ok(())
-        })
-        .invoke_handler(tauri::generate_handler![
-            bd_sync,
-            bd_repair_database,
-            bd_migrate_to_dolt,
-            bd_check_needs_migration,
-            bd_cleanup_stale_locks,
-            bd_check_changed,
-            bd_reset_mtime,
-            bd_poll_data,
-            bd_list,
-            bd_count,
-            bd_ready,
-            bd_status,
-            bd_show,
-            bd_create,
-            get_logging_enabled,
-            set_logging_enabled,
-            get_verbose_logging,
-            set_verbose_logging,
-            clear_logs,
-            export_logs,
-            read_logs,
-            get_log_path_string,
-            log_frontend,
-            get_bd_version,
-            check_bd_compatibility,
-            get_cli_binary_path,
-            set_cli_binary_path,
-            validate_cli_binary,
-            bd_update,
-            bd_close,
-            bd_search,
-            bd_label_add,
-            bd_label_remove,
-            bd_delete,
-            bd_comments_add,
-            bd_dep_add,
-            bd_dep_remove,
-            bd_dep_add_relation,
-            bd_dep_remove_relation,
-            bd_available_relation_types,
-            fs_exists,
-            fs_list,
-            check_for_updates,
-            check_for_updates_demo,
-            check_bd_cli_update,
-            download_and_install_update,
-            open_image_file,
-            read_image_file,
-            copy_file_to_attachments,
-            list_attachments,
-            delete_attachment,
-            read_text_file,
-            write_text_file,
-            purge_orphan_attachments,
-            check_refs_migration,
-            migrate_attachment_refs,
-            start_watching,
-            stop_watching,
-            get_watcher_status,
-            fetch_external_data,
-            check_external_health,
-            post_external_data,
-            delete_external_data,
-            patch_external_data,
-            launch_probe,
-        ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
-}
+    #[test]
+    fn create_with_discovered_from_links_the_source() {
+        let payload: CreatePayload =
+            serde_json::from_str(r#"{"title":"Crash on save","discoveredFrom":" app-7 "}"#).unwrap();
+        let args = create_args(&payload).unwrap();
+        assert_eq!(args, vec!["Crash on save", "--deps", "discovered-from:app-7"]);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let plain: CreatePayload = serde_json::from_str(r#"{"title":"Crash on save","discoveredFrom":""}"#).unwrap();
+        assert!(!create_args(&plain).unwrap().contains(&"--deps".to_string()));
 
-    fn minimal_issue_json(id: &str, title: &str) -> String {
-        format!(
-            r#"{{"id":"{}","title":"{}","description":null,"status":"open","priority":3,"issue_type":"task","owner":null,"assignee":null,"labels":[],"created_at":"2025-01-01T00:00:00Z","created_by":null,"updated_at":"2025-01-01T00:00:00Z","closed_at":null,"close_reason":null,"blocked_by":null,"blocks":null,"comments":null,"external_ref":null,"estimate":null,"design":null,"acceptance_criteria":null,"notes":null,"parent":null,"dependents":null,"dependencies":null,"dependency_count":null,"dependent_count":null,"metadata":null,"spec_id":null,"comment_count":null}}"#,
-            id, title
-        )
+        // The reloaded issue carries the relation
+        let issue = transform_issue(raw_issue_with("app-9", serde_json::json!({
+            "dependencies": [{ "id": "app-7", "dependency_type": "discovered-from" }],
+        })));
+        let relations = issue.relations.unwrap();
+        assert_eq!(relations.len(), 1);
+        assert_eq!(relations[0].id, "app-7");
+        assert_eq!(relations[0].relation_type, "discovered-from");
     }
 
     #[test]
-    fn parse_flat_array() {
-        let json = format!("[{}]", minimal_issue_json("abc-123", "Bug fix"));
-        let result = parse_issues_tolerant(&json, "test_flat");
-        assert!(result.is_ok());
-        let issues = result.unwrap();
-        assert_eq!(issues.len(), 1);
-        assert_eq!(issues[0].id, "abc-123");
-        assert_eq!(issues[0].title, "Bug fix");
+    fn backend_error_kinds_from_cli_messages() {
+        let kind = |message: &str| BackendError::from(message.to_string()).kind();
+        assert_eq!(kind("SCHEMA_MIGRATION_ERROR: Database schema is incompatible."), "schemaMigration");
+        assert_eq!(kind("Failed to execute bd: No such file or directory"), "io");
+        // CLI text is never keyword-matched: a missing database is not a missing issue
+        assert_eq!(kind("Error: database not found at .beads/beads.db"), "bd");
+        assert_eq!(kind("Error: database is locked"), "bd");
+        assert_eq!(kind("HAS_OPEN_CHILDREN:app-2: Issue has open children"), "bd");
+        assert_eq!(BackendError::NotFound("Issue not found: app-404".to_string()).to_string(), "Issue not found: app-404");
     }
 
     #[test]
-    fn parse_paginated_envelope() {
-        let json = format!(
-            r#"{{"issues":[{},{}],"total":2,"offset":0,"limit":50,"has_more":false}}"#,
-            minimal_issue_json("abc-123", "First"),
-            minimal_issue_json("def-456", "Second")
+    fn show_of_missing_issue_is_not_found() {
+        let err = found_or_not_found("app-404", None).unwrap_err();
+        assert_eq!(err.kind(), "notFound");
+        assert_eq!(
+            serde_json::to_value(&err).unwrap(),
+            serde_json::json!({ "kind": "notFound", "message": "Issue not found: app-404" })
         );
-        let result = parse_issues_tolerant(&json, "test_envelope");
-        assert!(result.is_ok());
-        let issues = result.unwrap();
-        assert_eq!(issues.len(), 2);
-        assert_eq!(issues[0].id, "abc-123");
-        assert_eq!(issues[1].id, "def-456");
+        let issue = issue_created_at("app-1", "2025-01-01T00:00:00Z");
+        assert_eq!(found_or_not_found("app-1", Some(issue)).unwrap().id, "app-1");
     }
 
     #[test]
-    fn parse_empty_flat_array() {
-        let result = parse_issues_tolerant("[]", "test_empty_flat");
-        assert!(result.is_ok());
-        assert!(result.unwrap().is_empty());
+    fn sync_summary_diffs_jsonl_before_and_after() {
+        let line = |id: &str, status: &str, updated: &str| {
+            format!(r#"{{"id":"{}","title":"t","status":"{}","updated_at":"{}"}}"#, id, status, updated)
+        };
+        let before = [
+            line("p-1", "open", "2025-01-01T00:00:00Z"),
+            line("p-2", "open", "2025-01-01T00:00:00Z"),
+            line("p-3", "in_progress", "2025-01-01T00:00:00Z"),
+            line("p-4", "open", "2025-01-01T00:00:00Z"),
+            line("p-5", "open", "2025-01-01T00:00:00Z"),
+        ].join("\n");
+        let after = [
+            line("p-1", "open", "2025-01-01T00:00:00Z"),
+            line("p-2", "open", "2025-01-02T00:00:00Z"),
+            line("p-3", "closed", "2025-01-02T00:00:00Z"),
+            line("p-5", "tombstone", "2025-01-02T00:00:00Z"),
+            line("p-6", "open", "2025-01-02T00:00:00Z"),
+            "not json".to_string(),
+        ].join("\n");
+
+        let summary = diff_jsonl_snapshots(&before, &after);
+        assert_eq!(summary.created, vec!["p-6"]);
+        assert_eq!(summary.updated, vec!["p-2"]);
+        assert_eq!(summary.closed, vec!["p-3"]);
+        assert_eq!(summary.deleted, vec!["p-4", "p-5"]);
+        assert_eq!(diff_jsonl_snapshots(&before, &before), SyncSummary::default());
     }
 
     #[test]
-    fn parse_empty_envelope() {
-        let json = r#"{"issues":[],"total":0,"offset":0,"limit":50,"has_more":false}"#;
-        let result = parse_issues_tolerant(json, "test_empty_envelope");
-        assert!(result.is_ok());
-        assert!(result.unwrap().is_empty());
+    fn migration_preview_reports_prefix_and_counts() {
+        let dir = test_dir("migrate-preview");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let long_ref = "x".repeat(120);
+        let jsonl = [
+            r#"{"id":"app-1","status":"open"}"#.to_string(),
+            r#"{"id":"app-2","status":"closed","external_ref":"gh-12\n.beads/attachments/2/a.png"}"#.to_string(),
+            format!(r#"{{"id":"app-3","status":"open","external_ref":"{}"}}"#, long_ref),
+            r#"{"id":"old-4","status":"open"}"#.to_string(),
+            r#"{"id":"app-5","status":"tombstone"}"#.to_string(),
+            "{broken".to_string(),
+        ].join("\n");
+        fs::write(dir.join("issues.jsonl"), &jsonl).unwrap();
+        fs::write(dir.join("beads.db"), "").unwrap();
+        fs::write(dir.join("beads.db-wal"), "").unwrap();
+
+        let preview = preview_migration(&dir).unwrap();
+        assert_eq!(preview, MigrationPreview {
+            prefix: Some("app".to_string()),
+            issue_count: 4,
+            tombstones: 1,
+            malformed: 1,
+            reprefixed: 1,
+            refs_to_truncate: 2,
+            files_to_backup: vec!["beads.db".to_string()],
+            files_to_remove: vec!["beads.db-wal".to_string()],
+        });
+        // Nothing was touched
+        assert_eq!(fs::read_to_string(dir.join("issues.jsonl")).unwrap(), jsonl);
+        assert!(dir.join("beads.db").exists());
+        let _ = fs::remove_dir_all(&dir);
     }
 
+    #[cfg(unix)]
     #[test]
-    fn parse_object_without_issues_key_fails() {
-        let json = r#"{"error":"something went wrong"}"#;
-        let result = parse_issues_tolerant(json, "test_bad_object");
-        assert!(result.is_err());
+    fn cli_info_cache_is_per_resolved_binary() {
+        use std::os::unix::fs::PermissionsExt;
+        let root = test_dir("cli-cache");
+        let (brew, manual) = (root.join("brew"), root.join("manual"));
+        let write_bd = |dir: &std::path::Path, version: &str| {
+            fs::create_dir_all(dir).unwrap();
+            let path = dir.join("bd");
+            fs::write(&path, format!("#!/bin/sh\necho 'bd version {}'\n", version)).unwrap();
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        };
+        write_bd(&brew, "0.49.6 (Homebrew)");
+        write_bd(&manual, "0.55.1");
+        let brew_path = brew.to_string_lossy().to_string();
+        let manual_path = manual.to_string_lossy().to_string();
+
+        // Same configured name, different binaries on PATH: no shared cache entry
+        assert_eq!(cli_client_info_for("bd", &brew_path), Some((CliClient::Bd, 0, 49, 6)));
+        assert_eq!(cli_client_info_for("bd", &manual_path), Some((CliClient::Bd, 0, 55, 1)));
+
+        // Cached: later changes to the binary aren't seen until its entry is dropped
+        write_bd(&brew, "0.50.0");
+        assert_eq!(cli_client_info_for("bd", &brew_path), Some((CliClient::Bd, 0, 49, 6)));
+        forget_cli_info("bd", &brew_path);
+        assert_eq!(cli_client_info_for("bd", &brew_path), Some((CliClient::Bd, 0, 50, 0)));
+        assert_eq!(cli_client_info_for("bd", &manual_path), Some((CliClient::Bd, 0, 55, 1)));
+
+        forget_cli_info("bd", &brew_path);
+        forget_cli_info("bd", &manual_path);
+        let _ = fs::remove_dir_all(&root);
     }
 
     #[test]
-    fn parse_invalid_json_fails() {
-        let result = parse_issues_tolerant("not json at all", "test_invalid");
-        assert!(result.is_err());
+    fn ready_since_is_last_blocker_close_or_creation() {
+        let issue = |id: &str, status: &str, closed_at: Option<&str>, blockers: &[&str]| {
+            let mut i = issue_created_at(id, "2025-01-01T00:00:00Z");
+            i.status = status.to_string();
+            i.closed_at = closed_at.map(str::to_string);
+            if !blockers.is_empty() {
+                i.blocked_by = Some(blockers.iter().map(|b| b.to_string()).collect());
+            }
+            i
+        };
+        let all = vec![
+            issue("old", "closed", Some("2025-01-05T00:00:00Z"), &[]),
+            issue("recent", "closed", Some("2025-01-09T12:00:00Z"), &[]),
+            issue("pending", "open", None, &[]),
+            issue("a", "open", None, &["old", "recent"]),
+            issue("b", "open", None, &[]),
+            issue("c", "open", None, &["recent", "pending"]),
+        ];
+        let mut issues: Vec<Issue> = all.iter().filter(|i| i.status == "open").cloned().collect();
+        // 2025-01-10T00:00:00Z
+        attach_ready_since(&mut issues, &all, 1_736_467_200);
+
+        let get = |id: &str| issues.iter().find(|i| i.id == id).unwrap();
+        assert_eq!(get("a").ready_since.as_deref(), Some("2025-01-09T12:00:00Z"));
+        assert_eq!(get("a").days_ready, Some(0));
+        assert_eq!(get("b").ready_since.as_deref(), Some("2025-01-01T00:00:00Z"));
+        assert_eq!(get("b").days_ready, Some(9));
+        assert_eq!(get("c").ready_since, None);
+        assert_eq!(get("c").days_ready, None);
     }
 
     #[test]
-    fn parse_real_br_envelope() {
-        // Matches the shape from br 0.1.30+ (`br list --json --limit 1`):
-        // br omits many optional fields (owner, assignee, labels, etc.) and includes extra
-        // fields (source_repo, compaction_level). serde_json defaults missing Option<T> to None
-        // and ignores unknown fields, so this parses correctly.
-        let json = r#"{"issues":[{"id":"proj-abc","title":"Example bug report","description":"A test description","status":"open","priority":2,"issue_type":"bug","created_at":"2025-06-15T09:30:00.000000000Z","updated_at":"2025-06-15T10:45:00.000000000Z","source_repo":".","compaction_level":0,"dependency_count":0,"dependent_count":0}],"total":1,"limit":1,"offset":0,"has_more":true}"#;
-        let result = parse_issues_tolerant(json, "test_real_br");
-        assert!(result.is_ok());
-        let issues = result.unwrap();
-        assert_eq!(issues.len(), 1);
-        assert_eq!(issues[0].id, "proj-abc");
-        assert_eq!(issues[0].issue_type, "bug");
-        assert_eq!(issues[0].priority, 2);
+    fn batch_payloads_share_defaults_and_skip_blank_titles() {
+        let defaults: CreatePayload = serde_json::from_str(
+            r#"{"title":"","type":"task","priority":"p1","labels":["import"],"parent":"p-epic"}"#,
+        ).unwrap();
+        let titles: Vec<String> = ["One", "  Two ", "", "Three", "Four", "   ", "Five"]
+            .iter().map(|t| t.to_string()).collect();
+        let payloads = batch_payloads(&titles, &defaults, Some("/repo"));
+
+        let indexed: Vec<(usize, &str)> = payloads.iter().map(|(i, p)| (*i, p.title.as_str())).collect();
+        assert_eq!(indexed, vec![(0, "One"), (1, "Two"), (3, "Three"), (4, "Four"), (6, "Five")]);
+        for (_, payload) in &payloads {
+            assert_eq!(payload.parent.as_deref(), Some("p-epic"));
+            assert_eq!(payload.cwd.as_deref(), Some("/repo"));
+            let args = create_args(payload).unwrap();
+            assert!(args.windows(2).any(|w| w == ["--parent", "p-epic"]), "{:?}", args);
+            assert!(args.windows(2).any(|w| w == ["--labels", "import"]), "{:?}", args);
+        }
     }
 
     #[test]
-    fn parse_envelope_skips_malformed_entries() {
-        let good = minimal_issue_json("abc-123", "Good");
-        let bad = r#"{"id":"bad-456","title":"Bad"}"#; // missing required fields
-        let json = format!(r#"{{"issues":[{},{}],"total":2,"offset":0,"limit":50,"has_more":false}}"#, good, bad);
-        let result = parse_issues_tolerant(&json, "test_tolerant_envelope");
-        assert!(result.is_ok());
-        let issues = result.unwrap();
-        assert_eq!(issues.len(), 1);
-        assert_eq!(issues[0].id, "abc-123");
+    fn type_change_of_issue_with_children_needs_a_parent_type() {
+        let mut epic = issue_created_at("p-epic", "2025-01-01T00:00:00Z");
+        epic.issue_type = "epic".to_string();
+        // bd >= 0.50: no children on the issue itself, only a dotted child in the list
+        let all = vec![epic.clone(), issue_created_at("p-epic.1", "2025-01-01T00:00:00Z")];
+        let children = children_in_list("p-epic", &all);
+
+        let err = check_type_change(&epic, &children, Some("bug")).unwrap_err();
+        assert!(err.starts_with("p-epic has 1 child issue(s) and a bug can't have children"), "{}", err);
+        assert!(check_type_change(&epic, &children, Some("feature")).is_ok());
+        assert!(check_type_change(&epic, &children, Some("epic")).is_ok());
+        assert!(check_type_change(&epic, &children, None).is_ok());
+
+        let mut leaf = issue_created_at("p-1", "2025-01-01T00:00:00Z");
+        leaf.issue_type = "bug".to_string();
+        assert!(check_type_change(&leaf, &[], Some("epic")).is_ok());
+        assert!(check_type_change(&leaf, &[], Some("chore")).is_ok());
+    }
+
+    #[test]
+    fn relation_type_is_validated_per_client() {
+        assert!(validate_relation_type("relates-to", Some(CliClient::Br)).is_ok());
+        assert!(validate_relation_type("tracks", Some(CliClient::Bd)).is_ok());
+        assert!(validate_relation_type("tracks", None).is_ok());
+
+        let err = validate_relation_type("relats-to", Some(CliClient::Bd)).unwrap_err();
+        assert!(err.starts_with("Unknown relation type 'relats-to'; valid: relates-to, related,"), "{}", err);
+        let err = validate_relation_type("tracks", Some(CliClient::Br)).unwrap_err();
+        assert!(!err.contains("tracks,") && err.ends_with("replies-to"), "{}", err);
+    }
+
+    #[test]
+    fn build_bd_args_appends_json_only_when_requested() {
+        let args = vec!["abc-1".to_string(), "hello".to_string()];
+        assert_eq!(
+            build_bd_args("comments add", &args, false, true),
+            vec!["comments", "add", "abc-1", "hello", "--json"]
+        );
+        assert_eq!(
+            build_bd_args("export", &[], true, false),
+            vec!["export", "--no-daemon"]
+        );
     }
 }
@@ -12,6 +12,7 @@ import {
   statusOrder,
   priorityOrder,
   typeOrder,
+  formatRelativeTime,
 } from '~/utils/issue-helpers'
 
 // ---------------------------------------------------------------------------
@@ -454,3 +455,29 @@ describe('computeReadyIssues', () => {
     expect(computeReadyIssues([])).toEqual([])
   })
 })
+
+describe('formatRelativeTime', () => {
+  const now = Date.parse('2025-06-15T12:00:00Z')
+
+  it('renders past and future distances', () => {
+    expect(formatRelativeTime('2025-06-15T10:00:00Z', now, 'en')).toBe('2 hours ago')
+    expect(formatRelativeTime('2025-06-12T12:00:00Z', now, 'en')).toBe('3 days ago')
+    expect(formatRelativeTime('2025-06-18T12:00:00Z', now, 'en')).toBe('in 3 days')
+    expect(formatRelativeTime('2023-06-15T12:00:00Z', now, 'en')).toBe('2 years ago')
+    expect(formatRelativeTime('2025-06-15T11:59:40Z', now, 'en')).toBe('now')
+  })
+
+  it('reads zone-less SQLite timestamps as UTC', () => {
+    expect(formatRelativeTime('2025-06-15 10:00:00', now, 'en')).toBe('2 hours ago')
+    expect(formatRelativeTime('2025-06-15T12:00:00+02:00', now, 'en')).toBe('2 hours ago')
+  })
+
+  it('follows the requested locale', () => {
+    expect(formatRelativeTime('2025-06-14T12:00:00Z', now, 'fr')).toBe('hier')
+  })
+
+  it('returns null for missing or invalid input', () => {
+    expect(formatRelativeTime(undefined, now)).toBeNull()
+    expect(formatRelativeTime('not a date', now)).toBeNull()
+  })
+})